@@ -5,19 +5,25 @@ use sunscreen::{
     bulletproofs::BulletproofsBackend, types::zkp::BulletproofsField, ZkpProgramFnExt,
 };
 
-use zkp::{default_list, allowlist};
+use zkp::{allowlist, default_list, default_tree};
 
 fn main() -> Result<()> {
     let prog = allowlist.compile::<BulletproofsBackend>()?;
     let runtime = allowlist.runtime::<BulletproofsBackend>()?;
 
     let entry: BulletproofsField = get_first_arg()?.unwrap_or(101).into();
-    let list: [BulletproofsField; 100] = default_list();
+    let list: Vec<BulletproofsField> = default_list();
+    let tree = default_tree();
+    let root: BulletproofsField = tree.root();
+
+    // If the entry isn't on the list, any path will do; the proof will fail.
+    let index = list.iter().position(|x| x.val == entry.val).unwrap_or(0);
 
     let proof = runtime
         .proof_builder(&prog)
         .private_input(entry)
-        .public_input(list)
+        .private_input(tree.path(index))
+        .public_input(root)
         .prove()?;
 
     bincode::serialize_into(io::stdout(), &proof)?;
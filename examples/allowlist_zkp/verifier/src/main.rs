@@ -5,7 +5,7 @@ use sunscreen::{
     bulletproofs::BulletproofsBackend, types::zkp::BulletproofsField, Proof, ZkpProgramFnExt,
 };
 
-use zkp::{default_list, allowlist};
+use zkp::{allowlist, default_tree};
 
 fn main() -> Result<()> {
     let prog = allowlist.compile::<BulletproofsBackend>()?;
@@ -13,12 +13,12 @@ fn main() -> Result<()> {
 
     let proof: Proof = bincode::deserialize_from(io::stdin())?;
 
-    let list: [BulletproofsField; 100] = default_list();
+    let root: BulletproofsField = default_tree().root();
 
     runtime
         .verification_builder(&prog)
         .proof(&proof)
-        .public_input(list)
+        .public_input(root)
         .verify()?;
 
     println!("Verified proof successfully!");
@@ -35,18 +35,20 @@ mod tests {
         let runtime = allowlist.runtime::<BulletproofsBackend>()?;
 
         let entry: BulletproofsField = 101.into();
-        let list: [BulletproofsField; 100] = default_list();
+        let tree = default_tree();
+        let root: BulletproofsField = tree.root();
 
         let proof = runtime
             .proof_builder(&prog)
             .private_input(entry)
-            .public_input(list)
+            .private_input(tree.path(1))
+            .public_input(root)
             .prove()?;
 
         runtime
             .verification_builder(&prog)
             .proof(&proof)
-            .public_input(list)
+            .public_input(root)
             .verify()?;
 
         Ok(())
@@ -1,20 +1,27 @@
-use std::array;
+use sunscreen::{
+    types::zkp::{Field, MerkleMembership, MerklePath, MerkleTree},
+    zkp_program, FieldSpec,
+};
 
-use sunscreen::{types::zkp::Field, zkp_program, zkp_var, FieldSpec};
+/// The depth of the allowlist's Merkle tree, which holds up to 2^20 entries.
+pub const DEPTH: usize = 20;
 
-/// A ZKP proving a private entry is equal to one of the values in a list.
+/// A ZKP proving a private entry is in the Merkle tree with a public root.
 #[zkp_program]
-pub fn allowlist<F: FieldSpec>(entry: Field<F>, #[public] list: [Field<F>; 100]) {
-    let zero = zkp_var!(0);
-    let one = zkp_var!(1);
-    let mut poly = one;
-    for x in list {
-        poly = poly * (x - entry);
-    }
-    poly.constrain_eq(zero);
+pub fn allowlist<F: FieldSpec>(
+    entry: Field<F>,
+    path: MerklePath<F, DEPTH>,
+    #[public] root: Field<F>,
+) {
+    path.constrain_membership(entry, root);
 }
 
 /// A default list for the prover and verifier to use: [100, 199]
-pub fn default_list<F: FieldSpec>() -> [Field<F>; 100] {
-    array::from_fn(|i| Field::from(100 + i as u32))
+pub fn default_list<F: FieldSpec>() -> Vec<Field<F>> {
+    (100..200u32).map(Field::from).collect()
+}
+
+/// The Merkle tree over the default list.
+pub fn default_tree<F: FieldSpec>() -> MerkleTree<F, DEPTH> {
+    MerkleTree::new(&default_list())
 }
@@ -0,0 +1,153 @@
+use std::time::Instant;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use petgraph::Direction;
+use sunscreen::{
+    types::zkp::{
+        poseidon_hash, poseidon_hash_native, poseidon_permutation, poseidon_permutation_native,
+        ConstrainEq, Field,
+    },
+    *,
+};
+use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, CompiledZkpProgram, Operation};
+
+type BPField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+/// Counts the constraints Bulletproofs will generate for the given program.
+///
+/// # Remarks
+/// Every 2 inputs share a multiplication gate, each multiplication of 2
+/// non-constant values needs a gate and each constraint is a linear
+/// constraint on the gates' wires.
+fn constraint_count(prog: &CompiledZkpProgram) -> usize {
+    let mut inputs = 0;
+    let mut count = 0;
+
+    for i in prog.node_indices() {
+        match prog[i].operation {
+            Operation::PrivateInput(_)
+            | Operation::PublicInput(_)
+            | Operation::ConstantInput(_)
+            | Operation::HiddenInput(_) => inputs += 1,
+            Operation::Constraint(_) => count += 1,
+            Operation::Mul => {
+                let has_constant = prog
+                    .neighbors_directed(i, Direction::Incoming)
+                    .any(|x| matches!(prog[x].operation, Operation::Constant(_)));
+
+                if !has_constant {
+                    count += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    count + (inputs + 1) / 2
+}
+
+fn prove_and_verify(
+    name: &str,
+    prog: &CompiledZkpProgram,
+    private: Vec<ZkpProgramInput>,
+    public: Vec<ZkpProgramInput>,
+) {
+    println!("{}: {} constraints", name, constraint_count(prog));
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let prover_time = Instant::now();
+
+    let proof = runtime
+        .prove(prog, private, public.clone(), vec![])
+        .unwrap();
+
+    println!("Prover time {}s", prover_time.elapsed().as_secs_f64());
+
+    let verifier_time = Instant::now();
+
+    runtime.verify(prog, &proof, public, vec![]).unwrap();
+
+    println!("Verifier time {}s", verifier_time.elapsed().as_secs_f64());
+
+    let proof_ser = bincode::serialize(&proof).unwrap();
+    println!("Proof size {}B", proof_ser.len());
+}
+
+fn inputs<const N: usize>() -> [BPField; N] {
+    (0..N)
+        .map(|x| BPField::from(x as u64))
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+/// Proves knowledge of a Poseidon permutation preimage.
+fn poseidon_permutation_proof(_c: &mut Criterion) {
+    #[zkp_program]
+    fn permutation<F: FieldSpec>(x: [Field<F>; 3], #[public] y: [Field<F>; 3]) {
+        let out = poseidon_permutation(x);
+
+        for (o, y) in out.iter().zip(y.iter()) {
+            o.constrain_eq(*y);
+        }
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(permutation)
+        .compile()
+        .unwrap();
+
+    let prog = app.get_zkp_program(permutation).unwrap();
+
+    let x = inputs::<3>();
+    let y = poseidon_permutation_native(x);
+
+    prove_and_verify("permutation", prog, vec![x.into()], vec![y.into()]);
+}
+
+/// Proves knowledge of the preimages of Poseidon hashes over a range of
+/// input lengths.
+fn poseidon_hash_proof(_c: &mut Criterion) {
+    #[zkp_program]
+    fn hash_2<F: FieldSpec>(x: [Field<F>; 2], #[public] h: Field<F>) {
+        poseidon_hash(&x).constrain_eq(h);
+    }
+
+    #[zkp_program]
+    fn hash_8<F: FieldSpec>(x: [Field<F>; 8], #[public] h: Field<F>) {
+        poseidon_hash(&x).constrain_eq(h);
+    }
+
+    #[zkp_program]
+    fn hash_32<F: FieldSpec>(x: [Field<F>; 32], #[public] h: Field<F>) {
+        poseidon_hash(&x).constrain_eq(h);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(hash_2)
+        .zkp_program(hash_8)
+        .zkp_program(hash_32)
+        .compile()
+        .unwrap();
+
+    let x = inputs::<2>();
+    let h = poseidon_hash_native(&x);
+    let prog = app.get_zkp_program(hash_2).unwrap();
+    prove_and_verify("hash_2", prog, vec![x.into()], vec![h.into()]);
+
+    let x = inputs::<8>();
+    let h = poseidon_hash_native(&x);
+    let prog = app.get_zkp_program(hash_8).unwrap();
+    prove_and_verify("hash_8", prog, vec![x.into()], vec![h.into()]);
+
+    let x = inputs::<32>();
+    let h = poseidon_hash_native(&x);
+    let prog = app.get_zkp_program(hash_32).unwrap();
+    prove_and_verify("hash_32", prog, vec![x.into()], vec![h.into()]);
+}
+
+criterion_group!(benches, poseidon_permutation_proof, poseidon_hash_proof);
+criterion_main!(benches);
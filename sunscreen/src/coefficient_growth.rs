@@ -0,0 +1,491 @@
+use crate::{Error, Params, Result};
+
+use petgraph::{stable_graph::NodeIndex, visit::Topo};
+use seal_fhe::Plaintext as SealPlaintext;
+use sunscreen_compiler_common::GraphQuery;
+use sunscreen_fhe_program::{FheProgram, Literal, Operation::*};
+use sunscreen_runtime::InnerPlaintext;
+
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/**
+ * A worst-case bound on the coefficients of a plaintext polynomial.
+ *
+ * # Remarks
+ * The bound describes the polynomial before reduction modulo `x^n + 1`,
+ * which lets multiplication track exactly which powers of `x` can be
+ * nonzero. Negative powers stand for the high coefficients digit encodings
+ * such as [`Fractional`](crate::types::bfv::Fractional) use, since
+ * `x^(n - k) = -x^-k`.
+ */
+pub(crate) struct CoefficientBound {
+    /**
+     * The lowest power of `x` that may have a nonzero coefficient.
+     */
+    min_power: i64,
+
+    /**
+     * The highest power of `x` that may have a nonzero coefficient.
+     */
+    max_power: i64,
+
+    /**
+     * The largest magnitude any coefficient may have. A magnitude of 0
+     * means the polynomial is 0.
+     */
+    magnitude: u128,
+}
+
+impl CoefficientBound {
+    fn new(powers: RangeInclusive<i64>, magnitude: u128) -> Self {
+        if powers.is_empty() || magnitude == 0 {
+            return Self::zero();
+        }
+
+        Self {
+            min_power: *powers.start(),
+            max_power: *powers.end(),
+            magnitude,
+        }
+    }
+
+    fn zero() -> Self {
+        Self {
+            min_power: 0,
+            max_power: 0,
+            magnitude: 0,
+        }
+    }
+
+    /**
+     * Bounds the given plaintext, interpreting coefficients in the upper half
+     * of the polynomial as negative powers of `x`.
+     */
+    fn from_plaintext(plaintext: &SealPlaintext, params: &Params) -> Self {
+        let n = params.lattice_dimension as i64;
+        let plain_modulus = params.plain_modulus;
+
+        let mut bound: Option<Self> = None;
+
+        for i in 0..plaintext.len() {
+            let coeff = plaintext.get_coefficient(i);
+
+            if coeff == 0 {
+                continue;
+            }
+
+            let magnitude = u64::min(coeff, plain_modulus - coeff) as u128;
+            let power = if (i as i64) < n / 2 {
+                i as i64
+            } else {
+                i as i64 - n
+            };
+
+            bound = Some(match bound {
+                Some(b) => Self {
+                    min_power: i64::min(b.min_power, power),
+                    max_power: i64::max(b.max_power, power),
+                    magnitude: u128::max(b.magnitude, magnitude),
+                },
+                None => Self::new(power..=power, magnitude),
+            });
+        }
+
+        bound.unwrap_or_else(Self::zero)
+    }
+
+    /**
+     * The number of powers of `x` that may have a nonzero coefficient.
+     */
+    fn terms(&self) -> u128 {
+        (self.max_power - self.min_power + 1) as u128
+    }
+
+    /**
+     * Bounds the sum or difference of two polynomials.
+     */
+    fn add(&self, other: &Self) -> Self {
+        if self.magnitude == 0 {
+            return *other;
+        } else if other.magnitude == 0 {
+            return *self;
+        }
+
+        let overlaps = self.min_power <= other.max_power && other.min_power <= self.max_power;
+
+        let magnitude = if overlaps {
+            self.magnitude.saturating_add(other.magnitude)
+        } else {
+            u128::max(self.magnitude, other.magnitude)
+        };
+
+        Self {
+            min_power: i64::min(self.min_power, other.min_power),
+            max_power: i64::max(self.max_power, other.max_power),
+            magnitude,
+        }
+    }
+
+    /**
+     * Bounds the product of two polynomials. Each coefficient of the
+     * product sums at most as many products of coefficients as the
+     * shorter operand has terms.
+     */
+    fn mul(&self, other: &Self) -> Self {
+        if self.magnitude == 0 || other.magnitude == 0 {
+            return Self::zero();
+        }
+
+        let magnitude = self
+            .magnitude
+            .saturating_mul(other.magnitude)
+            .saturating_mul(u128::min(self.terms(), other.terms()));
+
+        Self {
+            min_power: self.min_power.saturating_add(other.min_power),
+            max_power: self.max_power.saturating_add(other.max_power),
+            magnitude,
+        }
+    }
+
+    /**
+     * The largest magnitude a coefficient may have after reducing the
+     * polynomial modulo `x^n + 1`, where powers that differ by a multiple of
+     * `n` land on the same coefficient.
+     */
+    pub(crate) fn reduced_magnitude(&self, lattice_dimension: u64) -> u128 {
+        let aliases = self.terms().div_ceil(lattice_dimension as u128);
+
+        self.magnitude.saturating_mul(aliases)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/**
+ * Declares the largest values an [`fhe_program`](crate::fhe_program)
+ * argument may take.
+ *
+ * # Remarks
+ * When using [`PlainModulusConstraint::Automatic`](crate::PlainModulusConstraint::Automatic),
+ * the compiler propagates these bounds through each FHE program to find
+ * how large plaintext coefficients can grow and chooses the smallest plain
+ * modulus that holds them.
+ *
+ * Declare one bound per argument, in argument order. Values that exceed
+ * their declared bounds may overflow the chosen plain modulus.
+ */
+pub struct InputBound {
+    polynomials: Vec<CoefficientBound>,
+}
+
+impl InputBound {
+    /**
+     * A [`Signed`](crate::types::bfv::Signed) value whose magnitude is less
+     * than `2^bits`.
+     */
+    pub fn signed(bits: u32) -> Self {
+        Self::coefficients(0..=i64::max(bits as i64 - 1, 0), 1)
+    }
+
+    /**
+     * An [`Unsigned`](crate::types::bfv::Unsigned) value less than `2^bits`.
+     */
+    pub fn unsigned(bits: u32) -> Self {
+        Self::signed(bits)
+    }
+
+    /**
+     * A [`Fractional`](crate::types::bfv::Fractional) value whose magnitude
+     * is less than `2^int_bits` and which has at most `frac_bits` binary
+     * digits after the point.
+     */
+    pub fn fractional(int_bits: u32, frac_bits: u32) -> Self {
+        let min_power = -(frac_bits as i64);
+
+        Self::coefficients(min_power..=i64::max(int_bits as i64 - 1, min_power), 1)
+    }
+
+    /**
+     * A [`Rational`](crate::types::bfv::Rational) value whose numerator and
+     * denominator have magnitudes less than `2^numerator_bits` and
+     * `2^denominator_bits` respectively.
+     */
+    pub fn rational(numerator_bits: u32, denominator_bits: u32) -> Self {
+        Self {
+            polynomials: [numerator_bits, denominator_bits]
+                .iter()
+                .flat_map(|bits| Self::signed(*bits).polynomials)
+                .collect(),
+        }
+    }
+
+    /**
+     * A value encoded as a single polynomial whose nonzero coefficients lie
+     * at the given powers of `x` and have magnitude at most `magnitude`.
+     * Negative powers `-k` stand for the coefficient of `x^(n - k)`.
+     */
+    pub fn coefficients(powers: RangeInclusive<i64>, magnitude: u64) -> Self {
+        Self {
+            polynomials: vec![CoefficientBound::new(powers, magnitude as u128)],
+        }
+    }
+
+    /**
+     * Flattens the given argument bounds into one bound per input
+     * ciphertext or plaintext.
+     */
+    pub(crate) fn flatten(bounds: &[InputBound]) -> Vec<CoefficientBound> {
+        bounds
+            .iter()
+            .flat_map(|b| b.polynomials.iter().copied())
+            .collect()
+    }
+}
+
+/**
+ * Computes a worst-case bound on the plaintext coefficients at every node
+ * in the given [`FheProgram`]. The result is indexed by node; nodes that
+ * don't hold a polynomial (e.g. rotation amounts) have no bound.
+ *
+ * # Remarks
+ * `inputs` holds one bound for each input ciphertext or plaintext. The
+ * program's plaintext literals must be encoded under `params`.
+ *
+ * Returns [`Error::InputBoundsError`] if `inputs` doesn't cover every input
+ * and [`Error::Unsupported`] if the program uses batching operations, whose
+ * effect on coefficients can't be bounded.
+ *
+ * # Panics
+ * Panics if the FHE program is not well formed. You should call
+ * validate before using this function to ascertain this.
+ */
+pub(crate) fn coefficient_bounds(
+    ir: &FheProgram,
+    params: &Params,
+    inputs: &[CoefficientBound],
+) -> Result<Vec<Option<CoefficientBound>>> {
+    let graph = &ir.graph.0;
+    let query = GraphQuery::new(graph);
+
+    let mut bounds: Vec<Option<CoefficientBound>> = vec![None; graph.node_bound()];
+    let mut topo = Topo::new(graph);
+
+    while let Some(node_id) = topo.next(graph) {
+        let operand = |id: NodeIndex| {
+            bounds[id.index()].expect("Operand doesn't hold a plaintext polynomial.")
+        };
+
+        let bound = match &graph[node_id].operation {
+            InputCiphertext(i) | InputPlaintext(i) => Some(*inputs.get(*i).ok_or_else(|| {
+                Error::input_bounds_error(&format!(
+                    "no bound declared for input {}; {} input bounds were given",
+                    i,
+                    inputs.len()
+                ))
+            })?),
+            Literal(Literal::U64(_)) => None,
+            Literal(Literal::Plaintext(bytes)) => {
+                let plaintext = InnerPlaintext::from_bytes(bytes)?;
+                let plaintext = &plaintext.as_seal_plaintext()?[0].data;
+
+                Some(CoefficientBound::from_plaintext(plaintext, params))
+            }
+            Add | AddPlaintext | Sub | SubPlaintext => {
+                let (left, right) = query.get_binary_operands(node_id).unwrap();
+
+                Some(operand(left).add(&operand(right)))
+            }
+            Multiply | MultiplyPlaintext => {
+                let (left, right) = query.get_binary_operands(node_id).unwrap();
+
+                Some(operand(left).mul(&operand(right)))
+            }
+            Negate | Relinearize | OutputCiphertext => {
+                let x = query.get_unary_operand(node_id).unwrap();
+
+                Some(operand(x))
+            }
+            ShiftLeft | ShiftRight | SwapRows => {
+                return Err(Error::unsupported(
+                    "Can't bound coefficient growth through batching operations. Use PlainModulusConstraint::BatchingMinimum instead.",
+                ));
+            }
+        };
+
+        bounds[node_id.index()] = bound;
+    }
+
+    Ok(bounds)
+}
+
+/**
+ * Computes a worst-case bound on the plaintext coefficients of each of the
+ * given [`FheProgram`]'s outputs, in output order. See
+ * [`coefficient_bounds`].
+ */
+pub(crate) fn output_bounds(
+    ir: &FheProgram,
+    params: &Params,
+    inputs: &[CoefficientBound],
+) -> Result<Vec<CoefficientBound>> {
+    let bounds = coefficient_bounds(ir, params, inputs)?;
+
+    Ok(ir
+        .graph
+        .node_indices()
+        .filter(|id| matches!(ir.graph[*id].operation, OutputCiphertext))
+        .map(|id| bounds[id.index()].unwrap())
+        .collect())
+}
+
+/**
+ * Returns the smallest plain modulus under which coefficients with the
+ * given magnitude decode without error.
+ *
+ * # Remarks
+ * Decoding digit-encoded types reports
+ * [`PlaintextOverflow`](crate::RuntimeError::PlaintextOverflow) for digits
+ * within 1/8 of the plain modulus of wrapping, so the plain modulus must
+ * satisfy `3 * (p / 8) >= magnitude` rather than merely exceed twice the
+ * magnitude.
+ *
+ * Intermediate values don't need to fit; reduction modulo the plain
+ * modulus commutes with addition and multiplication, so only outputs
+ * determine correctness.
+ */
+pub(crate) fn required_plain_modulus(magnitude: u128) -> u128 {
+    let magnitude = u128::max(magnitude, 1);
+
+    8 * magnitude.div_ceil(3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        fhe::FheCompile,
+        fhe_program,
+        types::{bfv::Fractional, bfv::Rational, bfv::Signed, Cipher},
+        FheProgramFn, SchemeType, SecurityLevel,
+    };
+
+    // Needed to make the fhe_program macro work.
+    use crate as sunscreen;
+
+    fn params() -> Params {
+        Params {
+            lattice_dimension: 4096,
+            plain_modulus: 1 << 20,
+            coeff_modulus: vec![],
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn outputs<F: FheProgramFn>(program: F, inputs: &[InputBound]) -> Result<Vec<u128>> {
+        let params = params();
+        let ir = program.build(&params)?.compile();
+
+        Ok(output_bounds(&ir, &params, &InputBound::flatten(inputs))?
+            .iter()
+            .map(|b| b.reduced_magnitude(params.lattice_dimension))
+            .collect())
+    }
+
+    #[test]
+    fn bounds_signed_arithmetic() {
+        #[fhe_program(scheme = "bfv")]
+        fn mul(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+            a * b
+        }
+
+        #[fhe_program(scheme = "bfv")]
+        fn square_sum(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+            a * a + b * b
+        }
+
+        let bounds = [InputBound::signed(16), InputBound::signed(8)];
+
+        assert_eq!(outputs(mul, &bounds).unwrap(), vec![8]);
+        assert_eq!(outputs(square_sum, &bounds).unwrap(), vec![24]);
+    }
+
+    #[test]
+    fn bounds_literals() {
+        #[fhe_program(scheme = "bfv")]
+        fn scale(a: Cipher<Signed>) -> Cipher<Signed> {
+            a * Signed::from(-7) - Signed::from(5)
+        }
+
+        // -7 has 3 digits of magnitude 1, and 5 adds to the low digits.
+        assert_eq!(outputs(scale, &[InputBound::signed(4)]).unwrap(), vec![4]);
+    }
+
+    #[test]
+    fn bounds_fractional_without_aliasing() {
+        #[fhe_program(scheme = "bfv")]
+        fn mul(a: Cipher<Fractional<64>>, b: Cipher<Fractional<64>>) -> Cipher<Fractional<64>> {
+            a * b
+        }
+
+        let bounds = [InputBound::fractional(4, 20), InputBound::fractional(8, 10)];
+
+        assert_eq!(outputs(mul, &bounds).unwrap(), vec![18]);
+    }
+
+    #[test]
+    fn bounds_rational_components() {
+        #[fhe_program(scheme = "bfv")]
+        fn add(a: Cipher<Rational>, b: Cipher<Rational>) -> Cipher<Rational> {
+            a + b
+        }
+
+        let bounds = [InputBound::rational(8, 4), InputBound::rational(8, 4)];
+
+        // Numerator: a.num * b.den + b.num * a.den. Denominator: a.den * b.den.
+        assert_eq!(outputs(add, &bounds).unwrap(), vec![8, 4]);
+    }
+
+    #[test]
+    fn reduction_aliases_wide_polynomials() {
+        let bound = CoefficientBound::new(-3000..=3000, 5);
+
+        assert_eq!(bound.reduced_magnitude(4096), 10);
+        assert_eq!(bound.reduced_magnitude(8192), 5);
+    }
+
+    #[test]
+    fn disjoint_terms_dont_accumulate() {
+        let low = CoefficientBound::new(0..=3, 2);
+        let high = CoefficientBound::new(4..=7, 3);
+
+        assert_eq!(low.add(&high), CoefficientBound::new(0..=7, 3));
+        assert_eq!(low.add(&low), CoefficientBound::new(0..=3, 4));
+        assert_eq!(low.add(&CoefficientBound::zero()), low);
+        assert_eq!(low.mul(&CoefficientBound::zero()), CoefficientBound::zero());
+    }
+
+    #[test]
+    fn missing_bounds_is_error() {
+        #[fhe_program(scheme = "bfv")]
+        fn mul(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+            a * b
+        }
+
+        assert!(matches!(
+            outputs(mul, &[InputBound::signed(16)]),
+            Err(Error::InputBoundsError(_))
+        ));
+    }
+
+    #[test]
+    fn required_modulus_leaves_overflow_margin() {
+        for magnitude in 1..1000 {
+            let p = required_plain_modulus(magnitude);
+
+            assert!(3 * (p / 8) >= magnitude);
+            assert!(3 * ((p - 8) / 8) < magnitude);
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use crate::fhe::{FheCompile, FheFrontendCompilation};
 use crate::params::{determine_params, PlainModulusConstraint};
 use crate::{
-    zkp, Application, CallSignature, Error, FheProgramMetadata, Params, RequiredKeys, Result,
-    SchemeType, SecurityLevel, ZkpProgramFn,
+    zkp, Application, CallSignature, Error, FheProgramMetadata, InputBound, Params, RequiredKeys,
+    Result, SchemeType, SecurityLevel, ZkpProgramFn,
 };
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
@@ -137,6 +137,7 @@ struct FheCompilerData {
     fhe_program_fns: Vec<Box<dyn FheProgramFn>>,
     params_mode: ParamsMode,
     plain_modulus_constraint: PlainModulusConstraint,
+    input_bounds: HashMap<String, Vec<InputBound>>,
     security_level: SecurityLevel,
     noise_margin: u32,
 }
@@ -149,6 +150,7 @@ impl Default for FheCompilerData {
             // This default value is sufficient for doing 3 levels of 64-bit
             // multiplications
             plain_modulus_constraint: PlainModulusConstraint::Raw(262_144),
+            input_bounds: HashMap::new(),
             security_level: SecurityLevel::TC128,
             noise_margin: 20,
         }
@@ -357,6 +359,7 @@ impl<T, B> GenericCompiler<T, B> {
             ParamsMode::Search => determine_params(
                 &fhe_data.fhe_program_fns,
                 fhe_data.plain_modulus_constraint,
+                &fhe_data.input_bounds,
                 fhe_data.security_level,
                 fhe_data.noise_margin,
                 scheme,
@@ -593,14 +596,31 @@ where
 
     /**
      * Set the constraint the parameter search algorithm places on the plaintext modulus.
-     * You can either force the algorithm to use an exact value or any value that supports
-     * batching of at least n bits in length.
+     * You can either force the algorithm to use an exact value, any value that supports
+     * batching of at least n bits in length, or the smallest value that holds the outputs
+     * given the declared [`input_bounds`](Self::input_bounds).
      */
     pub fn plain_modulus_constraint(mut self, p: PlainModulusConstraint) -> Self {
         self.data.fhe_data_mut().plain_modulus_constraint = p;
         self
     }
 
+    /**
+     * Declare bounds on the arguments of the given FHE program, one per argument in
+     * argument order. [`PlainModulusConstraint::Automatic`] uses these to choose the
+     * plain modulus.
+     */
+    pub fn input_bounds<N>(mut self, fhe_program: N, bounds: &[InputBound]) -> Self
+    where
+        N: AsRef<str>,
+    {
+        self.data
+            .fhe_data_mut()
+            .input_bounds
+            .insert(fhe_program.as_ref().to_owned(), bounds.to_vec());
+        self
+    }
+
     /**
      * Don't use the parameter search algorithm, and instead explicitly set the scheme's parameters.
      * For expert use and may cause failures.
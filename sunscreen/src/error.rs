@@ -77,6 +77,33 @@ pub enum Error {
      */
     #[error("Unsupported: {0}")]
     Unsupported(Box<String>),
+
+    /**
+     * Failed to serialize or deserialize a saved [`Application`](crate::Application).
+     */
+    #[error("Serialization error: {0}")]
+    SerializationError(Box<String>),
+
+    /**
+     * A saved [`Application`](crate::Application) was produced by an incompatible
+     * version of Sunscreen or uses incompatible type versions.
+     */
+    #[error("Version mismatch: {0}")]
+    VersionMismatch(Box<String>),
+
+    /**
+     * The contents of a saved [`Application`](crate::Application) don't match the
+     * hash recorded in its header.
+     */
+    #[error("Application failed integrity check")]
+    IntegrityCheckFailed,
+
+    /**
+     * The [`InputBound`](crate::InputBound)s declared for an FHE program
+     * don't match its inputs.
+     */
+    #[error("Input bounds error: {0}")]
+    InputBoundsError(Box<String>),
 }
 
 const_assert!(std::mem::size_of::<Error>() <= 24);
@@ -88,6 +115,27 @@ impl Error {
     pub fn unsupported(msg: &str) -> Self {
         Self::Unsupported(Box::new(msg.to_owned()))
     }
+
+    /**
+     * Create an [`Error::SerializationError`]
+     */
+    pub fn serialization_error(msg: &str) -> Self {
+        Self::SerializationError(Box::new(msg.to_owned()))
+    }
+
+    /**
+     * Create an [`Error::VersionMismatch`]
+     */
+    pub fn version_mismatch(msg: &str) -> Self {
+        Self::VersionMismatch(Box::new(msg.to_owned()))
+    }
+
+    /**
+     * Create an [`Error::InputBoundsError`]
+     */
+    pub fn input_bounds_error(msg: &str) -> Self {
+        Self::InputBoundsError(Box::new(msg.to_owned()))
+    }
 }
 
 /**
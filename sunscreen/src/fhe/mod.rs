@@ -352,40 +352,45 @@ impl FheCompile for FheFrontendCompilation {
         let mut fhe_program = FheProgram::new(SchemeType::Bfv);
 
         let mapped_graph = self.0.map(
-            |id, n| match &n.operation {
-                FheOperation::Add => NodeInfo::new(FheProgramOperation::Add),
-                FheOperation::InputCiphertext => {
-                    // HACKHACK: Input nodes are always added first to the graph in the order
-                    // they're specified as function arguments. We should not depend on this.
-                    NodeInfo::new(FheProgramOperation::InputCiphertext(id.index()))
+            |id, n| {
+                let operation = match &n.operation {
+                    FheOperation::Add => FheProgramOperation::Add,
+                    FheOperation::InputCiphertext => {
+                        // HACKHACK: Input nodes are always added first to the graph in the order
+                        // they're specified as function arguments. We should not depend on this.
+                        FheProgramOperation::InputCiphertext(id.index())
+                    }
+                    FheOperation::InputPlaintext => {
+                        // HACKHACK: Input nodes are always added first to the graph in the order
+                        // they're specified as function arguments. We should not depend on this.
+                        FheProgramOperation::InputPlaintext(id.index())
+                    }
+                    FheOperation::Literal(Literal::U64(x)) => {
+                        FheProgramOperation::Literal(FheProgramLiteral::U64(*x))
+                    }
+                    FheOperation::Literal(Literal::Plaintext(x)) => {
+                        // It's okay to unwrap here because fhe_program compilation will
+                        // catch the panic and return a compilation error.
+                        FheProgramOperation::Literal(FheProgramLiteral::Plaintext(
+                            x.to_bytes().expect("Failed to serialize plaintext."),
+                        ))
+                    }
+                    FheOperation::Sub => FheProgramOperation::Sub,
+                    FheOperation::SubPlaintext => FheProgramOperation::SubPlaintext,
+                    FheOperation::Negate => FheProgramOperation::Negate,
+                    FheOperation::Multiply => FheProgramOperation::Multiply,
+                    FheOperation::MultiplyPlaintext => FheProgramOperation::MultiplyPlaintext,
+                    FheOperation::Output => FheProgramOperation::OutputCiphertext,
+                    FheOperation::RotateLeft => FheProgramOperation::ShiftLeft,
+                    FheOperation::RotateRight => FheProgramOperation::ShiftRight,
+                    FheOperation::SwapRows => FheProgramOperation::SwapRows,
+                    FheOperation::AddPlaintext => FheProgramOperation::AddPlaintext,
+                };
+
+                NodeInfo {
+                    operation,
+                    provenance: n.provenance.clone(),
                 }
-                FheOperation::InputPlaintext => {
-                    // HACKHACK: Input nodes are always added first to the graph in the order
-                    // they're specified as function arguments. We should not depend on this.
-                    NodeInfo::new(FheProgramOperation::InputPlaintext(id.index()))
-                }
-                FheOperation::Literal(Literal::U64(x)) => {
-                    NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::U64(*x)))
-                }
-                FheOperation::Literal(Literal::Plaintext(x)) => {
-                    // It's okay to unwrap here because fhe_program compilation will
-                    // catch the panic and return a compilation error.
-                    NodeInfo::new(FheProgramOperation::Literal(FheProgramLiteral::Plaintext(
-                        x.to_bytes().expect("Failed to serialize plaintext."),
-                    )))
-                }
-                FheOperation::Sub => NodeInfo::new(FheProgramOperation::Sub),
-                FheOperation::SubPlaintext => NodeInfo::new(FheProgramOperation::SubPlaintext),
-                FheOperation::Negate => NodeInfo::new(FheProgramOperation::Negate),
-                FheOperation::Multiply => NodeInfo::new(FheProgramOperation::Multiply),
-                FheOperation::MultiplyPlaintext => {
-                    NodeInfo::new(FheProgramOperation::MultiplyPlaintext)
-                }
-                FheOperation::Output => NodeInfo::new(FheProgramOperation::OutputCiphertext),
-                FheOperation::RotateLeft => NodeInfo::new(FheProgramOperation::ShiftLeft),
-                FheOperation::RotateRight => NodeInfo::new(FheProgramOperation::ShiftRight),
-                FheOperation::SwapRows => NodeInfo::new(FheProgramOperation::SwapRows),
-                FheOperation::AddPlaintext => NodeInfo::new(FheProgramOperation::AddPlaintext),
             },
             |_, e| match e {
                 EdgeInfo::Left => EdgeInfo::Left,
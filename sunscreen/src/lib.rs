@@ -36,6 +36,7 @@
 //! ```
 //!
 
+mod coefficient_growth;
 mod compiler;
 mod error;
 /**
@@ -44,6 +45,7 @@ mod error;
  */
 pub mod fhe;
 mod params;
+mod persistence;
 mod zkp;
 
 /**
@@ -64,13 +66,14 @@ pub mod types;
 use fhe::{FheOperation, Literal};
 use petgraph::stable_graph::StableGraph;
 use serde::{Deserialize, Serialize};
-use sunscreen_runtime::{marker, Fhe, FheZkp, Zkp};
+use sunscreen_runtime::{Fhe, FheZkp, Zkp};
 use sunscreen_zkp_backend::CompiledZkpProgram;
 
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::marker::PhantomData;
 
+pub use coefficient_growth::InputBound;
 pub use compiler::{Compiler, FheProgramFn, FheProgramFnExt, GenericCompiler};
 pub use error::{Error, Result};
 pub use params::PlainModulusConstraint;
@@ -78,10 +81,11 @@ pub use seal_fhe::Plaintext as SealPlaintext;
 pub use sunscreen_compiler_macros::*;
 pub use sunscreen_fhe_program::{SchemeType, SecurityLevel};
 pub use sunscreen_runtime::{
-    CallSignature, Ciphertext, CompiledFheProgram, Error as RuntimeError, FheProgramInput,
-    FheProgramInputTrait, FheProgramMetadata, FheRuntime, FheZkpRuntime, InnerCiphertext,
-    InnerPlaintext, Params, Plaintext, PrivateKey, ProofBuilder, PublicKey, RequiredKeys, Runtime,
-    VerificationBuilder, WithContext, ZkpProgramInput, ZkpRuntime,
+    marker, CallSignature, Ciphertext, CompiledFheProgram, Error as RuntimeError, FheProgramInput,
+    FheProgramInputTrait, FheProgramMetadata, FheRuntime, FheZkpRuntime, GenericRuntime,
+    InnerCiphertext, InnerPlaintext, Params, Plaintext, PrivateKey, ProofBuilder, PublicKey,
+    RequiredKeys, Runtime, TypedCiphertext, VerificationBuilder, WithContext, ZkpProgramInput,
+    ZkpRuntime,
 };
 #[cfg(feature = "bulletproofs")]
 pub use sunscreen_zkp_backend::bulletproofs;
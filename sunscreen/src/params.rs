@@ -1,4 +1,5 @@
-use crate::{fhe::FheCompile, Error, FheProgramFn, Result, SecurityLevel};
+use crate::coefficient_growth::{output_bounds, required_plain_modulus};
+use crate::{fhe::FheCompile, Error, FheProgramFn, InputBound, Result, SecurityLevel};
 
 use log::{debug, trace};
 
@@ -12,6 +13,8 @@ use sunscreen_backend::noise_model::{
 use sunscreen_fhe_program::{FheProgram, FheProgramTrait, Operation, SchemeType};
 pub use sunscreen_runtime::Params;
 
+use std::collections::HashMap;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /**
  * A constraint on the plaintext
@@ -32,10 +35,26 @@ pub enum PlainModulusConstraint {
      * You can set this to 0 if all your values will be `2^14-1` or less.
      */
     BatchingMinimum(u32),
+
+    /**
+     * Chooses the smallest plain modulus that holds every coefficient the FHE
+     * programs can output, given the [`InputBound`]s declared for their
+     * arguments with
+     * [`GenericCompiler::input_bounds`](crate::GenericCompiler::input_bounds).
+     *
+     * # Remarks
+     * Use this with digit-encoded types such as
+     * [`Signed`](crate::types::bfv::Signed),
+     * [`Fractional`](crate::types::bfv::Fractional), and
+     * [`Rational`](crate::types::bfv::Rational). FHE programs that use
+     * batching operations aren't supported.
+     */
+    Automatic,
 }
 
 const LATTICE_DIMENSIONS: &[u64] = &[1024, 2048, 4096, 8192, 16384, 32768];
 const BATCHING_MIN_BITS: &[u32] = &[14, 14, 16, 17, 17, 17];
+const MAX_PLAIN_MODULUS: u64 = (1 << 60) - 1;
 
 /**
  * Returns a plaintext modulus that satisfies the given
@@ -45,6 +64,10 @@ const BATCHING_MIN_BITS: &[u32] = &[14, 14, 16, 17, 17, 17];
  * Particularly with batching, the constraint may not be satisfiable with
  * the given lattice dimension. In such cases, this function returns
  * [`Error::UnsatisfiableConstraint`].
+ *
+ * [`PlainModulusConstraint::Automatic`] depends on the FHE programs, so
+ * this function returns [`Error::Unsupported`] for it; use
+ * [`automatic_plain_modulus`] instead.
  */
 fn plaintext_constraint_to_modulus(
     constraint: PlainModulusConstraint,
@@ -70,11 +93,105 @@ fn plaintext_constraint_to_modulus(
                 }
             }
         }
+        PlainModulusConstraint::Automatic => {
+            return Err(Error::unsupported(
+                "Automatic plain moduli depend on the FHE programs and their input bounds.",
+            ));
+        }
     };
 
     Ok(plaintext_modulus)
 }
 
+/**
+ * Returns the smallest plain modulus that holds every output coefficient
+ * of the given FHE programs under their declared input bounds and the given
+ * lattice dimension.
+ *
+ * # Remarks
+ * Returns `None` if the required plain modulus exceeds the largest one
+ * SEAL supports. Returns [`Error::InputBoundsError`] if any FHE program
+ * lacks input bounds and [`Error::Unsupported`] if any FHE program takes
+ * or returns batched types.
+ */
+fn automatic_plain_modulus(
+    fhe_program_fns: &[Box<dyn FheProgramFn>],
+    input_bounds: &HashMap<String, Vec<InputBound>>,
+    lattice_dimension_index: usize,
+    security_level: SecurityLevel,
+    scheme_type: SchemeType,
+) -> Result<Option<seal_fhe::Modulus>> {
+    let lattice_dimension = LATTICE_DIMENSIONS[lattice_dimension_index];
+    let coeff = CoefficientModulus::bfv_default(lattice_dimension, security_level)?;
+
+    // Literals encode negative digits relative to the plain modulus, so
+    // build under the largest one to tell them apart from positive digits.
+    let params = Params {
+        coeff_modulus: coeff.iter().map(|v| v.value()).collect(),
+        lattice_dimension,
+        plain_modulus: MAX_PLAIN_MODULUS,
+        security_level,
+        scheme_type,
+    };
+
+    let mut magnitude = 0u128;
+
+    for program in fhe_program_fns {
+        // Batched literals need a batching plain modulus, which we can't
+        // build under, so reject these programs before building them.
+        let signature = program.signature();
+
+        if signature
+            .arguments
+            .iter()
+            .chain(signature.returns.iter())
+            .any(|t| t.name.contains("Batched<"))
+        {
+            return Err(Error::unsupported(&format!(
+                "FHE program {} uses batched types, which automatic plain moduli don't support. Use PlainModulusConstraint::BatchingMinimum instead.",
+                program.name()
+            )));
+        }
+
+        let bounds = input_bounds.get(program.name()).ok_or_else(|| {
+            Error::input_bounds_error(&format!(
+                "no input bounds declared for FHE program {}",
+                program.name()
+            ))
+        })?;
+
+        let ir = program.build(&params)?.compile();
+        ir.validate().map_err(Error::FheProgramError)?;
+
+        // Chained programs consume their previous outputs.
+        let mut inputs = InputBound::flatten(bounds);
+
+        for _ in 0..program.chain_count() {
+            inputs = output_bounds(&ir, &params, &inputs)?;
+
+            magnitude = inputs
+                .iter()
+                .map(|b| b.reduced_magnitude(lattice_dimension))
+                .fold(magnitude, u128::max);
+        }
+    }
+
+    let plain_modulus = required_plain_modulus(magnitude);
+
+    trace!(
+        "Coefficients reach magnitude {} for n={}, requiring p={}",
+        magnitude,
+        lattice_dimension,
+        plain_modulus
+    );
+
+    if plain_modulus > MAX_PLAIN_MODULUS as u128 {
+        return Ok(None);
+    }
+
+    Ok(Some(PlainModulus::raw(plain_modulus as u64)?))
+}
+
 /**
  * Verifies the keys required by the fhe_program can be created
  * with the given parameter set.
@@ -119,6 +236,7 @@ fn can_make_required_keys(fhe_program: &FheProgram, params: &Params) -> Result<b
 pub fn determine_params(
     fhe_program_fns: &[Box<dyn FheProgramFn>],
     plaintext_constraint: PlainModulusConstraint,
+    input_bounds: &HashMap<String, Vec<InputBound>>,
     security_level: SecurityLevel,
     noise_margin_bits: u32,
     scheme_type: SchemeType,
@@ -126,11 +244,25 @@ pub fn determine_params(
     'params_loop: for (i, n) in LATTICE_DIMENSIONS.iter().enumerate() {
         // Select a plain modulus that meets needs of the passed
         // constraint.
-        let plaintext_modulus = match plaintext_constraint_to_modulus(plaintext_constraint, i) {
-            Ok(v) => v,
-            Err(_) => {
-                continue 'params_loop;
-            }
+        let plaintext_modulus = match plaintext_constraint {
+            PlainModulusConstraint::Automatic => match automatic_plain_modulus(
+                fhe_program_fns,
+                input_bounds,
+                i,
+                security_level,
+                scheme_type,
+            )? {
+                Some(v) => v,
+                None => {
+                    continue 'params_loop;
+                }
+            },
+            _ => match plaintext_constraint_to_modulus(plaintext_constraint, i) {
+                Ok(v) => v,
+                Err(_) => {
+                    continue 'params_loop;
+                }
+            },
         };
 
         // Tell SEAL to give us whatever modulus chain it finds suitable.
@@ -0,0 +1,359 @@
+use crate::{marker, Application, CompiledFheProgram, Error, FheProgramFn, Result};
+
+use bincode::Options;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Sha3_256};
+use sunscreen_fhe_program::FheProgramTrait;
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+
+/**
+ * Identifies a saved [`Application`].
+ */
+const MAGIC: &[u8; 8] = b"SUNSCRAP";
+
+/**
+ * The version of the saved [`Application`] layout. Bump this whenever
+ * [`ApplicationHeader`] or [`ApplicationContents`] change.
+ */
+const FORMAT_VERSION: u32 = 2;
+
+/**
+ * The largest [`ApplicationHeader`] [`load`](Application::load) accepts, in
+ * bytes.
+ */
+const MAX_HEADER_SIZE: u64 = 1 << 20;
+
+#[derive(Serialize, Deserialize)]
+struct ApplicationHeader {
+    format_version: u32,
+
+    /// The version of Sunscreen that saved the application.
+    crate_version: String,
+
+    /// The version of each type appearing in a program's call signature.
+    type_versions: BTreeMap<String, String>,
+
+    /// The SHA3-256 hash of the serialized [`ApplicationContents`].
+    content_hash: [u8; 32],
+}
+
+#[derive(Serialize, Deserialize)]
+struct ApplicationContents {
+    fhe_programs: BTreeMap<String, CompiledFheProgram>,
+}
+
+fn type_versions<'a, I>(programs: I) -> Result<BTreeMap<String, String>>
+where
+    I: Iterator<Item = &'a CompiledFheProgram>,
+{
+    let mut versions = BTreeMap::new();
+
+    for program in programs {
+        let signature = &program.metadata.signature;
+
+        for t in signature.arguments.iter().chain(signature.returns.iter()) {
+            let version = t.version.to_string();
+
+            match versions.insert(t.name.clone(), version.clone()) {
+                Some(existing) if existing != version => {
+                    return Err(Error::version_mismatch(&format!(
+                        "type {} appears with versions {} and {}",
+                        t.name, existing, version
+                    )));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(versions)
+}
+
+/**
+ * Checks the saved programs are exactly `expected` and that their call
+ * signatures, including the type versions recorded in the header, match.
+ */
+fn check_programs(
+    header: &ApplicationHeader,
+    contents: &ApplicationContents,
+    expected: &[&dyn FheProgramFn],
+) -> Result<()> {
+    let mut expected_names = expected.iter().map(|p| p.name()).collect::<Vec<_>>();
+    expected_names.sort_unstable();
+
+    if !contents
+        .fhe_programs
+        .keys()
+        .map(|k| k.as_str())
+        .eq(expected_names.iter().copied())
+    {
+        return Err(Error::version_mismatch(&format!(
+            "application contains FHE programs {:?}, expected {:?}",
+            contents.fhe_programs.keys().collect::<Vec<_>>(),
+            expected_names
+        )));
+    }
+
+    for program in expected {
+        let signature = program.signature();
+
+        for t in signature.arguments.iter().chain(signature.returns.iter()) {
+            match header.type_versions.get(&t.name) {
+                Some(saved) if *saved != t.version.to_string() => {
+                    return Err(Error::version_mismatch(&format!(
+                        "program {} was saved with {} version {}, but this is version {}",
+                        program.name(),
+                        t.name,
+                        saved,
+                        t.version
+                    )));
+                }
+                _ => {}
+            }
+        }
+
+        if contents.fhe_programs[program.name()].metadata.signature != signature {
+            return Err(Error::version_mismatch(&format!(
+                "program {}'s call signature doesn't match the saved program",
+                program.name()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * The options [`bincode::serialize`] uses, so limits can be added when
+ * deserializing.
+ */
+fn bincode_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+fn content_hash(contents: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(contents).into()
+}
+
+fn serialization_error<E: std::fmt::Display>(e: E) -> Error {
+    Error::serialization_error(&e.to_string())
+}
+
+impl<T> Application<T>
+where
+    T: marker::Fhe,
+{
+    /**
+     * Writes every [`CompiledFheProgram`] in this application, along with
+     * its metadata and [`Params`](crate::Params), to `writer`.
+     *
+     * The output begins with a header containing the Sunscreen version, the
+     * versions of all types in the programs' call signatures, and a hash of
+     * the contents. Use [`load`](Self::load) to restore the application
+     * without recompiling it.
+     *
+     * # Remarks
+     * ZKP programs can't currently be saved; calling this on an application
+     * containing any returns [`Error::Unsupported`].
+     */
+    pub fn save<W>(&self, mut writer: W) -> Result<()>
+    where
+        W: Write,
+    {
+        if !self.zkp_programs.is_empty() {
+            return Err(Error::unsupported(
+                "Saving applications containing ZKP programs",
+            ));
+        }
+
+        let contents = ApplicationContents {
+            fhe_programs: self
+                .fhe_programs
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        };
+
+        let contents = bincode::serialize(&contents).map_err(serialization_error)?;
+
+        let header = ApplicationHeader {
+            format_version: FORMAT_VERSION,
+            crate_version: env!("CARGO_PKG_VERSION").to_owned(),
+            type_versions: type_versions(self.fhe_programs.values())?,
+            content_hash: content_hash(&contents),
+        };
+
+        writer.write_all(MAGIC).map_err(serialization_error)?;
+        bincode::serialize_into(&mut writer, &header).map_err(serialization_error)?;
+        writer.write_all(&contents).map_err(serialization_error)?;
+
+        Ok(())
+    }
+
+    /**
+     * Reads an application previously written with [`save`](Self::save).
+     * `fhe_programs` are the FHE programs the application is expected to
+     * contain, as they're defined in the running code.
+     *
+     * # Remarks
+     * Returns [`Error::VersionMismatch`] if
+     * * the application was saved by a different version of Sunscreen,
+     * * it doesn't contain exactly the programs in `fhe_programs`, or
+     * * a saved program's call signature differs from the given
+     *   program's, including when the version of any type in it changed.
+     *
+     * Returns [`Error::IntegrityCheckFailed`] if the contents were
+     * modified. Each program is validated before being returned.
+     */
+    pub fn load<R>(mut reader: R, fhe_programs: &[&dyn FheProgramFn]) -> Result<Self>
+    where
+        R: Read,
+    {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic).map_err(serialization_error)?;
+
+        if &magic != MAGIC {
+            return Err(Error::serialization_error(
+                "input is not a saved Sunscreen application",
+            ));
+        }
+
+        // The header precedes the content hash check, so bound how much
+        // a malformed length prefix can make us allocate.
+        let header: ApplicationHeader = bincode_options()
+            .with_limit(MAX_HEADER_SIZE)
+            .deserialize_from(&mut reader)
+            .map_err(serialization_error)?;
+
+        if header.format_version != FORMAT_VERSION {
+            return Err(Error::version_mismatch(&format!(
+                "expected application format {}, found {}",
+                FORMAT_VERSION, header.format_version
+            )));
+        }
+
+        if header.crate_version != env!("CARGO_PKG_VERSION") {
+            return Err(Error::version_mismatch(&format!(
+                "application was saved by Sunscreen {}, but this is Sunscreen {}",
+                header.crate_version,
+                env!("CARGO_PKG_VERSION")
+            )));
+        }
+
+        let mut contents = vec![];
+        reader
+            .read_to_end(&mut contents)
+            .map_err(serialization_error)?;
+
+        if content_hash(&contents) != header.content_hash {
+            return Err(Error::IntegrityCheckFailed);
+        }
+
+        let contents: ApplicationContents = bincode_options()
+            .with_limit(contents.len() as u64)
+            .deserialize(&contents)
+            .map_err(serialization_error)?;
+
+        check_programs(&header, &contents, fhe_programs)?;
+
+        for program in contents.fhe_programs.values() {
+            program
+                .fhe_program_fn
+                .validate()
+                .map_err(Error::FheProgramError)?;
+        }
+
+        Self::new(contents.fhe_programs.into_iter().collect(), HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Needed to make the fhe_program macro work.
+    use crate::{
+        self as sunscreen,
+        types::{bfv::Signed, Cipher, TypeName},
+        Compiler, FheApplication, PlainModulusConstraint,
+    };
+    use sunscreen_compiler_macros::fhe_program;
+
+    #[fhe_program(scheme = "bfv")]
+    fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a + b
+    }
+
+    fn compile() -> FheApplication {
+        Compiler::new()
+            .fhe_program(add)
+            .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+            .compile()
+            .unwrap()
+    }
+
+    fn save(app: &FheApplication) -> Vec<u8> {
+        let mut bytes = vec![];
+        app.save(&mut bytes).unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn can_load_expected_programs() {
+        let bytes = save(&compile());
+
+        let loaded = FheApplication::load(bytes.as_slice(), &[&add]).unwrap();
+
+        assert!(loaded.get_fhe_program(add).is_some());
+    }
+
+    #[test]
+    fn rejects_changed_type_versions() {
+        let mut app = compile();
+
+        let signature = &mut app.fhe_programs.get_mut("add").unwrap().metadata.signature;
+
+        for t in signature
+            .arguments
+            .iter_mut()
+            .chain(signature.returns.iter_mut())
+        {
+            t.version = "0.0.1".parse().unwrap();
+        }
+
+        let bytes = save(&app);
+
+        let err = FheApplication::load(bytes.as_slice(), &[&add]).unwrap_err();
+
+        let signed = Signed::type_name();
+
+        assert!(matches!(err, Error::VersionMismatch(_)));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "Version mismatch: program add was saved with {} version 0.0.1, but this is version {}",
+                signed.name, signed.version
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_headers() {
+        let mut bytes = MAGIC.to_vec();
+
+        // A format version followed by a crate version claiming to be
+        // u64::MAX bytes long.
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(matches!(
+            FheApplication::load(bytes.as_slice(), &[&add]),
+            Err(Error::SerializationError(_))
+        ));
+    }
+}
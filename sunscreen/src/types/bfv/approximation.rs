@@ -0,0 +1,702 @@
+use crate::types::bfv::FractionalValue;
+
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+/**
+ * The cost of evaluating a [`PolynomialApproximation`] on a
+ * [`Fractional`](crate::types::bfv::Fractional) ciphertext.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PolynomialCost {
+    /**
+     * The number of ciphertext-ciphertext multiplications.
+     */
+    pub multiplications: usize,
+
+    /**
+     * The longest chain of sequential ciphertext-ciphertext
+     * multiplications. Each one consumes noise budget.
+     */
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/**
+ * A polynomial approximating a function over an interval, for evaluating
+ * nonlinear functions on encrypted
+ * [`Fractional`](crate::types::bfv::Fractional) values.
+ *
+ * # Remarks
+ * [`PolynomialApproximation::chebyshev`] samples the function at the
+ * Chebyshev nodes of the interval and fits the Chebyshev series through
+ * them. This is the least-squares fit over those nodes and is within a
+ * small factor of the best possible (minimax) polynomial of the same
+ * degree. The fit happens when the FHE program is built, so it costs
+ * nothing at runtime. Presets exist for common functions, such as
+ * [`PolynomialApproximation::sigmoid`].
+ *
+ * The polynomial is stored in powers of `t`, the input mapped affinely
+ * from the interval onto `[-1, 1]`. This keeps the coefficients small.
+ * Mapping the input costs only plaintext operations.
+ *
+ * [`PolynomialApproximation::evaluate`] uses the Paterson-Stockmeyer
+ * algorithm: it computes the baby-step powers `t, t^2, ..., t^k`, splits
+ * the polynomial into chunks of `k` coefficients that use only those
+ * powers and plaintext multiplications, then combines the chunks with
+ * the giant-step powers `t^k, t^(2k), t^(4k), ...`. A degree `d`
+ * polynomial costs about `2 * sqrt(d)` ciphertext multiplications rather
+ * than the `d` that Horner's method needs, with multiplicative depth
+ * about `log2(d) + 1`. Use [`PolynomialApproximation::cost`] for the
+ * exact figures.
+ *
+ * Inputs outside the interval produce meaningless results; polynomials
+ * diverge quickly outside the interval they were fit on.
+ *
+ * Like all [`Fractional`](crate::types::bfv::Fractional) arithmetic,
+ * evaluation is carryless, so every multiplication grows the polynomial
+ * coefficients and the plain modulus must be large enough to hold them.
+ * Growth is slowest when the inputs and the interval's scale factor
+ * `2 / (end - start)` have few set binary digits, such as intervals
+ * symmetric around 0 with power of 2 width.
+ */
+pub struct PolynomialApproximation {
+    domain: RangeInclusive<f64>,
+
+    /// The coefficient of `t^i`, where `t` is the input mapped onto
+    /// `[-1, 1]`.
+    coefficients: Vec<f64>,
+}
+
+impl PolynomialApproximation {
+    /**
+     * Fits a polynomial of the given `degree` to `f` over `domain`.
+     *
+     * # Panics
+     * If `domain` is empty or not finite, or `f` isn't finite at every
+     * sample point.
+     */
+    pub fn chebyshev<F>(f: F, domain: RangeInclusive<f64>, degree: usize) -> Self
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (a, b) = (*domain.start(), *domain.end());
+
+        assert!(
+            a.is_finite() && b.is_finite() && a < b,
+            "Approximation domain must be finite and nonempty"
+        );
+
+        let n = degree + 1;
+
+        let nodes = (0..n)
+            .map(|j| PI * (j as f64 + 0.5) / n as f64)
+            .collect::<Vec<_>>();
+
+        let samples = nodes
+            .iter()
+            .map(|theta| {
+                let y = f(0.5 * (b - a) * theta.cos() + 0.5 * (a + b));
+
+                assert!(y.is_finite(), "Approximated function must be finite");
+
+                y
+            })
+            .collect::<Vec<_>>();
+
+        // c_k = 2 / n * sum_j f(x_j) T_k(x_j), where T_k(cos(theta)) =
+        // cos(k * theta).
+        let mut chebyshev = (0..n)
+            .map(|k| {
+                2.0 / n as f64
+                    * nodes
+                        .iter()
+                        .zip(samples.iter())
+                        .map(|(theta, y)| y * (k as f64 * theta).cos())
+                        .sum::<f64>()
+            })
+            .collect::<Vec<_>>();
+
+        chebyshev[0] /= 2.0;
+
+        // Drop coefficients that are only rounding noise, such as the even
+        // coefficients of an odd function. Each zero coefficient that
+        // survives conversion saves a plaintext multiplication.
+        let scale = chebyshev.iter().map(|c| c.abs()).sum::<f64>();
+
+        for c in chebyshev.iter_mut() {
+            if c.abs() <= f64::EPSILON * scale {
+                *c = 0.0;
+            }
+        }
+
+        // Convert to powers of t with T_{k+1} = 2t T_k - T_{k-1}.
+        let mut coefficients = vec![0.0; n];
+        let mut t_prev = vec![0.0; n];
+        let mut t_cur = vec![0.0; n];
+        t_cur[0] = 1.0;
+
+        for (k, c) in chebyshev.iter().enumerate() {
+            for (coeff, t) in coefficients.iter_mut().zip(t_cur.iter()) {
+                *coeff += c * t;
+            }
+
+            if k + 1 < n {
+                let mut t_next = vec![0.0; n];
+
+                // T_1 = t, then the recurrence applies.
+                let factor = if k == 0 { 1.0 } else { 2.0 };
+
+                for i in 0..n - 1 {
+                    t_next[i + 1] = factor * t_cur[i];
+                }
+
+                for (next, prev) in t_next.iter_mut().zip(t_prev.iter()) {
+                    *next -= prev;
+                }
+
+                t_prev = std::mem::replace(&mut t_cur, t_next);
+            }
+        }
+
+        Self {
+            domain,
+            coefficients,
+        }
+    }
+
+    /**
+     * Approximates the logistic function `1 / (1 + e^-x)`.
+     */
+    pub fn sigmoid(domain: RangeInclusive<f64>, degree: usize) -> Self {
+        Self::chebyshev(|x| 1.0 / (1.0 + (-x).exp()), domain, degree)
+    }
+
+    /**
+     * Approximates `e^x`.
+     */
+    pub fn exp(domain: RangeInclusive<f64>, degree: usize) -> Self {
+        Self::chebyshev(f64::exp, domain, degree)
+    }
+
+    /**
+     * Approximates the natural logarithm.
+     *
+     * # Panics
+     * If `domain` contains values that aren't positive.
+     */
+    pub fn ln(domain: RangeInclusive<f64>, degree: usize) -> Self {
+        assert!(*domain.start() > 0.0, "ln requires a positive domain");
+
+        Self::chebyshev(f64::ln, domain, degree)
+    }
+
+    /**
+     * Approximates the square root.
+     *
+     * # Remarks
+     * The square root isn't smooth at 0, so domains that start at or near
+     * 0 converge slowly with degree.
+     *
+     * # Panics
+     * If `domain` contains negative values.
+     */
+    pub fn sqrt(domain: RangeInclusive<f64>, degree: usize) -> Self {
+        assert!(*domain.start() >= 0.0, "sqrt requires a nonnegative domain");
+
+        Self::chebyshev(f64::sqrt, domain, degree)
+    }
+
+    /**
+     * Approximates `1 / sqrt(x)`.
+     *
+     * # Panics
+     * If `domain` contains values that aren't positive.
+     */
+    pub fn inv_sqrt(domain: RangeInclusive<f64>, degree: usize) -> Self {
+        assert!(*domain.start() > 0.0, "inv_sqrt requires a positive domain");
+
+        Self::chebyshev(|x| 1.0 / x.sqrt(), domain, degree)
+    }
+
+    /**
+     * The interval this polynomial approximates its function over.
+     */
+    pub fn domain(&self) -> RangeInclusive<f64> {
+        self.domain.clone()
+    }
+
+    /**
+     * The degree of the polynomial.
+     */
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    /**
+     * Evaluates the polynomial on `x`.
+     *
+     * # Remarks
+     * Inside an [`fhe_program`](crate::fhe_program), this evaluates on a
+     * [`Fractional`](crate::types::bfv::Fractional) ciphertext with the
+     * Paterson-Stockmeyer algorithm. On a plaintext
+     * [`Fractional`](crate::types::bfv::Fractional), this computes the
+     * same result without encryption.
+     */
+    pub fn evaluate<const INT_BITS: usize, V>(&self, x: V) -> V
+    where
+        V: FractionalValue<INT_BITS>,
+    {
+        let (scale, offset) = self.input_map();
+
+        let mut t = x;
+
+        if scale != 1.0 {
+            t = t * scale;
+        }
+
+        if offset != 0.0 {
+            t = t + offset;
+        }
+
+        let mut ops = Direct::<INT_BITS>;
+        let k = self.baby_step_count();
+
+        match PatersonStockmeyer::new(&mut ops, t, k).eval(&self.coefficients) {
+            Some(Term::Value(y)) => y,
+            Some(Term::Constant(c)) => x * 0.0 + c,
+            None => x * 0.0,
+        }
+    }
+
+    /**
+     * Evaluates the polynomial on `x` in [`f64`] arithmetic.
+     */
+    pub fn evaluate_f64(&self, x: f64) -> f64 {
+        let (scale, offset) = self.input_map();
+        let t = x * scale + offset;
+
+        self.coefficients.iter().rev().fold(0.0, |y, c| y * t + c)
+    }
+
+    /**
+     * Returns the largest absolute difference between the polynomial and
+     * `f` over `samples` evenly spaced points spanning the domain.
+     */
+    pub fn max_error<F>(&self, f: F, samples: usize) -> f64
+    where
+        F: Fn(f64) -> f64,
+    {
+        let (a, b) = (*self.domain.start(), *self.domain.end());
+        let samples = samples.max(2);
+
+        (0..samples)
+            .map(|i| {
+                let x = a + (b - a) * i as f64 / (samples - 1) as f64;
+
+                (self.evaluate_f64(x) - f(x)).abs()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    /**
+     * Returns the cost of [`PolynomialApproximation::evaluate`] on a
+     * ciphertext without emitting any operations.
+     */
+    pub fn cost(&self) -> PolynomialCost {
+        Self::cost_with(&self.coefficients, self.baby_step_count())
+    }
+
+    /**
+     * The affine map `t = scale * x + offset` from the domain onto
+     * `[-1, 1]`.
+     */
+    fn input_map(&self) -> (f64, f64) {
+        let (a, b) = (*self.domain.start(), *self.domain.end());
+
+        (2.0 / (b - a), -(a + b) / (b - a))
+    }
+
+    fn cost_with(coefficients: &[f64], k: usize) -> PolynomialCost {
+        let mut ops = CostCounter::default();
+
+        let depth = match PatersonStockmeyer::new(&mut ops, 0, k).eval(coefficients) {
+            Some(Term::Value(depth)) => depth,
+            _ => 0,
+        };
+
+        PolynomialCost {
+            multiplications: ops.multiplications,
+            depth,
+        }
+    }
+
+    /**
+     * Chooses the baby-step count `k` with the fewest ciphertext
+     * multiplications, breaking ties by depth.
+     */
+    fn baby_step_count(&self) -> usize {
+        (1..=self.coefficients.len())
+            .min_by_key(|k| {
+                let cost = Self::cost_with(&self.coefficients, *k);
+
+                (cost.multiplications, cost.depth)
+            })
+            .unwrap()
+    }
+}
+
+/**
+ * Either a plaintext constant or a value derived from the input.
+ * Tracking constants separately avoids ciphertext multiplications by
+ * chunks of the polynomial that don't depend on the input.
+ */
+#[derive(Debug, Clone, Copy)]
+enum Term<V> {
+    Constant(f64),
+    Value(V),
+}
+
+/**
+ * The arithmetic [`PatersonStockmeyer`] performs.
+ */
+trait Ops<V: Copy> {
+    fn mul(&mut self, a: V, b: V) -> V;
+
+    fn add(&mut self, a: V, b: V) -> V;
+
+    fn mul_constant(&mut self, a: V, c: f64) -> V;
+
+    fn add_constant(&mut self, a: V, c: f64) -> V;
+}
+
+/**
+ * Performs the arithmetic on [`Fractional`](crate::types::bfv::Fractional)
+ * values.
+ */
+struct Direct<const INT_BITS: usize>;
+
+impl<const INT_BITS: usize, V> Ops<V> for Direct<INT_BITS>
+where
+    V: FractionalValue<INT_BITS>,
+{
+    fn mul(&mut self, a: V, b: V) -> V {
+        a * b
+    }
+
+    fn add(&mut self, a: V, b: V) -> V {
+        a + b
+    }
+
+    fn mul_constant(&mut self, a: V, c: f64) -> V {
+        a * c
+    }
+
+    fn add_constant(&mut self, a: V, c: f64) -> V {
+        a + c
+    }
+}
+
+/**
+ * Counts ciphertext multiplications, where each value is its
+ * multiplicative depth.
+ */
+#[derive(Default)]
+struct CostCounter {
+    multiplications: usize,
+}
+
+impl Ops<usize> for CostCounter {
+    fn mul(&mut self, a: usize, b: usize) -> usize {
+        self.multiplications += 1;
+
+        a.max(b) + 1
+    }
+
+    fn add(&mut self, a: usize, b: usize) -> usize {
+        a.max(b)
+    }
+
+    fn mul_constant(&mut self, a: usize, _c: f64) -> usize {
+        a
+    }
+
+    fn add_constant(&mut self, a: usize, _c: f64) -> usize {
+        a
+    }
+}
+
+struct PatersonStockmeyer<'a, V, O> {
+    ops: &'a mut O,
+
+    /// `baby_steps[i]` is `t^(i + 1)`, computed on demand.
+    baby_steps: Vec<Option<V>>,
+
+    /// `giant_steps[j]` is `t^(k * 2^j)`, computed on demand.
+    giant_steps: Vec<V>,
+
+    k: usize,
+}
+
+impl<'a, V, O> PatersonStockmeyer<'a, V, O>
+where
+    V: Copy,
+    O: Ops<V>,
+{
+    fn new(ops: &'a mut O, t: V, k: usize) -> Self {
+        let mut baby_steps = vec![None; k];
+        baby_steps[0] = Some(t);
+
+        Self {
+            ops,
+            baby_steps,
+            giant_steps: vec![],
+            k,
+        }
+    }
+
+    /**
+     * Returns `t^i` for `1 <= i <= k`, splitting `i` in half so the depth
+     * is `ceil(log2(i))`.
+     */
+    fn power(&mut self, i: usize) -> V {
+        if let Some(p) = self.baby_steps[i - 1] {
+            return p;
+        }
+
+        let a = self.power(i / 2);
+        let b = self.power(i - i / 2);
+        let p = self.ops.mul(a, b);
+
+        self.baby_steps[i - 1] = Some(p);
+
+        p
+    }
+
+    /**
+     * Returns `t^(k * 2^j)`.
+     */
+    fn giant_step(&mut self, j: usize) -> V {
+        if self.giant_steps.is_empty() {
+            let p = self.power(self.k);
+            self.giant_steps.push(p);
+        }
+
+        while self.giant_steps.len() <= j {
+            let p = *self.giant_steps.last().unwrap();
+            let p = self.ops.mul(p, p);
+
+            self.giant_steps.push(p);
+        }
+
+        self.giant_steps[j]
+    }
+
+    fn add(&mut self, a: Option<Term<V>>, b: Option<Term<V>>) -> Option<Term<V>> {
+        Some(match (a?, b?) {
+            (Term::Constant(a), Term::Constant(b)) => Term::Constant(a + b),
+            (Term::Constant(c), Term::Value(v)) | (Term::Value(v), Term::Constant(c)) => {
+                Term::Value(self.ops.add_constant(v, c))
+            }
+            (Term::Value(a), Term::Value(b)) => Term::Value(self.ops.add(a, b)),
+        })
+    }
+
+    /**
+     * Evaluates a chunk of at most `k` coefficients with only plaintext
+     * multiplications by the baby steps.
+     */
+    fn eval_chunk(&mut self, chunk: &[f64]) -> Option<Term<V>> {
+        let mut sum = None;
+
+        for (i, c) in chunk.iter().enumerate().filter(|(_, c)| **c != 0.0) {
+            let term = if i == 0 {
+                Term::Constant(*c)
+            } else {
+                let p = self.power(i);
+
+                Term::Value(self.ops.mul_constant(p, *c))
+            };
+
+            sum = match sum {
+                Some(_) => self.add(sum, Some(term)),
+                None => Some(term),
+            };
+        }
+
+        sum
+    }
+
+    /**
+     * Evaluates `coefficients`, splitting it into a low half and a high
+     * half whose first chunk starts at a giant step.
+     */
+    fn eval(&mut self, coefficients: &[f64]) -> Option<Term<V>> {
+        let chunks = coefficients.len().div_ceil(self.k);
+
+        if chunks <= 1 {
+            return self.eval_chunk(coefficients);
+        }
+
+        // The largest power of 2 below chunks.
+        let j = (usize::BITS - 1 - (chunks - 1).leading_zeros()) as usize;
+        let (low, high) = coefficients.split_at(self.k << j);
+
+        let low = self.eval(low);
+
+        let high = match self.eval(high) {
+            None => None,
+            Some(Term::Constant(c)) => {
+                let g = self.giant_step(j);
+
+                Some(Term::Value(self.ops.mul_constant(g, c)))
+            }
+            Some(Term::Value(v)) => {
+                let g = self.giant_step(j);
+
+                Some(Term::Value(self.ops.mul(v, g)))
+            }
+        };
+
+        match (low, high) {
+            (None, x) | (x, None) => x,
+            (low, high) => self.add(low, high),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::bfv::Fractional;
+
+    const SAMPLES: usize = 1001;
+
+    fn sigmoid(x: f64) -> f64 {
+        1.0 / (1.0 + (-x).exp())
+    }
+
+    #[test]
+    fn presets_are_accurate() {
+        type Case = (&'static str, PolynomialApproximation, fn(f64) -> f64, f64);
+
+        let cases: Vec<Case> = vec![
+            (
+                "sigmoid",
+                PolynomialApproximation::sigmoid(-8.0..=8.0, 15),
+                sigmoid,
+                2e-3,
+            ),
+            (
+                "exp",
+                PolynomialApproximation::exp(-2.0..=2.0, 10),
+                f64::exp,
+                1e-7,
+            ),
+            (
+                "ln",
+                PolynomialApproximation::ln(1.0..=4.0, 12),
+                f64::ln,
+                1e-6,
+            ),
+            (
+                "sqrt",
+                PolynomialApproximation::sqrt(1.0..=16.0, 16),
+                f64::sqrt,
+                2e-5,
+            ),
+            (
+                "inv_sqrt",
+                PolynomialApproximation::inv_sqrt(1.0..=16.0, 20),
+                |x| 1.0 / x.sqrt(),
+                2e-5,
+            ),
+        ];
+
+        for (name, approx, f, tolerance) in cases {
+            let error = approx.max_error(f, SAMPLES);
+
+            println!("{}: max error {:e}", name, error);
+
+            assert!(error < tolerance, "{} error {} too large", name, error);
+        }
+    }
+
+    #[test]
+    fn error_decreases_with_degree() {
+        let errors = [3, 7, 11, 15]
+            .iter()
+            .map(|d| PolynomialApproximation::exp(-1.0..=1.0, *d).max_error(f64::exp, SAMPLES))
+            .collect::<Vec<_>>();
+
+        for e in errors.windows(2) {
+            assert!(e[1] < e[0]);
+        }
+    }
+
+    #[test]
+    fn reproduces_polynomials_exactly() {
+        let f = |x: f64| 3.0 * x * x * x - 2.0 * x + 0.5;
+        let approx = PolynomialApproximation::chebyshev(f, 0.0..=5.0, 3);
+
+        assert!(approx.max_error(f, SAMPLES) < 1e-12);
+    }
+
+    #[test]
+    fn evaluate_matches_horner() {
+        for degree in 0..20 {
+            let approx = PolynomialApproximation::sigmoid(-4.0..=4.0, degree);
+
+            for x in [-4.0, -2.5, -0.75, 0.0, 1.25, 3.0, 4.0] {
+                let y = approx.evaluate(Fractional::<64>::from(x));
+
+                assert!((*y - approx.evaluate_f64(x)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn odd_functions_drop_even_coefficients() {
+        let approx = PolynomialApproximation::chebyshev(f64::sin, -1.0..=1.0, 9);
+
+        for (i, c) in approx.coefficients.iter().enumerate() {
+            assert_eq!(*c == 0.0, i % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn paterson_stockmeyer_beats_horner() {
+        for degree in [7, 15, 31] {
+            let cost = PolynomialApproximation::exp(-1.0..=1.0, degree).cost();
+
+            assert!(cost.multiplications < degree);
+            assert!(cost.depth <= (degree as f64).log2().ceil() as usize + 1);
+        }
+
+        let cost = PolynomialApproximation::exp(-1.0..=1.0, 15).cost();
+
+        assert_eq!(
+            cost,
+            PolynomialCost {
+                multiplications: 7,
+                depth: 4
+            }
+        );
+    }
+
+    #[test]
+    fn linear_and_constant_polynomials_need_no_multiplications() {
+        let linear = PolynomialApproximation::chebyshev(|x| 2.0 * x + 1.0, -1.0..=1.0, 1);
+        let constant = PolynomialApproximation::chebyshev(|_| 3.0, -1.0..=1.0, 0);
+
+        assert_eq!(linear.cost(), PolynomialCost::default());
+        assert_eq!(constant.cost(), PolynomialCost::default());
+        assert_eq!(*constant.evaluate(Fractional::<64>::from(0.5)), 3.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive domain")]
+    fn ln_rejects_nonpositive_domain() {
+        PolynomialApproximation::ln(0.0..=1.0, 4);
+    }
+}
@@ -3,8 +3,8 @@ use crate::{
     types::{
         intern::{Cipher, FheProgramNode},
         ops::*,
-        BfvType, FheType, LaneCount, NumCiphertexts, SwapRows, TryFromPlaintext, TryIntoPlaintext,
-        Type, TypeName, TypeNameInstance, Version,
+        BfvType, FheType, LaneCount, LaneOps, NumCiphertexts, Select, SwapRows, TryFromPlaintext,
+        TryIntoPlaintext, Type, TypeName, TypeNameInstance, Version,
     },
     FheProgramInputTrait, InnerPlaintext, Params, Plaintext, WithContext,
 };
@@ -53,6 +53,18 @@ use sunscreen_runtime::{Error as RuntimeError, Result as RuntimeResult};
  * For example, `[0, 1, 2, 3; 4, 5, 6, 7] >> 1` yields `[3, 0, 1, 2; 7, 4, 5, 6]`.
  * * `x.swap_rows()` swaps the rows. For example, `[0, 1, 2, 3; 4, 5, 6, 7].swap_rows()` yields `[4, 5, 6, 7; 0, 1, 2, 3]`.
  *
+ * Rotation amounts are taken modulo `LANES`, so `x << LANES` is `x`.
+ * To move lanes arbitrarily, such as transposing or gathering, use
+ * [`Batched::permute`].
+ *
+ * # Comparisons
+ * [`Batched::eq`], [`Batched::is_zero`], and [`Batched::lt_small_range`]
+ * compare lanes by evaluating polynomials modulo the plain modulus `p`,
+ * producing 1 in lanes where the comparison holds and 0 elsewhere. These
+ * require `p` to be prime, which any batching-compatible modulus is. Since
+ * [`Batched::eq`] multiplies `ceil(log2(p-1))` times in sequence, choose the
+ * smallest suitable prime, e.g. `PlainModulusConstraint::Raw(65537)`.
+ *
  * # Performance
  * The BFV scheme is parameterized by a number of values. Generally,
  * the polynomial degree has primacy in determining execution time.
@@ -402,10 +414,24 @@ impl<const LANES: usize> Neg for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> Select for Batched<LANES> {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            bit.data.iter().flatten().all(|x| *x == 0 || *x == 1),
+            "Selector lanes must be 0 or 1, got {}",
+            bit
+        );
+
+        b + bit * (a - b)
+    }
+}
+
 impl<const LANES: usize> Shl<u64> for Batched<LANES> {
     type Output = Self;
 
     fn shl(self, x: u64) -> Self::Output {
+        let x = x % LANES as u64;
+
         let r_0: [i64; LANES] = [
             self.data[0]
                 .iter()
@@ -446,6 +472,8 @@ impl<const LANES: usize> Shr<u64> for Batched<LANES> {
     type Output = Self;
 
     fn shr(self, x: u64) -> Self::Output {
+        let x = x % LANES as u64;
+
         let r_0: [i64; LANES] = [
             self.data[0]
                 .iter()
@@ -559,12 +587,7 @@ impl<const LANES: usize> GraphCipherInsert for Batched<LANES> {
     type Val = Self;
 
     fn graph_cipher_insert(lit: Self::Lit) -> FheProgramNode<Self::Val> {
-        with_fhe_ctx(|ctx| {
-            let lit = Self::from(lit).try_into_plaintext(&ctx.data).unwrap();
-            let l = ctx.add_plaintext_literal(lit.inner);
-
-            FheProgramNode::new(&[l])
-        })
+        Self::from(lit).graph_insert()
     }
 }
 
@@ -585,6 +608,107 @@ impl<const LANES: usize> GraphCipherConstMul for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> GraphCipherPlainMul for Batched<LANES> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_cipher_plain_mul(
+        a: FheProgramNode<Cipher<Self::Left>>,
+        b: FheProgramNode<Self::Right>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_multiplication_plaintext(a.ids[0], b.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LANES: usize> Batched<LANES> {
+    /**
+     * Adds this value to the current FHE program as a plaintext literal.
+     */
+    pub(crate) fn graph_insert(&self) -> FheProgramNode<Self> {
+        with_fhe_ctx(|ctx| {
+            let lit = self.try_into_plaintext(&ctx.data).unwrap();
+            let l = ctx.add_plaintext_literal(lit.inner);
+
+            FheProgramNode::new(&[l])
+        })
+    }
+
+    /**
+     * A mask with 1 in lane `lane` of both rows and 0 elsewhere.
+     */
+    fn lane_mask(lane: usize) -> Self {
+        assert!(lane < LANES, "Lane {} out of range [0, {})", lane, LANES);
+
+        let mut data = [[0; LANES]; 2];
+        data[0][lane] = 1;
+        data[1][lane] = 1;
+
+        Self { data }
+    }
+}
+
+fn sum_lanes<T>(x: T) -> T
+where
+    T: Copy + Add<Output = T> + Shl<u64, Output = T> + LaneCount,
+{
+    let mut x = x;
+    let mut step = 1;
+
+    while step < T::lane_count() {
+        x = x + (x << step as u64);
+        step *= 2;
+    }
+
+    x
+}
+
+fn sum_rows<T>(x: T) -> T
+where
+    T: Copy + Add<Output = T> + SwapRows<Output = T>,
+{
+    x + x.swap_rows()
+}
+
+impl<const LANES: usize> LaneOps for Batched<LANES> {
+    fn sum_lanes(self) -> Self {
+        sum_lanes(self)
+    }
+
+    fn sum_rows(self) -> Self {
+        sum_rows(self)
+    }
+
+    fn broadcast_lane(self, lane: usize) -> Self {
+        sum_lanes(self * Self::lane_mask(lane))
+    }
+
+    fn dot(self, other: &Self) -> Self {
+        sum_rows(sum_lanes(self * *other))
+    }
+}
+
+impl<const LANES: usize> LaneOps for FheProgramNode<Cipher<Batched<LANES>>> {
+    fn sum_lanes(self) -> Self {
+        sum_lanes(self)
+    }
+
+    fn sum_rows(self) -> Self {
+        sum_rows(self)
+    }
+
+    fn broadcast_lane(self, lane: usize) -> Self {
+        sum_lanes(self * Batched::lane_mask(lane).graph_insert())
+    }
+
+    fn dot(self, other: &Self) -> Self {
+        sum_rows(sum_lanes(self * *other))
+    }
+}
+
 impl<const LANES: usize> GraphCipherSwapRows for Batched<LANES> {
     fn graph_cipher_swap_rows(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>> {
         with_fhe_ctx(|ctx| {
@@ -601,7 +725,7 @@ impl<const LANES: usize> GraphCipherRotateLeft for Batched<LANES> {
         y: u64,
     ) -> FheProgramNode<Cipher<Self>> {
         with_fhe_ctx(|ctx| {
-            let y = ctx.add_literal(Literal::U64(y));
+            let y = ctx.add_literal(Literal::U64(y % LANES as u64));
             let n = ctx.add_rotate_left(x.ids[0], y);
 
             FheProgramNode::new(&[n])
@@ -615,7 +739,7 @@ impl<const LANES: usize> GraphCipherRotateRight for Batched<LANES> {
         y: u64,
     ) -> FheProgramNode<Cipher<Self>> {
         with_fhe_ctx(|ctx| {
-            let y = ctx.add_literal(Literal::U64(y));
+            let y = ctx.add_literal(Literal::U64(y % LANES as u64));
             let n = ctx.add_rotate_right(x.ids[0], y);
 
             FheProgramNode::new(&[n])
@@ -635,6 +759,269 @@ impl<const LANES: usize> GraphCipherNeg for Batched<LANES> {
     }
 }
 
+impl<const LANES: usize> Batched<LANES> {
+    /**
+     * Returns a ciphertext with 1 in every lane where `x` is zero and 0
+     * in every other lane.
+     *
+     * # Remarks
+     * This computes `1 - x^(p-1)`, where `p` is the plain modulus. By
+     * Fermat's little theorem, `x^(p-1)` is 1 for every nonzero lane, so
+     * `p` *must* be prime. Any batching-compatible modulus is prime, so
+     * both [`PlainModulusConstraint::BatchingMinimum`](crate::PlainModulusConstraint::BatchingMinimum)
+     * and a [`PlainModulusConstraint::Raw`](crate::PlainModulusConstraint::Raw) prime congruent
+     * to 1 mod twice the lattice dimension work.
+     *
+     * The exponentiation uses `ceil(log2(p-1))` sequential multiplications,
+     * which dominates the FHE program's noise budget. Prefer primes where
+     * `p-1` is small and has few set bits. `Raw(65537)` needs exactly 16
+     * squarings and supports batching for every lattice dimension.
+     */
+    pub fn is_zero(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>> {
+        let p = FheProgramNode::<Cipher<Self>>::get_plain_modulus();
+
+        let x_pow = pow(x, p - 1);
+        let one = Self::graph_cipher_insert(1);
+
+        with_fhe_ctx(|ctx| {
+            let neg = ctx.add_negate(x_pow.ids[0]);
+            let n = ctx.add_addition_plaintext(neg, one.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+
+    /**
+     * Returns a ciphertext with 1 in every lane where `a` and `b` are
+     * equal and 0 in every other lane.
+     *
+     * # Remarks
+     * This is [`Batched::is_zero`] applied to `a - b`; the same
+     * constraints on the plain modulus apply.
+     */
+    pub fn eq(
+        a: FheProgramNode<Cipher<Self>>,
+        b: FheProgramNode<Cipher<Self>>,
+    ) -> FheProgramNode<Cipher<Self>> {
+        Self::is_zero(a - b)
+    }
+
+    /**
+     * Returns a ciphertext with 1 in every lane where `a < b` and 0 in
+     * every other lane. Each lane must satisfy `|a - b| < range`;
+     * lanes outside this range produce meaningless results.
+     *
+     * # Remarks
+     * This evaluates the degree `2 * range - 2` polynomial that
+     * interpolates the comparison over every difference in
+     * `(-range, range)` modulo the plain modulus `p`. The plain modulus
+     * must be prime (see [`Batched::is_zero`]) and at least
+     * `2 * range - 1`. The evaluation uses `ceil(log2(2 * range - 2))`
+     * sequential multiplications, so this is far cheaper than
+     * [`Batched::is_zero`] when `range` is small.
+     *
+     * # Panics
+     * If `range < 2` or `2 * range - 1` exceeds the plain modulus.
+     */
+    pub fn lt_small_range(
+        a: FheProgramNode<Cipher<Self>>,
+        b: FheProgramNode<Cipher<Self>>,
+        range: u64,
+    ) -> FheProgramNode<Cipher<Self>> {
+        let p = FheProgramNode::<Cipher<Self>>::get_plain_modulus();
+
+        assert!(range >= 2, "lt_small_range requires range >= 2");
+        assert!(
+            2 * range - 1 <= p,
+            "lt_small_range requires 2 * range - 1 <= plain modulus"
+        );
+
+        let coeffs = lt_polynomial(range, p);
+        let d = a - b;
+        let powers = powers(d, coeffs.len() - 1);
+
+        let mut sum: Option<FheProgramNode<Cipher<Self>>> = None;
+
+        for (c, x) in coeffs.iter().skip(1).zip(powers.iter()) {
+            if *c == 0 {
+                continue;
+            }
+
+            let term = *x * to_signed(*c, p);
+
+            sum = Some(match sum {
+                Some(s) => s + term,
+                None => term,
+            });
+        }
+
+        // f is nonconstant for range >= 2, so some non-constant term
+        // is nonzero.
+        let sum = sum.unwrap();
+
+        if coeffs[0] == 0 {
+            return sum;
+        }
+
+        let c_0 = Self::graph_cipher_insert(to_signed(coeffs[0], p));
+
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_addition_plaintext(sum.ids[0], c_0.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+/**
+ * Computes `x^e` with minimal multiplicative depth by repeatedly
+ * multiplying the two shallowest of the needed `x^(2^i)` terms.
+ */
+fn pow<const LANES: usize>(
+    x: FheProgramNode<Cipher<Batched<LANES>>>,
+    e: u64,
+) -> FheProgramNode<Cipher<Batched<LANES>>> {
+    assert!(e > 0);
+
+    let mut terms = vec![];
+    let mut square = x;
+
+    for i in 0..(u64::BITS - e.leading_zeros()) {
+        if i > 0 {
+            square = square * square;
+        }
+
+        if e & (1 << i) != 0 {
+            terms.push((i, square));
+        }
+    }
+
+    while terms.len() > 1 {
+        terms.sort_by_key(|(depth, _)| std::cmp::Reverse(*depth));
+
+        let (d_0, a) = terms.pop().unwrap();
+        let (d_1, b) = terms.pop().unwrap();
+
+        terms.push((u32::max(d_0, d_1) + 1, a * b));
+    }
+
+    terms[0].1
+}
+
+/**
+ * Computes `[x, x^2, ..., x^n]`, where each `x^k` has the minimal
+ * multiplicative depth `ceil(log2(k))`.
+ */
+fn powers<const LANES: usize>(
+    x: FheProgramNode<Cipher<Batched<LANES>>>,
+    n: usize,
+) -> Vec<FheProgramNode<Cipher<Batched<LANES>>>> {
+    let mut powers = vec![x];
+
+    for k in 2..=n {
+        // Split k into its highest power of 2 and the remainder.
+        let hi = 1 << (usize::BITS - 1 - k.leading_zeros());
+        let lo = k - hi;
+
+        let x_k = if lo == 0 {
+            powers[hi / 2 - 1] * powers[hi / 2 - 1]
+        } else {
+            powers[hi - 1] * powers[lo - 1]
+        };
+
+        powers.push(x_k);
+    }
+
+    powers
+}
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    ((a as u128 * b as u128) % p as u128) as u64
+}
+
+fn inv_mod(a: u64, p: u64) -> u64 {
+    // p is prime, so a^(p-2) = a^-1.
+    let mut result = 1;
+    let mut base = a % p;
+    let mut e = p - 2;
+
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, base, p);
+        }
+
+        base = mul_mod(base, base, p);
+        e >>= 1;
+    }
+
+    result
+}
+
+/**
+ * Maps `c` in `[0, p)` to its representative in `(-p/2, p/2]` for
+ * encoding.
+ */
+fn to_signed(c: u64, p: u64) -> i64 {
+    if c > p / 2 {
+        c as i64 - p as i64
+    } else {
+        c as i64
+    }
+}
+
+/**
+ * Returns the coefficients (lowest degree first) of the polynomial over
+ * `Z_p` that maps each `d` in `(-range, range)` to 1 if `d < 0` and 0
+ * otherwise.
+ */
+fn lt_polynomial(range: u64, p: u64) -> Vec<u64> {
+    let points = (1..range)
+        .map(|d| p - d)
+        .chain(0..range)
+        .collect::<Vec<_>>();
+    let n = points.len();
+
+    // m(x) = prod_i (x - x_i)
+    let mut m = vec![0u64; n + 1];
+    m[0] = 1;
+
+    for (i, x_i) in points.iter().enumerate() {
+        for j in (0..=i + 1).rev() {
+            let shifted = if j > 0 { m[j - 1] } else { 0 };
+            let scaled = mul_mod(m[j], p - x_i, p);
+
+            m[j] = (shifted + scaled) % p;
+        }
+    }
+
+    let mut coeffs = vec![0u64; n];
+
+    // Only the negative differences (the first range - 1 points) have
+    // nonzero outputs.
+    for x_i in points.iter().take(range as usize - 1) {
+        // q(x) = m(x) / (x - x_i) via synthetic division.
+        let mut q = vec![0u64; n];
+        let mut carry = 0;
+
+        for (j, q_j) in q.iter_mut().enumerate().rev() {
+            carry = (m[j + 1] + mul_mod(carry, *x_i, p)) % p;
+            *q_j = carry;
+        }
+
+        let denom = q
+            .iter()
+            .rev()
+            .fold(0, |acc, c| (mul_mod(acc, *x_i, p) + c) % p);
+        let scale = inv_mod(denom, p);
+
+        for (c, q_j) in coeffs.iter_mut().zip(q.iter()) {
+            *c = (*c + mul_mod(*q_j, scale, p)) % p;
+        }
+    }
+
+    coeffs
+}
+
 impl<const LANES: usize> LaneCount for Batched<LANES> {
     fn lane_count() -> usize {
         LANES
@@ -719,10 +1106,105 @@ mod tests {
         assert_eq!(a >> 3, [[2, 3, 4, 1], [6, 7, 8, 5]].into());
     }
 
+    #[test]
+    fn rotations_wrap_around_lanes() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a << 4, a);
+        assert_eq!(a >> 7, a >> 3);
+        assert_eq!(a << 9, a << 1);
+    }
+
     #[test]
     fn can_swap_rows_non_fhe() {
         let a = Batched::<4>::try_from(A_VEC).unwrap();
 
         assert_eq!(a.swap_rows(), [[5, 6, 7, 8], [1, 2, 3, 4]].into());
     }
+
+    #[test]
+    fn can_select_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+        let b = Batched::<4>::try_from(B_VEC).unwrap();
+        let mask = Batched::<4>::from([[1, 0, 1, 0], [0, 0, 1, 1]]);
+
+        assert_eq!(
+            Batched::select(mask, a, b),
+            [[1, 6, 3, 8], [1, 2, 7, 8]].into()
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Selector lanes must be 0 or 1")]
+    fn select_non_fhe_rejects_invalid_mask() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+        let b = Batched::<4>::try_from(B_VEC).unwrap();
+        let mask = Batched::<4>::from([[1, 0, 2, 0], [0, 0, 1, 1]]);
+
+        Batched::select(mask, a, b);
+    }
+
+    #[test]
+    fn can_sum_lanes_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a.sum_lanes(), [[10; 4], [26; 4]].into());
+    }
+
+    #[test]
+    fn can_sum_rows_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a.sum_rows(), [[6, 8, 10, 12], [6, 8, 10, 12]].into());
+    }
+
+    #[test]
+    fn can_broadcast_lane_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+
+        assert_eq!(a.broadcast_lane(1), [[2; 4], [6; 4]].into());
+    }
+
+    #[test]
+    fn can_dot_non_fhe() {
+        let a = Batched::<4>::try_from(A_VEC).unwrap();
+        let b = Batched::<4>::try_from(B_VEC).unwrap();
+
+        assert_eq!(a.dot(&b), Batched::from(140));
+    }
+
+    #[test]
+    fn lt_polynomial_interpolates_comparison() {
+        for (range, p) in [(2, 5), (4, 17), (16, 65537)] {
+            let coeffs = lt_polynomial(range, p);
+
+            assert_eq!(coeffs.len() as u64, 2 * range - 1);
+
+            for d in -(range as i64 - 1)..range as i64 {
+                let x = d.rem_euclid(p as i64) as u64;
+                let y = coeffs
+                    .iter()
+                    .rev()
+                    .fold(0, |acc, c| (mul_mod(acc, x, p) + c) % p);
+
+                assert_eq!(y, u64::from(d < 0), "range={range} p={p} d={d}");
+            }
+        }
+    }
+
+    #[test]
+    fn can_invert_mod_prime() {
+        for a in 1..17 {
+            assert_eq!(mul_mod(a, inv_mod(a, 17), 17), 1);
+        }
+    }
+
+    #[test]
+    fn to_signed_centers_values() {
+        assert_eq!(to_signed(3, 17), 3);
+        assert_eq!(to_signed(8, 17), 8);
+        assert_eq!(to_signed(9, 17), -8);
+        assert_eq!(to_signed(16, 17), -1);
+    }
 }
@@ -13,7 +13,11 @@ use crate::{
     },
 };
 use crate::{
-    types::{intern::FheProgramNode, BfvType, FheType, Type, Version},
+    types::{
+        bfv::overflow::{expect_zero_digit, signed_digit},
+        intern::FheProgramNode,
+        BfvType, FheType, Select, Type, Version,
+    },
     FheProgramInputTrait, Params, WithContext,
 };
 
@@ -124,6 +128,15 @@ use std::ops::*;
  * `2^(n-INT_BITS)` decimals appear, they will overflow into the integer
  * portion and garble the number.
  *
+ * Decrypting detects the likely cases of both and returns
+ * [`Error::PlaintextOverflow`](sunscreen_runtime::Error::PlaintextOverflow)
+ * rather than a garbled value. A digit is considered overflowed when its
+ * magnitude is within `plain_modulus / 8` of `plain_modulus / 2`, where it
+ * wraps. Integer and decimal digits are considered to overlap if any digit
+ * lands in the lower half of the decimal coefficients, which is far beyond
+ * [`f64`] precision. Digits that wrap far past `plain_modulus / 2` can't be
+ * detected, so this is a safety net rather than a guarantee.
+ *
  * To mitigate these issues, you should do some mix of the following:
  * * Ensure inputs never result in either of these scenarios. Inputs to a
  * FHE program need to have small enough digits to avoid digit overflow, values
@@ -202,7 +215,187 @@ impl<const INT_BITS: usize> TypeNameInstance for Fractional<INT_BITS> {
 impl<const INT_BITS: usize> FheType for Fractional<INT_BITS> {}
 impl<const INT_BITS: usize> BfvType for Fractional<INT_BITS> {}
 
-impl<const INT_BITS: usize> Fractional<INT_BITS> {}
+/**
+ * A [`Fractional`] value that [`Fractional::reciprocal`],
+ * [`Fractional::divide`], and
+ * [`PolynomialApproximation::evaluate`](crate::types::bfv::PolynomialApproximation::evaluate)
+ * can operate on.
+ *
+ * # Remarks
+ * This is implemented for [`Fractional`] ciphertexts inside an
+ * [`fhe_program`](crate::fhe_program) and for plaintext [`Fractional`]
+ * values, which compute the same result without encryption.
+ */
+pub trait FractionalValue<const INT_BITS: usize>:
+    Copy
+    + Add<Output = Self>
+    + Add<f64, Output = Self>
+    + Mul<Output = Self>
+    + Mul<f64, Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+impl<const INT_BITS: usize> FractionalValue<INT_BITS> for Fractional<INT_BITS> {}
+
+impl<const INT_BITS: usize> FractionalValue<INT_BITS>
+    for FheProgramNode<Cipher<Fractional<INT_BITS>>>
+{
+}
+
+/**
+ * The number of significant bits kept in the constants of
+ * [`Fractional::reciprocal`]'s initial guess. Each set bit becomes a
+ * nonzero polynomial coefficient, so rounding the constants limits digit
+ * growth at a small cost in accuracy.
+ */
+const RECIPROCAL_GUESS_BITS: i32 = 8;
+
+impl<const INT_BITS: usize> Fractional<INT_BITS> {
+    /**
+     * Approximates `1 / x` with `iterations` Newton-Raphson steps, where
+     * `x` is known to lie in `range`.
+     *
+     * # Remarks
+     * The initial guess `y_0 = c_1 - c_2 * x` is the linear function
+     * minimizing the worst relative error `e_0 = 1 - x * y_0` over `range`.
+     * Each iteration computes `y_{k+1} = y_k * (1 + e_k)` and
+     * `e_{k+1} = e_k^2`, so the relative error `|1 - x * y_k|` shrinks to
+     * `|e_0|^(2^k)`. [`Fractional::reciprocal_error`] returns this bound.
+     * Narrow ranges converge quickly; a range spanning a factor of 2
+     * starts with `|e_0| < 0.065` and exceeds `f64` precision after 4
+     * iterations.
+     *
+     * The initial guess uses only plaintext multiplications. Computing
+     * `e_0` costs 1 ciphertext multiplication and each iteration 2, except
+     * the last, which doesn't need `e_{k+1}`. An iteration's
+     * multiplications run in parallel, so the multiplicative depth is
+     * `iterations + 1`. With 0 iterations, the result is the initial
+     * guess and needs no ciphertext multiplications.
+     *
+     * Since [`Fractional`] arithmetic is carryless, every multiplication
+     * grows the polynomial coefficients and the plain modulus must be
+     * large enough to hold them (see [`Fractional`]). This growth roughly
+     * squares with each iteration and is worse for inputs with many set
+     * binary digits, so prefer few iterations over a narrow range.
+     *
+     * This works on both [`Fractional`] ciphertexts inside an
+     * [`fhe_program`](crate::fhe_program) and plaintext [`Fractional`]
+     * values, which compute the same approximation without encryption.
+     * Values of `x` outside `range` produce meaningless results.
+     *
+     * # Panics
+     * If `range` is empty, not finite, or contains 0.
+     */
+    pub fn reciprocal<V>(x: V, range: RangeInclusive<f64>, iterations: usize) -> V
+    where
+        V: FractionalValue<INT_BITS>,
+    {
+        let (c_1, c_2) = reciprocal_guess(&range);
+
+        let mut y = x * -c_2 + c_1;
+
+        if iterations == 0 {
+            return y;
+        }
+
+        let mut e = -(x * y) + 1.0;
+
+        for i in 0..iterations {
+            y = y * (e + 1.0);
+
+            // Nothing uses the final iteration's error.
+            if i + 1 < iterations {
+                e = e * e;
+            }
+        }
+
+        y
+    }
+
+    /**
+     * Approximates `a / b` as `a` times [`Fractional::reciprocal`] of `b`,
+     * where `b` is known to lie in `range`.
+     *
+     * # Remarks
+     * The relative error is at most [`Fractional::reciprocal_error`] and
+     * the multiplicative depth is `iterations + 2`, or 1 with 0
+     * iterations.
+     *
+     * # Panics
+     * If `range` is empty, not finite, or contains 0.
+     */
+    pub fn divide<V>(a: V, b: V, range: RangeInclusive<f64>, iterations: usize) -> V
+    where
+        V: FractionalValue<INT_BITS>,
+    {
+        a * Self::reciprocal(b, range, iterations)
+    }
+
+    /**
+     * Returns the largest relative error `|1 - x * y|` of
+     * [`Fractional::reciprocal`]'s result `y` for any `x` in `range`,
+     * ignoring floating point rounding.
+     *
+     * # Panics
+     * If `range` is empty, not finite, or contains 0.
+     */
+    pub fn reciprocal_error(range: RangeInclusive<f64>, iterations: usize) -> f64 {
+        let (c_1, c_2) = reciprocal_guess(&range);
+
+        let e_0 = |x: f64| 1.0 - x * (c_1 - c_2 * x);
+
+        // e_0 is a parabola, so its extremes are at the ends of the range
+        // or its vertex.
+        let vertex = c_1 / (2.0 * c_2);
+        let mut e = e_0(*range.start()).abs().max(e_0(*range.end()).abs());
+
+        if range.contains(&vertex) {
+            e = e.max(e_0(vertex).abs());
+        }
+
+        (0..iterations).fold(e, |e, _| e * e)
+    }
+}
+
+/**
+ * Returns the constants `(c_1, c_2)` of the initial reciprocal guess
+ * `c_1 - c_2 * x` over `range`.
+ */
+fn reciprocal_guess(range: &RangeInclusive<f64>) -> (f64, f64) {
+    let (a, b) = (*range.start(), *range.end());
+
+    assert!(
+        a.is_finite() && b.is_finite() && a <= b && (b < 0.0 || a > 0.0),
+        "Reciprocal range must be finite, nonempty, and exclude 0"
+    );
+
+    // Equioscillation of e_0 at a, b, and (a + b) / 2 gives the minimax
+    // linear guess. For negative ranges, 1 / x = -1 / -x flips the sign of
+    // c_1 and leaves c_2 unchanged.
+    let (a, b, sign) = if a > 0.0 { (a, b, 1.0) } else { (-b, -a, -1.0) };
+
+    let c_2 = 8.0 / ((a + b) * (a + b) + 4.0 * a * b);
+    let c_1 = c_2 * (a + b);
+
+    (
+        sign * round_to_bits(c_1, RECIPROCAL_GUESS_BITS),
+        round_to_bits(c_2, RECIPROCAL_GUESS_BITS),
+    )
+}
+
+/**
+ * Rounds `x` to `bits` significant binary digits.
+ */
+fn round_to_bits(x: f64, bits: i32) -> f64 {
+    if x == 0.0 {
+        return x;
+    }
+
+    let quantum = 2f64.powi(x.abs().log2().floor() as i32 - bits + 1);
+
+    (x / quantum).round() * quantum
+}
 
 impl<const INT_BITS: usize> GraphCipherAdd for Fractional<INT_BITS> {
     type Left = Fractional<INT_BITS>;
@@ -528,25 +721,32 @@ impl<const INT_BITS: usize> TryFromPlaintext for Fractional<INT_BITS> {
 
                 let len = p[0].len();
 
-                let negative_cutoff = (params.plain_modulus + 1) / 2;
+                // Integer digits that carry past INT_BITS and fractional
+                // digits that extend toward INT_BITS both land just above
+                // INT_BITS. Legitimate fractional digits never get close, as
+                // they'd be far below f64 precision.
+                let guard_end = INT_BITS + n.saturating_sub(INT_BITS) / 2;
 
                 for i in 0..usize::min(n, len) {
+                    let coeff = p[0].get_coefficient(i);
+
+                    if (INT_BITS..guard_end).contains(&i) {
+                        expect_zero_digit(coeff, i, "the integer and fractional digits overlap")?;
+                        continue;
+                    }
+
                     let power = if i < INT_BITS {
                         i as i64
                     } else {
                         i as i64 - n as i64
                     };
 
-                    let coeff = p[0].get_coefficient(i);
+                    let digit = signed_digit(coeff, i, params)?;
 
                     // Reverse the sign of negative powers.
                     let sign = if power >= 0 { 1f64 } else { -1f64 };
 
-                    if coeff < negative_cutoff {
-                        val += sign * coeff as f64 * (power as f64).exp2();
-                    } else {
-                        val -= sign * (params.plain_modulus - coeff) as f64 * (power as f64).exp2();
-                    };
+                    val += sign * digit as f64 * (power as f64).exp2();
                 }
 
                 Self { val }
@@ -677,6 +877,18 @@ impl<const INT_BITS: usize> Neg for Fractional<INT_BITS> {
     }
 }
 
+impl<const INT_BITS: usize> Select for Fractional<INT_BITS> {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            bit.val == 0.0 || bit.val == 1.0,
+            "Selector must be 0 or 1, got {}",
+            bit.val
+        );
+
+        b + bit * (a - b)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -770,4 +982,67 @@ mod tests {
         // Allow 1 ULP of error
         assert_eq!(-a, (-3.14).into());
     }
+
+    #[test]
+    fn reciprocal_is_within_error_bound() {
+        for (range, iterations) in [(2.0..=4.0, 2), (0.5..=1.0, 3), (1.0..=100.0, 6)] {
+            let bound = Fractional::<64>::reciprocal_error(range.clone(), iterations);
+            let (lo, hi) = (*range.start(), *range.end());
+
+            for i in 0..=100 {
+                let x = lo + (hi - lo) * i as f64 / 100.0;
+                let y =
+                    Fractional::<64>::reciprocal(Fractional::from(x), range.clone(), iterations);
+
+                assert!((1.0 - x * *y).abs() <= bound + 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn reciprocal_converges() {
+        let errors = (0..5)
+            .map(|i| Fractional::<64>::reciprocal_error(0.5..=1.0, i))
+            .collect::<Vec<_>>();
+
+        // The rounded initial guess is nearly minimax.
+        assert!(errors[0] < 0.065);
+
+        for e in errors.windows(2) {
+            assert_eq!(e[1], e[0] * e[0]);
+        }
+
+        let y = Fractional::<64>::reciprocal(Fractional::from(0.75), 0.5..=1.0, 4);
+
+        assert!((*y - 1.0 / 0.75).abs() < 1e-14);
+    }
+
+    #[test]
+    fn can_reciprocal_negative_range() {
+        let bound = Fractional::<64>::reciprocal_error(-4.0..=-2.0, 3);
+
+        assert_eq!(bound, Fractional::<64>::reciprocal_error(2.0..=4.0, 3));
+
+        for x in [-4.0, -3.3, -2.5, -2.0] {
+            let y = Fractional::<64>::reciprocal(Fractional::from(x), -4.0..=-2.0, 3);
+
+            assert!((1.0 - x * *y).abs() <= bound + 1e-12);
+        }
+    }
+
+    #[test]
+    fn can_divide_non_fhe() {
+        let a = Fractional::<64>::from(-7.5);
+        let b = Fractional::<64>::from(3.0);
+
+        let q = Fractional::<64>::divide(a, b, 1.0..=4.0, 5);
+
+        assert!((*q + 2.5).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "exclude 0")]
+    fn reciprocal_rejects_range_containing_zero() {
+        Fractional::<64>::reciprocal(Fractional::from(1.0), -1.0..=1.0, 2);
+    }
 }
@@ -0,0 +1,245 @@
+use crate::types::{bfv::Batched, intern::FheProgramNode, Cipher};
+
+use std::collections::{BTreeMap, HashMap};
+use std::ops::{Add, Shl, Shr};
+
+/**
+ * A vector of lanes that can be multiplied by a plaintext matrix with
+ * [`matrix_vector_product`] or [`sparse_matrix_vector_product`] and
+ * permuted with [`Batched::permute`].
+ *
+ * # Remarks
+ * This is implemented for [`Batched`] ciphertexts inside an
+ * [`fhe_program`](crate::fhe_program) and for plaintext [`Batched`]
+ * values, which compute the same result without encryption.
+ */
+pub trait LaneVector<const LANES: usize>:
+    Copy + Add<Output = Self> + Shl<u64, Output = Self> + Shr<u64, Output = Self>
+{
+    /**
+     * Multiplies each lane of `self` by the corresponding lane of `x`.
+     */
+    fn mul_lanes(self, x: &Batched<LANES>) -> Self;
+}
+
+impl<const LANES: usize> LaneVector<LANES> for Batched<LANES> {
+    fn mul_lanes(self, x: &Batched<LANES>) -> Self {
+        self * *x
+    }
+}
+
+impl<const LANES: usize> LaneVector<LANES> for FheProgramNode<Cipher<Batched<LANES>>> {
+    fn mul_lanes(self, x: &Batched<LANES>) -> Self {
+        self * x.graph_insert()
+    }
+}
+
+/**
+ * Multiplies the `LANES x LANES` plaintext `matrix` by the vector in each
+ * row of `v`. Lane `j` of each row in the result is
+ * `sum_k matrix[j][k] * v[k]`.
+ *
+ * # Remarks
+ * This uses the Halevi-Shoup diagonal method: the product is the sum
+ * over each generalized diagonal `i` of the diagonal multiplied by `v`
+ * rotated left `i` lanes. Diagonals that are entirely zero are skipped.
+ *
+ * Rotations use the baby-step giant-step decomposition `i = g * k + b`
+ * with `g ~ sqrt(LANES)`. The `g` baby-step rotations of `v` are computed
+ * once and shared by every giant step, and each giant step's diagonals
+ * are pre-rotated in plaintext. A dense matrix thus costs `LANES`
+ * plaintext multiplications but only about `2 * sqrt(LANES)` ciphertext
+ * rotations.
+ *
+ * The matrix lives on the stack, so this is only suitable for small
+ * `LANES`. Use [`sparse_matrix_vector_product`] for large or sparse
+ * matrices.
+ */
+pub fn matrix_vector_product<const LANES: usize, V>(matrix: &[[i64; LANES]; LANES], v: V) -> V
+where
+    V: LaneVector<LANES>,
+{
+    let entries = matrix
+        .iter()
+        .enumerate()
+        .flat_map(|(row, vals)| {
+            vals.iter()
+                .enumerate()
+                .map(move |(col, val)| (row, col, *val))
+        })
+        .collect::<Vec<_>>();
+
+    sparse_matrix_vector_product(&entries, v)
+}
+
+/**
+ * Multiplies a sparse `LANES x LANES` plaintext matrix, given as
+ * `(row, column, value)` entries, by the vector in each row of `v`.
+ * Entries that share a position are summed.
+ *
+ * # Remarks
+ * See [`matrix_vector_product`]. Only diagonals containing a nonzero
+ * entry cost a plaintext multiplication, and only the baby and giant
+ * steps those diagonals need emit rotations.
+ *
+ * # Panics
+ * If any row or column is not less than `LANES`.
+ */
+pub fn sparse_matrix_vector_product<const LANES: usize, V>(
+    entries: &[(usize, usize, i64)],
+    v: V,
+) -> V
+where
+    V: LaneVector<LANES>,
+{
+    // diagonals[i][j] = matrix[j][(j + i) % LANES]
+    let mut diagonals: BTreeMap<usize, [i64; LANES]> = BTreeMap::new();
+
+    for (row, col, val) in entries {
+        assert!(
+            *row < LANES && *col < LANES,
+            "Matrix entry ({}, {}) out of range [0, {})",
+            row,
+            col,
+            LANES
+        );
+
+        let i = (col + LANES - row) % LANES;
+
+        diagonals.entry(i).or_insert([0; LANES])[*row] += val;
+    }
+
+    diagonals.retain(|_, d| d.iter().any(|x| *x != 0));
+
+    let g = baby_step_count(LANES);
+    let mut baby_steps = HashMap::new();
+    let mut result: Option<V> = None;
+
+    // BTreeMap iterates in order, so each giant step's diagonals are
+    // contiguous.
+    let mut diagonals = diagonals.into_iter().peekable();
+
+    while let Some((first, _)) = diagonals.peek() {
+        let k = first / g;
+        let shift = g * k;
+        let mut inner: Option<V> = None;
+
+        while let Some((i, diagonal)) = diagonals.next_if(|(i, _)| i / g == k) {
+            let b = i - shift;
+
+            let rotated_v =
+                *baby_steps
+                    .entry(b)
+                    .or_insert_with(|| if b == 0 { v } else { v << b as u64 });
+
+            // Rotate the diagonal right by the giant step so rotating the
+            // sum left afterwards realigns it.
+            let mut rotated = [0; LANES];
+
+            for (j, x) in rotated.iter_mut().enumerate() {
+                *x = diagonal[(j + LANES - shift) % LANES];
+            }
+
+            let term = rotated_v.mul_lanes(&Batched::from([rotated, rotated]));
+
+            inner = Some(match inner {
+                Some(s) => s + term,
+                None => term,
+            });
+        }
+
+        let inner = inner.unwrap();
+        let term = if shift == 0 {
+            inner
+        } else {
+            inner << shift as u64
+        };
+
+        result = Some(match result {
+            Some(s) => s + term,
+            None => term,
+        });
+    }
+
+    // An all-zero matrix still needs to produce a value.
+    result.unwrap_or_else(|| v.mul_lanes(&Batched::from(0)))
+}
+
+/**
+ * The baby-step size `g`, a power of 2 near `sqrt(lanes)`.
+ */
+fn baby_step_count(lanes: usize) -> usize {
+    let log_lanes = usize::BITS - 1 - lanes.leading_zeros();
+
+    1 << log_lanes.div_ceil(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn naive<const LANES: usize>(
+        matrix: &[[i64; LANES]; LANES],
+        v: [[i64; LANES]; 2],
+    ) -> Batched<LANES> {
+        let mut out = [[0; LANES]; 2];
+
+        for (out_row, v_row) in out.iter_mut().zip(v.iter()) {
+            for (x, m_row) in out_row.iter_mut().zip(matrix.iter()) {
+                *x = m_row.iter().zip(v_row.iter()).map(|(m, v)| m * v).sum();
+            }
+        }
+
+        Batched::from(out)
+    }
+
+    #[test]
+    fn can_multiply_dense_matrix() {
+        let mut matrix = [[0; 8]; 8];
+
+        for (j, row) in matrix.iter_mut().enumerate() {
+            for (k, x) in row.iter_mut().enumerate() {
+                *x = (3 * j as i64 + 5 * k as i64) % 11 - 5;
+            }
+        }
+
+        let v = [[1, -2, 3, 4, 0, 6, -7, 8], [2, 2, 1, 0, -1, 5, 3, 9]];
+
+        assert_eq!(
+            matrix_vector_product(&matrix, Batched::from(v)),
+            naive(&matrix, v)
+        );
+    }
+
+    #[test]
+    fn can_multiply_sparse_matrix() {
+        let entries = [(0, 3, 2), (1, 1, -1), (3, 0, 4), (2, 2, 7), (2, 2, 1)];
+        let v = [[1, 2, 3, 4], [5, 6, 7, 8]];
+
+        let mut matrix = [[0; 4]; 4];
+
+        for (row, col, val) in entries {
+            matrix[row][col] += val;
+        }
+
+        assert_eq!(
+            sparse_matrix_vector_product(&entries, Batched::from(v)),
+            naive(&matrix, v)
+        );
+    }
+
+    #[test]
+    fn zero_matrix_yields_zero() {
+        let v = Batched::<4>::from([[1, 2, 3, 4], [5, 6, 7, 8]]);
+
+        assert_eq!(sparse_matrix_vector_product(&[], v), Batched::from(0));
+    }
+
+    #[test]
+    fn baby_steps_are_near_sqrt() {
+        assert_eq!(baby_step_count(1), 1);
+        assert_eq!(baby_step_count(4), 2);
+        assert_eq!(baby_step_count(8), 4);
+        assert_eq!(baby_step_count(4096), 64);
+    }
+}
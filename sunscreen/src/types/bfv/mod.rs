@@ -1,11 +1,20 @@
+mod approximation;
 mod batched;
 mod fractional;
+mod matrix;
+mod overflow;
+mod permute;
 mod rational;
 mod signed;
+mod signed_big;
 mod unsigned;
 
+pub use approximation::*;
 pub use batched::*;
 pub use fractional::*;
+pub use matrix::*;
+pub use permute::*;
 pub use rational::*;
 pub use signed::*;
+pub use signed_big::*;
 pub use unsigned::*;
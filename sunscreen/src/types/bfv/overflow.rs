@@ -0,0 +1,203 @@
+use crate::Params;
+
+use sunscreen_runtime::Error;
+
+/**
+ * Interprets a plaintext polynomial coefficient as a signed digit in
+ * `(-plain_modulus / 2, plain_modulus / 2]`.
+ *
+ * # Remarks
+ * Carryless arithmetic grows digits until they exceed `plain_modulus / 2`
+ * and wrap to the opposite sign. A wrapped digit has nearly the largest
+ * possible magnitude, so this returns [`Error::PlaintextOverflow`] for
+ * digits whose magnitude is within 1/8 of the plain modulus of wrapping.
+ * Digits that wrap further than that can't be distinguished from legitimate
+ * ones.
+ */
+pub(crate) fn signed_digit(coeff: u64, index: usize, params: &Params) -> Result<i128, Error> {
+    let plain_modulus = params.plain_modulus;
+    let negative_cutoff = (plain_modulus + 1) / 2;
+
+    let digit = if coeff < negative_cutoff {
+        coeff as i128
+    } else {
+        -((plain_modulus - coeff) as i128)
+    };
+
+    let limit = u64::max(1, 3 * (plain_modulus / 8)) as i128;
+
+    if digit.abs() > limit {
+        return Err(Error::plaintext_overflow(&format!(
+            "coefficient {} has value {}, which is close to wrapping around plain modulus {}",
+            index, digit, plain_modulus
+        )));
+    }
+
+    Ok(digit)
+}
+
+/**
+ * Returns [`Error::PlaintextOverflow`] if `coeff` is nonzero, as it lies
+ * outside the coefficients a type's values occupy.
+ */
+pub(crate) fn expect_zero_digit(coeff: u64, index: usize, reason: &str) -> Result<(), Error> {
+    if coeff != 0 {
+        return Err(Error::plaintext_overflow(&format!(
+            "coefficient {} is nonzero; {}",
+            index, reason
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::bfv::{Fractional, Signed, SignedBig128, Unsigned64};
+    use crate::{SchemeType, SecurityLevel};
+
+    use num::BigInt;
+    use sunscreen_runtime::{InnerPlaintext, Plaintext, TryFromPlaintext, TryIntoPlaintext};
+
+    fn params(plain_modulus: u64) -> Params {
+        Params {
+            lattice_dimension: 4096,
+            plain_modulus,
+            coeff_modulus: vec![],
+            scheme_type: SchemeType::Bfv,
+            security_level: SecurityLevel::TC128,
+        }
+    }
+
+    fn set_coefficient(plaintext: &mut Plaintext, index: usize, value: u64) {
+        let InnerPlaintext::Seal(p) = &mut plaintext.inner;
+
+        if p[0].data.len() <= index {
+            p[0].data.resize(index + 1);
+        }
+
+        p[0].data.set_coefficient(index, value);
+    }
+
+    fn is_overflow<T>(result: Result<T, Error>) -> bool {
+        matches!(result, Err(Error::PlaintextOverflow(_)))
+    }
+
+    #[test]
+    fn signed_digit_detects_near_wrap() {
+        let params = params(64);
+
+        assert_eq!(signed_digit(24, 0, &params).unwrap(), 24);
+        assert_eq!(signed_digit(40, 0, &params).unwrap(), -24);
+        assert!(is_overflow(signed_digit(25, 0, &params)));
+        assert!(is_overflow(signed_digit(39, 0, &params)));
+    }
+
+    #[test]
+    fn signed_detects_wrapped_digits() {
+        let params = params(64);
+        let mut pt = Signed::from(5).try_into_plaintext(&params).unwrap();
+
+        set_coefficient(&mut pt, 1, 20);
+        assert_eq!(
+            Signed::try_from_plaintext(&pt, &params).unwrap(),
+            Signed::from(45)
+        );
+
+        // 33 reads as -31, which is what 33 looks like after wrapping.
+        set_coefficient(&mut pt, 1, 33);
+        assert!(is_overflow(Signed::try_from_plaintext(&pt, &params)));
+    }
+
+    #[test]
+    fn signed_detects_values_beyond_i64() {
+        let params = params(64);
+        let mut pt = Signed::from(0).try_into_plaintext(&params).unwrap();
+
+        set_coefficient(&mut pt, 64, 1);
+        assert!(is_overflow(Signed::try_from_plaintext(&pt, &params)));
+
+        // 2^64 - 2^63 - 2^62 = 2^62 fits, even though its digits don't.
+        set_coefficient(&mut pt, 63, 63);
+        set_coefficient(&mut pt, 62, 63);
+        assert_eq!(
+            Signed::try_from_plaintext(&pt, &params).unwrap(),
+            Signed::from(1 << 62)
+        );
+    }
+
+    #[test]
+    fn signed_big_detects_values_beyond_limbs() {
+        let params = params(64);
+        let mut pt = SignedBig128::from(-3).try_into_plaintext(&params).unwrap();
+
+        set_coefficient(&mut pt, 100, 63);
+        assert_eq!(
+            BigInt::from(SignedBig128::try_from_plaintext(&pt, &params).unwrap()),
+            -(BigInt::from(1i64) << 100usize) - 3
+        );
+
+        // 2^129 - 2^100 - 3 needs 129 bits.
+        set_coefficient(&mut pt, 129, 1);
+        assert!(is_overflow(SignedBig128::try_from_plaintext(&pt, &params)));
+
+        set_coefficient(&mut pt, 129, 0);
+        set_coefficient(&mut pt, 100, 33);
+        assert!(is_overflow(SignedBig128::try_from_plaintext(&pt, &params)));
+    }
+
+    #[test]
+    fn unsigned_detects_wrapped_digits() {
+        let params = params(500);
+        let mut pt = Unsigned64::from(3).try_into_plaintext(&params).unwrap();
+
+        set_coefficient(&mut pt, 2, 186);
+        assert_eq!(
+            Unsigned64::try_from_plaintext(&pt, &params).unwrap(),
+            Unsigned64::from(3 + 4 * 186)
+        );
+
+        set_coefficient(&mut pt, 2, 249);
+        assert!(is_overflow(Unsigned64::try_from_plaintext(&pt, &params)));
+    }
+
+    #[test]
+    fn fractional_detects_overlapping_digits() {
+        let params = params(1_000_000);
+        let mut pt = Fractional::<64>::from(-5.8125)
+            .try_into_plaintext(&params)
+            .unwrap();
+
+        assert_eq!(
+            Fractional::<64>::try_from_plaintext(&pt, &params).unwrap(),
+            Fractional::from(-5.8125)
+        );
+
+        // An integer digit carried past INT_BITS.
+        set_coefficient(&mut pt, 64, 1);
+        assert!(is_overflow(Fractional::<64>::try_from_plaintext(
+            &pt, &params
+        )));
+
+        // A fractional digit far below f64 precision.
+        set_coefficient(&mut pt, 64, 0);
+        set_coefficient(&mut pt, 1000, 1);
+        assert!(is_overflow(Fractional::<64>::try_from_plaintext(
+            &pt, &params
+        )));
+    }
+
+    #[test]
+    fn fractional_detects_wrapped_digits() {
+        let params = params(1_000_000);
+        let mut pt = Fractional::<64>::from(1.5)
+            .try_into_plaintext(&params)
+            .unwrap();
+
+        set_coefficient(&mut pt, 4095, 600_000);
+        assert!(is_overflow(Fractional::<64>::try_from_plaintext(
+            &pt, &params
+        )));
+    }
+}
@@ -0,0 +1,535 @@
+use crate::types::bfv::{Batched, LaneVector};
+
+use std::collections::BTreeMap;
+
+/**
+ * The cost of permuting the lanes of a [`Batched`] value with
+ * [`Batched::permute`].
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PermutationCost {
+    /**
+     * The number of ciphertext rotations.
+     */
+    pub rotations: usize,
+
+    /**
+     * The number of ciphertext-plaintext multiplications by lane masks.
+     */
+    pub plaintext_multiplications: usize,
+
+    /**
+     * The longest chain of sequential mask multiplications. Each one
+     * consumes noise budget.
+     */
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rotation {
+    None,
+    Left(u64),
+    Right(u64),
+}
+
+/**
+ * A term in a [`Layer`]: the layer's input rotated, then multiplied by
+ * a mask selecting the lanes this term contributes.
+ */
+#[derive(Debug, Clone)]
+struct Term {
+    rotation: Rotation,
+    mask: Vec<bool>,
+}
+
+/**
+ * The sum of its terms.
+ */
+#[derive(Debug, Clone)]
+struct Layer {
+    terms: Vec<Term>,
+}
+
+#[derive(Debug, Clone)]
+struct PermutationPlan {
+    layers: Vec<Layer>,
+}
+
+impl PermutationPlan {
+    /**
+     * Groups output lanes by how far their source lane is rotated. This
+     * works for any gather, but needs a rotation for every distinct
+     * offset.
+     */
+    fn direct(indices: &[usize]) -> Self {
+        let lanes = indices.len();
+        let mut masks: BTreeMap<usize, Vec<bool>> = BTreeMap::new();
+
+        for (j, i) in indices.iter().enumerate() {
+            let shift = (i + lanes - j) % lanes;
+
+            masks.entry(shift).or_insert_with(|| vec![false; lanes])[j] = true;
+        }
+
+        let terms = masks
+            .into_iter()
+            .map(|(shift, mask)| Term {
+                rotation: if shift == 0 {
+                    Rotation::None
+                } else {
+                    Rotation::Left(shift as u64)
+                },
+                mask,
+            })
+            .collect();
+
+        Self {
+            layers: vec![Layer { terms }],
+        }
+    }
+
+    /**
+     * Routes a permutation through a Beneš network. Each of the
+     * `2 * log2(LANES) - 1` layers swaps lanes a power of 2 apart, so
+     * rotations match the Galois keys generated for every program.
+     */
+    fn benes(indices: &[usize]) -> Self {
+        let lanes = indices.len();
+        let switches = route_benes(indices);
+        let layer_count = switches.len();
+
+        let layers = switches
+            .into_iter()
+            .enumerate()
+            .filter(|(_, swap)| swap.iter().any(|s| *s))
+            .map(|(l, swap)| {
+                let level = usize::min(l, layer_count - 1 - l);
+                let stride = lanes >> (level + 1);
+
+                let keep = swap.iter().map(|s| !s).collect::<Vec<_>>();
+                let from_above = (0..lanes)
+                    .map(|p| swap[p] && p & stride == 0)
+                    .collect::<Vec<_>>();
+                let from_below = (0..lanes)
+                    .map(|p| swap[p] && p & stride != 0)
+                    .collect::<Vec<_>>();
+
+                let terms = [
+                    (Rotation::None, keep),
+                    (Rotation::Left(stride as u64), from_above),
+                    (Rotation::Right(stride as u64), from_below),
+                ]
+                .into_iter()
+                .filter(|(_, mask)| mask.iter().any(|m| *m))
+                .map(|(rotation, mask)| Term { rotation, mask })
+                .collect();
+
+                Layer { terms }
+            })
+            .collect();
+
+        Self { layers }
+    }
+
+    fn cost(&self) -> PermutationCost {
+        let mut cost = PermutationCost::default();
+
+        for layer in &self.layers {
+            let mut masked = false;
+
+            for term in &layer.terms {
+                if term.rotation != Rotation::None {
+                    cost.rotations += 1;
+                }
+
+                if !term.mask.iter().all(|m| *m) {
+                    cost.plaintext_multiplications += 1;
+                    masked = true;
+                }
+            }
+
+            if masked {
+                cost.depth += 1;
+            }
+        }
+
+        cost
+    }
+
+    fn apply<const LANES: usize, V>(&self, x: V) -> V
+    where
+        V: LaneVector<LANES>,
+    {
+        let mut x = x;
+
+        for layer in &self.layers {
+            let mut sum: Option<V> = None;
+
+            for term in &layer.terms {
+                let rotated = match term.rotation {
+                    Rotation::None => x,
+                    Rotation::Left(n) => x << n,
+                    Rotation::Right(n) => x >> n,
+                };
+
+                let masked = if term.mask.iter().all(|m| *m) {
+                    rotated
+                } else {
+                    let mut mask = [0; LANES];
+
+                    for (m, keep) in mask.iter_mut().zip(term.mask.iter()) {
+                        *m = i64::from(*keep);
+                    }
+
+                    rotated.mul_lanes(&Batched::from([mask, mask]))
+                };
+
+                sum = Some(match sum {
+                    Some(s) => s + masked,
+                    None => masked,
+                });
+            }
+
+            x = sum.unwrap();
+        }
+
+        x
+    }
+
+    /**
+     * The cheaper of the direct and Beneš plans, preferring fewer
+     * rotations and then less depth.
+     */
+    fn new(indices: &[usize]) -> Self {
+        let direct = Self::direct(indices);
+
+        if !is_permutation(indices) {
+            return direct;
+        }
+
+        let benes = Self::benes(indices);
+
+        let key = |p: &Self| {
+            let c = p.cost();
+            (c.rotations, c.depth, c.plaintext_multiplications)
+        };
+
+        if key(&benes) < key(&direct) {
+            benes
+        } else {
+            direct
+        }
+    }
+}
+
+fn is_permutation(indices: &[usize]) -> bool {
+    let mut seen = vec![false; indices.len()];
+
+    for i in indices {
+        if seen[*i] {
+            return false;
+        }
+
+        seen[*i] = true;
+    }
+
+    true
+}
+
+/**
+ * Computes the switch settings of an in-place Beneš network realizing
+ * `out[j] = in[perm[j]]` with the looping algorithm.
+ *
+ * Returns one `Vec` per layer, where `true` at position `p` means `p`
+ * takes its value from its partner `p ^ stride`. Layer strides are
+ * `n/2, n/4, ..., 1, ..., n/4, n/2`.
+ */
+fn route_benes(perm: &[usize]) -> Vec<Vec<bool>> {
+    let n = perm.len();
+
+    if n == 1 {
+        return vec![];
+    }
+
+    if n == 2 {
+        return vec![vec![perm[0] == 1; 2]];
+    }
+
+    let s = n / 2;
+
+    let mut inverse = vec![0; n];
+
+    for (j, i) in perm.iter().enumerate() {
+        inverse[*i] = j;
+    }
+
+    // Whether each output comes from the lower subnetwork. The outputs of
+    // a switch come from different subnetworks, as do the inputs of a
+    // switch. Follow the cycles of these constraints to 2-color outputs.
+    let mut lower: Vec<Option<bool>> = vec![None; n];
+
+    for start in 0..s {
+        let mut j = start;
+
+        while lower[j].is_none() {
+            lower[j] = Some(false);
+            lower[j ^ s] = Some(true);
+
+            // The lower output's source goes down, so its input partner
+            // goes up.
+            j = inverse[perm[j ^ s] ^ s];
+        }
+    }
+
+    let lower = lower.into_iter().map(Option::unwrap).collect::<Vec<_>>();
+
+    let mut first = vec![false; n];
+    let mut last = vec![false; n];
+    let mut upper_perm = vec![0; s];
+    let mut lower_perm = vec![0; s];
+
+    for j in 0..s {
+        // Input j must feed the upper subnetwork, so swap if its element
+        // is routed down.
+        let swap_in = lower[inverse[j]];
+        first[j] = swap_in;
+        first[j + s] = swap_in;
+
+        let swap_out = lower[j];
+        last[j] = swap_out;
+        last[j + s] = swap_out;
+
+        let (up, down) = if swap_out { (j + s, j) } else { (j, j + s) };
+
+        upper_perm[j] = perm[up] % s;
+        lower_perm[j] = perm[down] % s;
+    }
+
+    let upper = route_benes(&upper_perm);
+    let lower = route_benes(&lower_perm);
+
+    let mut layers = vec![first];
+
+    for (u, l) in upper.into_iter().zip(lower) {
+        layers.push([u, l].concat());
+    }
+
+    layers.push(last);
+
+    layers
+}
+
+impl<const LANES: usize> Batched<LANES> {
+    /**
+     * Returns a value whose lane `j` in each row holds lane `indices[j]`
+     * of `x`.
+     *
+     * # Remarks
+     * `indices` may be any gather, including ones that repeat or drop
+     * lanes. The permutation is compiled into masked plaintext
+     * multiplications and rotations using the cheaper of two plans:
+     * * Rotating `x` once per distinct offset `indices[j] - j` and masking
+     *   each rotation to the lanes it serves. This needs a single
+     *   multiplication depth.
+     * * For true permutations, a Beneš network of
+     *   `2 * log2(LANES) - 1` layers that each swap lanes a power of 2
+     *   apart. This needs at most 2 rotations per layer but one
+     *   multiplication depth per layer.
+     *
+     * The plan with fewer rotations wins, and then the one with less
+     * depth. Use [`Batched::permutation_cost`] to inspect the cost.
+     *
+     * Rotation amounts are always in `[0, LANES)`; as documented on
+     * [`Batched`], lanes repeat when the lattice dimension exceeds
+     * `2 * LANES`, so the result is the same for any lattice dimension.
+     *
+     * This works on both [`Batched`] ciphertexts inside an
+     * [`fhe_program`](crate::fhe_program) and plaintext [`Batched`]
+     * values.
+     *
+     * # Panics
+     * If `indices.len() != LANES` or any index is not less than `LANES`.
+     */
+    pub fn permute<V>(x: V, indices: &[usize]) -> V
+    where
+        V: LaneVector<LANES>,
+    {
+        validate_indices::<LANES>(indices);
+
+        PermutationPlan::new(indices).apply::<LANES, V>(x)
+    }
+
+    /**
+     * Returns the cost of [`Batched::permute`] with the given `indices`
+     * without emitting any operations.
+     *
+     * # Panics
+     * If `indices.len() != LANES` or any index is not less than `LANES`.
+     */
+    pub fn permutation_cost(indices: &[usize]) -> PermutationCost {
+        validate_indices::<LANES>(indices);
+
+        PermutationPlan::new(indices).cost()
+    }
+}
+
+fn validate_indices<const LANES: usize>(indices: &[usize]) {
+    assert_eq!(
+        indices.len(),
+        LANES,
+        "Expected {} permutation indices, got {}",
+        LANES,
+        indices.len()
+    );
+
+    if let Some(i) = indices.iter().find(|i| **i >= LANES) {
+        panic!("Permutation index {} out of range [0, {})", i, LANES);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lanes<const LANES: usize>(f: impl Fn(usize) -> i64) -> Batched<LANES> {
+        let mut row_0 = [0; LANES];
+        let mut row_1 = [0; LANES];
+
+        for (j, (x_0, x_1)) in row_0.iter_mut().zip(row_1.iter_mut()).enumerate() {
+            *x_0 = f(j);
+            *x_1 = 100 + f(j);
+        }
+
+        Batched::from([row_0, row_1])
+    }
+
+    fn check_plan(plan: &PermutationPlan, indices: &[usize; 16]) {
+        let x = lanes::<16>(|j| j as i64);
+        let expected = lanes::<16>(|j| indices[j] as i64);
+
+        assert_eq!(plan.apply::<16, _>(x), expected);
+    }
+
+    fn bit_reverse() -> [usize; 16] {
+        let mut indices = [0; 16];
+
+        for (j, i) in indices.iter_mut().enumerate() {
+            *i = j.reverse_bits() >> (usize::BITS - 4);
+        }
+
+        indices
+    }
+
+    #[test]
+    fn benes_routes_permutations() {
+        let transpose = {
+            // 4x4 transpose.
+            let mut indices = [0; 16];
+
+            for (j, i) in indices.iter_mut().enumerate() {
+                *i = (j % 4) * 4 + j / 4;
+            }
+
+            indices
+        };
+
+        let interleave = {
+            let mut indices = [0; 16];
+
+            for (j, i) in indices.iter_mut().enumerate() {
+                *i = if j % 2 == 0 { j / 2 } else { 8 + j / 2 };
+            }
+
+            indices
+        };
+
+        let reverse = {
+            let mut indices = [0; 16];
+
+            for (j, i) in indices.iter_mut().enumerate() {
+                *i = 15 - j;
+            }
+
+            indices
+        };
+
+        for indices in [transpose, interleave, reverse, bit_reverse()] {
+            let plan = PermutationPlan::benes(&indices);
+
+            assert!(plan.layers.len() <= 7);
+            check_plan(&plan, &indices);
+            check_plan(&PermutationPlan::direct(&indices), &indices);
+            check_plan(&PermutationPlan::new(&indices), &indices);
+        }
+    }
+
+    #[test]
+    fn rotation_uses_direct_plan() {
+        let mut indices = [0; 16];
+
+        for (j, i) in indices.iter_mut().enumerate() {
+            *i = (j + 3) % 16;
+        }
+
+        check_plan(&PermutationPlan::new(&indices), &indices);
+
+        assert_eq!(
+            Batched::<16>::permutation_cost(&indices),
+            PermutationCost {
+                rotations: 1,
+                plaintext_multiplications: 0,
+                depth: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn benes_beats_direct_for_shuffle() {
+        // Fisher-Yates shuffle with a fixed LCG.
+        let mut seed = 42u64;
+        let mut indices = (0..64).collect::<Vec<_>>();
+
+        for i in (1..64).rev() {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            indices.swap(i, (seed >> 33) as usize % (i + 1));
+        }
+
+        let cost = Batched::<64>::permutation_cost(&indices);
+        let direct = PermutationPlan::direct(&indices).cost();
+
+        assert!(cost.rotations < direct.rotations);
+        assert!(cost.rotations <= 2 * 11);
+        assert!(cost.depth <= 11);
+
+        let x = lanes::<64>(|j| j as i64);
+        let expected = lanes::<64>(|j| indices[j] as i64);
+
+        assert_eq!(Batched::permute(x, &indices), expected);
+    }
+
+    #[test]
+    fn can_gather() {
+        let indices = [0, 0, 5, 5, 1, 2, 3, 15, 15, 15, 8, 9, 10, 11, 12, 0];
+
+        check_plan(&PermutationPlan::new(&indices), &indices);
+    }
+
+    #[test]
+    fn identity_is_free() {
+        let indices = (0..16).collect::<Vec<_>>();
+
+        assert_eq!(
+            Batched::<16>::permutation_cost(&indices),
+            PermutationCost::default()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn rejects_out_of_range_indices() {
+        Batched::<4>::permutation_cost(&[0, 1, 2, 4]);
+    }
+}
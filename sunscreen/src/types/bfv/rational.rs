@@ -2,7 +2,7 @@ use crate as sunscreen;
 use crate::fhe::{with_fhe_ctx, FheContextOps};
 use crate::types::{
     bfv::Signed, intern::FheProgramNode, ops::*, BfvType, Cipher, FheType, GraphCipherAdd,
-    GraphCipherDiv, GraphCipherMul, GraphCipherSub, NumCiphertexts, TryFromPlaintext,
+    GraphCipherDiv, GraphCipherMul, GraphCipherSub, NumCiphertexts, Select, TryFromPlaintext,
     TryIntoPlaintext, TypeName,
 };
 use crate::{FheProgramInputTrait, InnerPlaintext, Params, Plaintext, TypeName};
@@ -268,6 +268,18 @@ impl Neg for Rational {
     }
 }
 
+impl Select for Rational {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            f64::from(bit) == 0.0 || f64::from(bit) == 1.0,
+            "Selector must be 0 or 1, got {}",
+            f64::from(bit)
+        );
+
+        b + bit * (a - b)
+    }
+}
+
 impl GraphCipherAdd for Rational {
     type Left = Self;
     type Right = Self;
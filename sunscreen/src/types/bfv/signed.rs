@@ -14,7 +14,11 @@ use crate::{
     },
 };
 use crate::{
-    types::{intern::FheProgramNode, BfvType, FheType, TypeNameInstance},
+    types::{
+        bfv::overflow::{expect_zero_digit, signed_digit},
+        intern::FheProgramNode,
+        BfvType, FheType, Select, TypeNameInstance,
+    },
     FheProgramInputTrait, Params, TypeName as DeriveTypeName, WithContext,
 };
 
@@ -27,6 +31,15 @@ use std::ops::*;
 #[derive(Debug, Clone, Copy, DeriveTypeName, PartialEq, Eq)]
 /**
  * A single signed integer.
+ *
+ * # Remarks
+ * Each binary digit of the integer is a plaintext polynomial coefficient,
+ * and arithmetic doesn't propagate carries, so digits grow with each
+ * operation. Decrypting returns
+ * [`Error::PlaintextOverflow`](sunscreen_runtime::Error::PlaintextOverflow)
+ * if a digit's magnitude is within `plain_modulus / 8` of wrapping around
+ * `plain_modulus / 2` or the value doesn't fit in an [`i64`]. Use
+ * [`SignedBig`](crate::types::bfv::SignedBig) for wider values.
  */
 pub struct Signed {
     val: i64,
@@ -109,25 +122,32 @@ impl TryFromPlaintext for Signed {
                     return Err(sunscreen_runtime::Error::IncorrectCiphertextCount);
                 }
 
-                let bits = usize::min(
-                    usize::min(std::mem::size_of::<u64>() * 8, p[0].len()),
-                    p[0].len(),
-                );
-
-                let negative_cutoff = (params.plain_modulus + 1) / 2;
+                let overflow = || {
+                    sunscreen_runtime::Error::plaintext_overflow("the value doesn't fit in an i64")
+                };
 
-                let mut val: i64 = 0;
+                // Accumulate in i128 so high digits that cancel, such as
+                // 2^70 - 2^69 - ... - 2^64, still decode correctly.
+                let mut val: i128 = 0;
 
-                for i in 0..bits {
+                for i in 0..p[0].len() {
                     let coeff = p[0].get_coefficient(i);
 
-                    if coeff < negative_cutoff {
-                        val += ((0x1 << i) * coeff) as i64;
-                    } else {
-                        val -= ((0x1 << i) * (params.plain_modulus - coeff)) as i64;
+                    if i >= 127 {
+                        expect_zero_digit(coeff, i, "the value doesn't fit in an i64")?;
+                        continue;
                     }
+
+                    let digit = signed_digit(coeff, i, params)?;
+
+                    val = digit
+                        .checked_mul(1 << i)
+                        .and_then(|x| val.checked_add(x))
+                        .ok_or_else(overflow)?;
                 }
 
+                let val = i64::try_from(val).map_err(|_| overflow())?;
+
                 Self { val }
             }
         };
@@ -246,6 +266,18 @@ impl Neg for Signed {
     }
 }
 
+impl Select for Signed {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            bit.val == 0 || bit.val == 1,
+            "Selector must be 0 or 1, got {}",
+            bit.val
+        );
+
+        b + bit * (a - b)
+    }
+}
+
 impl GraphCipherAdd for Signed {
     type Left = Signed;
     type Right = Signed;
@@ -492,4 +524,20 @@ mod tests {
 
         assert_eq!(-a, (-5).into());
     }
+
+    #[test]
+    fn can_select_non_fhe() {
+        let a = Signed::from(5);
+        let b = Signed::from(10);
+
+        assert_eq!(Signed::select(1.into(), a, b), a);
+        assert_eq!(Signed::select(0.into(), a, b), b);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Selector must be 0 or 1")]
+    fn select_non_fhe_rejects_invalid_selector() {
+        Signed::select(2.into(), 5.into(), 10.into());
+    }
 }
@@ -0,0 +1,565 @@
+use std::ops::*;
+
+use crypto_bigint::{nlimbs, Limb, UInt};
+use num::{BigInt, BigUint, Zero};
+use paste::paste;
+use seal_fhe::Plaintext as SealPlaintext;
+
+use sunscreen_runtime::{
+    Error as RuntimeError, InnerPlaintext, NumCiphertexts, Plaintext, TryFromPlaintext,
+    TryIntoPlaintext,
+};
+
+use crate as sunscreen;
+use crate::types::ops::GraphCipherInsert;
+use crate::{
+    fhe::{with_fhe_ctx, FheContextOps},
+    types::{
+        ops::{
+            GraphCipherAdd, GraphCipherConstAdd, GraphCipherConstMul, GraphCipherConstSub,
+            GraphCipherMul, GraphCipherNeg, GraphCipherPlainAdd, GraphCipherPlainMul,
+            GraphCipherPlainSub, GraphCipherSub, GraphConstCipherSub, GraphPlainCipherSub,
+        },
+        Cipher,
+    },
+};
+use crate::{
+    types::{
+        bfv::overflow::signed_digit, intern::FheProgramNode, BfvType, FheType, Select,
+        TypeNameInstance,
+    },
+    FheProgramInputTrait, Params, TypeName as DeriveTypeName, WithContext,
+};
+
+#[derive(Debug, Clone, Copy, DeriveTypeName, PartialEq, Eq)]
+/**
+ * A single signed integer whose magnitude is a `64 * LIMBS`-bit unsigned
+ * integer.
+ *
+ * # Remarks
+ * Like [`Signed`](crate::types::bfv::Signed), each binary digit of the
+ * magnitude is a plaintext polynomial coefficient and negative values
+ * negate every coefficient. Decrypting returns
+ * [`Error::PlaintextOverflow`](sunscreen_runtime::Error::PlaintextOverflow)
+ * if a digit's magnitude is within `plain_modulus / 8` of wrapping around
+ * `plain_modulus / 2` or the value's magnitude doesn't fit in `LIMBS`
+ * limbs.
+ *
+ * Plaintext arithmetic panics on overflow.
+ */
+pub struct SignedBig<const LIMBS: usize> {
+    negative: bool,
+    magnitude: UInt<LIMBS>,
+}
+
+impl<const LIMBS: usize> SignedBig<LIMBS> {
+    /**
+     * Creates a value from its sign and magnitude. Zero is never negative.
+     */
+    pub fn from_sign_magnitude(negative: bool, magnitude: UInt<LIMBS>) -> Self {
+        Self {
+            negative: negative && magnitude != UInt::ZERO,
+            magnitude,
+        }
+    }
+
+    /**
+     * Whether this value is less than zero.
+     */
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /**
+     * The absolute value of this value.
+     */
+    pub fn magnitude(&self) -> UInt<LIMBS> {
+        self.magnitude
+    }
+}
+
+impl<const LIMBS: usize> NumCiphertexts for SignedBig<LIMBS> {
+    const NUM_CIPHERTEXTS: usize = 1;
+}
+
+impl<const LIMBS: usize> FheProgramInputTrait for SignedBig<LIMBS> {}
+impl<const LIMBS: usize> FheType for SignedBig<LIMBS> {}
+impl<const LIMBS: usize> BfvType for SignedBig<LIMBS> {}
+
+impl<const LIMBS: usize> std::fmt::Display for SignedBig<LIMBS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", BigInt::from(*self))
+    }
+}
+
+impl<const LIMBS: usize> Default for SignedBig<LIMBS> {
+    fn default() -> Self {
+        Self::from(0)
+    }
+}
+
+impl<const LIMBS: usize> TryIntoPlaintext for SignedBig<LIMBS> {
+    fn try_into_plaintext(
+        &self,
+        params: &Params,
+    ) -> std::result::Result<Plaintext, sunscreen_runtime::Error> {
+        let mut seal_plaintext = SealPlaintext::new()?;
+
+        let sig_bits = self.magnitude.bits_vartime();
+        seal_plaintext.resize(sig_bits);
+
+        for i in 0..sig_bits {
+            #[allow(clippy::unnecessary_cast)]
+            let bit_value = self.magnitude.bit_vartime(i) as u64;
+
+            let coeff_value = if self.negative {
+                bit_value * (params.plain_modulus - bit_value)
+            } else {
+                bit_value
+            };
+
+            seal_plaintext.set_coefficient(i, coeff_value);
+        }
+
+        Ok(Plaintext {
+            data_type: self.type_name_instance(),
+            inner: InnerPlaintext::Seal(vec![WithContext {
+                params: params.clone(),
+                data: seal_plaintext,
+            }]),
+        })
+    }
+}
+
+impl<const LIMBS: usize> TryFromPlaintext for SignedBig<LIMBS> {
+    fn try_from_plaintext(
+        plaintext: &Plaintext,
+        params: &Params,
+    ) -> std::result::Result<Self, sunscreen_runtime::Error> {
+        let val = match &plaintext.inner {
+            InnerPlaintext::Seal(p) => {
+                if p.len() != 1 {
+                    return Err(sunscreen_runtime::Error::IncorrectCiphertextCount);
+                }
+
+                // Accumulate exactly so high digits that cancel still
+                // decode correctly.
+                let mut val = BigInt::zero();
+
+                for i in 0..p[0].len() {
+                    let digit = signed_digit(p[0].get_coefficient(i), i, params)?;
+
+                    if digit != 0 {
+                        val += BigInt::from(digit) << i;
+                    }
+                }
+
+                Self::try_from(val).map_err(|_| {
+                    sunscreen_runtime::Error::plaintext_overflow(&format!(
+                        "the value's magnitude doesn't fit in {} bits",
+                        bits::<LIMBS>()
+                    ))
+                })?
+            }
+        };
+
+        Ok(val)
+    }
+}
+
+fn bits<const LIMBS: usize>() -> usize {
+    std::mem::size_of::<UInt<LIMBS>>() * 8
+}
+
+impl<const LIMBS: usize> From<i64> for SignedBig<LIMBS> {
+    fn from(val: i64) -> Self {
+        Self::from_sign_magnitude(val < 0, UInt::from_u64(val.unsigned_abs()))
+    }
+}
+
+impl<const LIMBS: usize> From<UInt<LIMBS>> for SignedBig<LIMBS> {
+    fn from(magnitude: UInt<LIMBS>) -> Self {
+        Self::from_sign_magnitude(false, magnitude)
+    }
+}
+
+impl<const LIMBS: usize> TryFrom<SignedBig<LIMBS>> for UInt<LIMBS> {
+    type Error = RuntimeError;
+
+    fn try_from(val: SignedBig<LIMBS>) -> Result<Self, Self::Error> {
+        if val.negative {
+            return Err(RuntimeError::fhe_type_error(
+                "Can't convert a negative value to UInt",
+            ));
+        }
+
+        Ok(val.magnitude)
+    }
+}
+
+impl<const LIMBS: usize> From<SignedBig<LIMBS>> for BigInt {
+    fn from(val: SignedBig<LIMBS>) -> Self {
+        let mut magnitude = BigUint::zero();
+
+        for i in 0..val.magnitude.bits_vartime() {
+            if val.magnitude.bit_vartime(i) == 1 {
+                magnitude.set_bit(i as u64, true);
+            }
+        }
+
+        if val.negative {
+            -BigInt::from(magnitude)
+        } else {
+            BigInt::from(magnitude)
+        }
+    }
+}
+
+impl<const LIMBS: usize> TryFrom<BigInt> for SignedBig<LIMBS> {
+    type Error = RuntimeError;
+
+    fn try_from(val: BigInt) -> Result<Self, Self::Error> {
+        let magnitude = val.magnitude();
+
+        if magnitude.bits() > bits::<LIMBS>() as u64 {
+            return Err(RuntimeError::fhe_type_error(&format!(
+                "{} doesn't fit in a {}-bit magnitude",
+                val,
+                bits::<LIMBS>()
+            )));
+        }
+
+        let mut result = UInt::ZERO;
+
+        for i in 0..magnitude.bits() as usize {
+            if magnitude.bit(i as u64) {
+                result = result.wrapping_add(&UInt::ONE.shl_vartime(i));
+            }
+        }
+
+        Ok(Self::from_sign_magnitude(val < BigInt::zero(), result))
+    }
+}
+
+impl<const LIMBS: usize> Add for SignedBig<LIMBS> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.negative == rhs.negative {
+            let (magnitude, carry) = self.magnitude.adc(&rhs.magnitude, Limb::ZERO);
+
+            assert!(carry == Limb::ZERO, "attempt to add with overflow");
+
+            Self::from_sign_magnitude(self.negative, magnitude)
+        } else if self.magnitude >= rhs.magnitude {
+            Self::from_sign_magnitude(self.negative, self.magnitude.wrapping_sub(&rhs.magnitude))
+        } else {
+            Self::from_sign_magnitude(rhs.negative, rhs.magnitude.wrapping_sub(&self.magnitude))
+        }
+    }
+}
+
+impl<const LIMBS: usize> Sub for SignedBig<LIMBS> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const LIMBS: usize> Mul for SignedBig<LIMBS> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let (magnitude, high) = self.magnitude.mul_wide(&rhs.magnitude);
+
+        assert!(high == UInt::ZERO, "attempt to multiply with overflow");
+
+        Self::from_sign_magnitude(self.negative != rhs.negative, magnitude)
+    }
+}
+
+impl<const LIMBS: usize> Neg for SignedBig<LIMBS> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_sign_magnitude(!self.negative, self.magnitude)
+    }
+}
+
+macro_rules! impl_i64_op {
+    ($($op:ident),+) => {
+        $(
+            paste! {
+                impl<const LIMBS: usize> $op<i64> for SignedBig<LIMBS> {
+                    type Output = Self;
+
+                    fn [<$op:lower>](self, rhs: i64) -> Self::Output {
+                        self.[<$op:lower>](Self::from(rhs))
+                    }
+                }
+
+                impl<const LIMBS: usize> $op<SignedBig<LIMBS>> for i64 {
+                    type Output = SignedBig<LIMBS>;
+
+                    fn [<$op:lower>](self, rhs: SignedBig<LIMBS>) -> Self::Output {
+                        Self::Output::from(self).[<$op:lower>](rhs)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_i64_op! {
+    Add, Sub, Mul
+}
+
+impl<const LIMBS: usize> Select for SignedBig<LIMBS> {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            bit == Self::from(0) || bit == Self::from(1),
+            "Selector must be 0 or 1, got {}",
+            bit
+        );
+
+        b + bit * (a - b)
+    }
+}
+
+macro_rules! impl_graph_cipher_op {
+    ($(($op:ident, $op_noun:ident)),+) => {
+        $(
+            paste! {
+                impl<const LIMBS: usize> [<GraphCipher $op>] for SignedBig<LIMBS> {
+                    type Left = Self;
+                    type Right = Self;
+
+                    fn [<graph_cipher_ $op:lower>](
+                        a: FheProgramNode<Cipher<Self::Left>>,
+                        b: FheProgramNode<Cipher<Self::Right>>,
+                    ) -> FheProgramNode<Cipher<Self::Left>> {
+                        with_fhe_ctx(|ctx| {
+                            let n = ctx.[<add_ $op_noun>](a.ids[0], b.ids[0]);
+
+                            FheProgramNode::new(&[n])
+                        })
+                    }
+                }
+
+                impl<const LIMBS: usize> [<GraphCipherPlain $op>] for SignedBig<LIMBS> {
+                    type Left = Self;
+                    type Right = Self;
+
+                    fn [<graph_cipher_plain_ $op:lower>](
+                        a: FheProgramNode<Cipher<Self::Left>>,
+                        b: FheProgramNode<Self::Right>,
+                    ) -> FheProgramNode<Cipher<Self::Left>> {
+                        with_fhe_ctx(|ctx| {
+                            let n = ctx.[<add_ $op_noun _plaintext>](a.ids[0], b.ids[0]);
+
+                            FheProgramNode::new(&[n])
+                        })
+                    }
+                }
+
+                impl<const LIMBS: usize> [<GraphCipherConst $op>] for SignedBig<LIMBS> {
+                    type Left = Self;
+                    type Right = i64;
+
+                    fn [<graph_cipher_const_ $op:lower>](
+                        a: FheProgramNode<Cipher<Self::Left>>,
+                        b: i64,
+                    ) -> FheProgramNode<Cipher<Self::Left>> {
+                        let lit = Self::graph_cipher_insert(b);
+                        with_fhe_ctx(|ctx| {
+                            let [<$op:lower>] = ctx.[<add_ $op_noun _plaintext>](a.ids[0], lit.ids[0]);
+
+                            FheProgramNode::new(&[[<$op:lower>]])
+                        })
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_graph_cipher_op! {
+    (Add, addition),
+    (Sub, subtraction),
+    (Mul, multiplication)
+}
+
+impl<const LIMBS: usize> GraphCipherInsert for SignedBig<LIMBS> {
+    type Lit = i64;
+    type Val = Self;
+
+    fn graph_cipher_insert(lit: Self::Lit) -> FheProgramNode<Self::Val> {
+        with_fhe_ctx(|ctx| {
+            let lit = Self::from(lit).try_into_plaintext(&ctx.data).unwrap();
+            let lit = ctx.add_plaintext_literal(lit.inner);
+
+            FheProgramNode::new(&[lit])
+        })
+    }
+}
+
+impl<const LIMBS: usize> GraphConstCipherSub for SignedBig<LIMBS> {
+    type Left = i64;
+    type Right = Self;
+
+    fn graph_const_cipher_sub(
+        a: i64,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Right>> {
+        let lit = Self::graph_cipher_insert(a);
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(b.ids[0], lit.ids[0]);
+            let n = ctx.add_negate(n);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LIMBS: usize> GraphPlainCipherSub for SignedBig<LIMBS> {
+    type Left = Self;
+    type Right = Self;
+
+    fn graph_plain_cipher_sub(
+        a: FheProgramNode<Self::Left>,
+        b: FheProgramNode<Cipher<Self::Right>>,
+    ) -> FheProgramNode<Cipher<Self::Left>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_subtraction_plaintext(b.ids[0], a.ids[0]);
+            let n = ctx.add_negate(n);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+impl<const LIMBS: usize> GraphCipherNeg for SignedBig<LIMBS> {
+    type Val = Self;
+
+    fn graph_cipher_neg(a: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>> {
+        with_fhe_ctx(|ctx| {
+            let n = ctx.add_negate(a.ids[0]);
+
+            FheProgramNode::new(&[n])
+        })
+    }
+}
+
+macro_rules! type_synonyms {
+    ($($bits:expr),+) => {
+        $(
+            paste! {
+                #[doc= concat!("Signed integer with a ", stringify!($bits), "-bit magnitude")]
+                pub type [<SignedBig $bits>] = SignedBig<{nlimbs!($bits)}>;
+            }
+        )+
+    };
+}
+
+type_synonyms! {
+    128, 256, 512
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_add_non_fhe() {
+        let a = SignedBig256::from(5);
+        let b = SignedBig256::from(-10);
+
+        assert_eq!(a + b, (-5).into());
+        assert_eq!(a + 10, 15.into());
+        assert_eq!(-10 + a, (-5).into());
+        assert_eq!(b + b, (-20).into());
+    }
+
+    #[test]
+    fn can_mul_non_fhe() {
+        let a = SignedBig128::from(-5);
+        let b = SignedBig128::from(10);
+
+        assert_eq!(a * b, (-50).into());
+        assert_eq!(a * -10, 50.into());
+        assert_eq!(a * SignedBig128::default(), 0.into());
+        assert!(!(a * SignedBig128::default()).is_negative());
+    }
+
+    #[test]
+    fn can_sub_non_fhe() {
+        let a = SignedBig128::from(5);
+        let b = SignedBig128::from(11);
+
+        assert_eq!(a - b, (-6).into());
+        assert_eq!(b - 5, 6.into());
+        assert_eq!(5 - b, (-6).into());
+        assert_eq!(a - a, 0.into());
+    }
+
+    #[test]
+    fn can_neg_non_fhe() {
+        let a = SignedBig128::from(5);
+
+        assert_eq!(-a, (-5).into());
+        assert_eq!(-SignedBig128::from(0), 0.into());
+    }
+
+    #[test]
+    fn can_exceed_64_bits() {
+        let a = SignedBig128::from(i64::MIN);
+        let b = a * a * -2;
+
+        assert_eq!(BigInt::from(b), -(BigInt::from(1i64) << 127usize));
+        assert_eq!(
+            b.to_string(),
+            (-(BigInt::from(1i64) << 127usize)).to_string()
+        );
+    }
+
+    #[test]
+    fn can_convert_big_int() {
+        let val: BigInt = -(BigInt::from(3i64) << 200usize) + 17;
+        let signed = SignedBig256::try_from(val.clone()).unwrap();
+
+        assert!(signed.is_negative());
+        assert_eq!(BigInt::from(signed), val);
+
+        assert!(SignedBig128::try_from(BigInt::from(1i64) << 128usize).is_err());
+        assert!(SignedBig128::try_from(-(BigInt::from(1i64) << 128usize) + 1).is_ok());
+    }
+
+    #[test]
+    fn can_convert_uint() {
+        let magnitude = UInt::<2>::from_u64(12345).shl_vartime(70);
+
+        assert_eq!(
+            UInt::try_from(SignedBig::from(magnitude)).unwrap(),
+            magnitude
+        );
+        assert!(UInt::try_from(-SignedBig::from(magnitude)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt to multiply with overflow")]
+    fn mul_overflow_panics() {
+        let a = SignedBig128::from_sign_magnitude(false, UInt::ONE.shl_vartime(64));
+
+        let _ = a * a;
+    }
+
+    #[test]
+    fn can_select_non_fhe() {
+        let a = SignedBig256::from(5);
+        let b = SignedBig256::from(-10);
+
+        assert_eq!(SignedBig::select(1.into(), a, b), a);
+        assert_eq!(SignedBig::select(0.into(), a, b), b);
+    }
+}
@@ -22,13 +22,23 @@ use crate::{
     },
 };
 use crate::{
-    types::{intern::FheProgramNode, BfvType, FheType, TypeNameInstance},
+    types::{
+        bfv::overflow::signed_digit, intern::FheProgramNode, BfvType, FheType, Select,
+        TypeNameInstance,
+    },
     FheProgramInputTrait, Params, TypeName as DeriveTypeName, WithContext,
 };
 
 #[derive(Debug, Clone, Copy, DeriveTypeName, PartialEq, Eq)]
 /**
  * A single unsigned integer.
+ *
+ * # Remarks
+ * Arithmetic wraps modulo `2^(64 * LIMBS)`. As with
+ * [`Signed`](crate::types::bfv::Signed), decrypting returns
+ * [`Error::PlaintextOverflow`](sunscreen_runtime::Error::PlaintextOverflow)
+ * if a digit's magnitude is within `plain_modulus / 8` of wrapping around
+ * `plain_modulus / 2`.
  */
 pub struct Unsigned<const LIMBS: usize> {
     val: UInt<LIMBS>,
@@ -93,25 +103,19 @@ impl<const LIMBS: usize> TryFromPlaintext for Unsigned<LIMBS> {
 
                 let bits = usize::min(std::mem::size_of::<UInt<LIMBS>>() * 8, p[0].len());
 
-                let negative_cutoff = (params.plain_modulus + 1) / 2;
-
+                // Digits beyond the type's width are multiples of
+                // 2^bits, so they vanish under wrapping arithmetic.
                 let mut val = UInt::ZERO;
                 for i in 0..bits {
-                    let coeff = p[0].get_coefficient(i);
-                    if coeff < negative_cutoff {
-                        val = wrapping_add(
-                            val,
-                            wrapping_mul(UInt::from_u8(0x1) << i, UInt::from_u64(coeff)),
-                        );
+                    let digit = signed_digit(p[0].get_coefficient(i), i, params)?;
+                    let magnitude = UInt::from_u64(digit.unsigned_abs() as u64);
+                    let term = wrapping_mul(UInt::from_u8(0x1) << i, magnitude);
+
+                    val = if digit >= 0 {
+                        wrapping_add(val, term)
                     } else {
-                        val = wrapping_sub(
-                            val,
-                            wrapping_mul(
-                                UInt::from_u8(0x1) << i,
-                                UInt::from_u64(params.plain_modulus - coeff),
-                            ),
-                        );
-                    }
+                        wrapping_sub(val, term)
+                    };
                 }
 
                 Self { val }
@@ -214,6 +218,18 @@ impl_std_op! {
     Add, Sub, Mul
 }
 
+impl<const LIMBS: usize> Select for Unsigned<LIMBS> {
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        debug_assert!(
+            bit.val == UInt::ZERO || bit.val == UInt::ONE,
+            "Selector must be 0 or 1, got {}",
+            bit
+        );
+
+        b + bit * (a - b)
+    }
+}
+
 macro_rules! impl_graph_cipher_op {
     ($(($op:ident, $op_noun:ident)),+) => {
         $(
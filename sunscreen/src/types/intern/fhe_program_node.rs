@@ -1,8 +1,8 @@
 use crate::{
     fhe::with_fhe_ctx,
     types::{
-        intern::FheLiteral, ops::*, Cipher, FheType, LaneCount, NumCiphertexts, SwapRows, Type,
-        TypeName,
+        intern::FheLiteral, ops::*, Cipher, FheType, LaneCount, NumCiphertexts, Select, SwapRows,
+        Type, TypeName,
     },
     INDEX_ARENA,
 };
@@ -484,6 +484,18 @@ where
     }
 }
 
+impl<T> Select for FheProgramNode<Cipher<T>>
+where
+    T: FheType
+        + GraphCipherAdd<Left = T, Right = T>
+        + GraphCipherSub<Left = T, Right = T>
+        + GraphCipherMul<Left = T, Right = T>,
+{
+    fn select(bit: Self, a: Self, b: Self) -> Self {
+        b + bit * (a - b)
+    }
+}
+
 impl<T> Shl<u64> for FheProgramNode<Cipher<T>>
 where
     T: FheType + GraphCipherRotateLeft,
@@ -0,0 +1,40 @@
+use crate::types::{intern::FheProgramNode, Cipher, FheType};
+
+/**
+ * Implemented by user-defined composite types created with
+ * `#[derive(FheType)]`. Exposes the individual fields of an
+ * encrypted struct during FHE program construction.
+ *
+ * You should not implement this trait yourself; the derive macro
+ * generates the implementation along with the `Self::CipherFields`
+ * companion type.
+ */
+pub trait FheStruct: FheType {
+    /**
+     * A struct with the same field names as `Self`, where each field
+     * is an encrypted [`FheProgramNode`] of the corresponding type.
+     */
+    type CipherFields;
+
+    /**
+     * Splits an encrypted struct into its fields.
+     */
+    fn cipher_fields(node: FheProgramNode<Cipher<Self>>) -> Self::CipherFields;
+}
+
+impl<T> FheProgramNode<Cipher<T>>
+where
+    T: FheStruct,
+{
+    /**
+     * Returns the fields of this encrypted struct as individual
+     * [`FheProgramNode`]s.
+     *
+     * The returned value can be turned back into a
+     * `FheProgramNode<Cipher<T>>` with `into()` or returned directly
+     * from an FHE program.
+     */
+    pub fn fields(self) -> T::CipherFields {
+        T::cipher_fields(self)
+    }
+}
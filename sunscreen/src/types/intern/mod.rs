@@ -1,11 +1,13 @@
 mod fhe_literal;
 mod fhe_program_node;
+mod fhe_struct;
 mod input;
 mod output;
 mod u64_literal;
 
 pub use fhe_literal::*;
 pub use fhe_program_node::*;
+pub use fhe_struct::*;
 pub use input::*;
 pub use output::*;
 pub use u64_literal::*;
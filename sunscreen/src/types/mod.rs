@@ -65,16 +65,17 @@ pub mod intern;
 
 /**
  * Contains the set of ops traits that dictate legal operations
- * for FHE data types.
+ * for FHE data types. Generic FHE programs use these as bounds on
+ * their type parameters.
  */
-mod ops;
+pub mod ops;
 
 /**
  * Contains types used in creating zero-knowledge proof R1CS circuits.
  */
 pub mod zkp;
 
-use crate::types::ops::*;
+use crate::types::{intern::FheProgramNode, ops::*};
 
 pub use sunscreen_runtime::{
     BfvType, FheType, NumCiphertexts, TryFromPlaintext, TryIntoPlaintext, Type, TypeName,
@@ -106,6 +107,70 @@ pub trait LaneCount {
     fn lane_count() -> usize;
 }
 
+/**
+ * Chooses between two values using a selector that is either 0 or 1.
+ *
+ * # Remarks
+ * `select(bit, a, b)` computes `b + bit * (a - b)`, which yields `a` when
+ * `bit` is 1 and `b` when `bit` is 0. Other selector values produce
+ * meaningless results. On [`Batched`](crate::types::bfv::Batched) types,
+ * `bit` is a mask and each lane selects independently.
+ *
+ * In an [`fhe_program`](crate::fhe_program), the selector is encrypted and
+ * the program has no way to check its value. The plaintext types implement
+ * this trait with the same arithmetic and, in debug builds, panic if the
+ * selector isn't 0 or 1. Writing selection logic generically over
+ * [`Select`] lets you simulate it on plaintexts to catch bad selectors.
+ *
+ * Selecting between ciphertexts costs 1 subtraction, 1 multiplication, and
+ * 1 addition.
+ */
+pub trait Select: Sized {
+    /**
+     * Returns `a` if `bit` is 1 and `b` if `bit` is 0.
+     */
+    fn select(bit: Self, a: Self, b: Self) -> Self;
+}
+
+/**
+ * Reductions and broadcasts across the lanes of a
+ * [`Batched`](crate::types::bfv::Batched) type.
+ *
+ * # Remarks
+ * Within an [`fhe_program`](crate::fhe_program), these emit
+ * `log2(LANES)` rotate-and-add steps. Every rotation amount is a power of
+ * 2, which the Galois keys generated for the program always include.
+ *
+ * The plaintext implementation on [`Batched`](crate::types::bfv::Batched)
+ * computes the same result.
+ */
+pub trait LaneOps: Sized {
+    /**
+     * Sums the lanes in each row. Every lane in a row holds the row's sum.
+     */
+    fn sum_lanes(self) -> Self;
+
+    /**
+     * Adds the two rows together. Lane `i` of both rows holds the sum of
+     * lane `i` from each row.
+     */
+    fn sum_rows(self) -> Self;
+
+    /**
+     * Copies lane `lane` of each row into every lane of that row.
+     *
+     * # Panics
+     * If `lane` is not less than the number of lanes.
+     */
+    fn broadcast_lane(self, lane: usize) -> Self;
+
+    /**
+     * Computes the inner product of `self` and `other` across all lanes
+     * of both rows. Every lane holds the result.
+     */
+    fn dot(self, other: &Self) -> Self;
+}
+
 #[derive(Copy, Clone, Debug)]
 /**
  * Declares a type T as being encrypted in an [`fhe_program`](crate::fhe_program).
@@ -117,6 +182,24 @@ where
     _val: T,
 }
 
+impl<T> Cipher<T>
+where
+    T: FheType,
+    FheProgramNode<Cipher<T>>: Select,
+{
+    /**
+     * Returns `a` if the encrypted `bit` is 1 and `b` if it is 0. See
+     * [`Select`].
+     */
+    pub fn select(
+        bit: FheProgramNode<Cipher<T>>,
+        a: FheProgramNode<Cipher<T>>,
+        b: FheProgramNode<Cipher<T>>,
+    ) -> FheProgramNode<Cipher<T>> {
+        Select::select(bit, a, b)
+    }
+}
+
 impl<T> NumCiphertexts for Cipher<T>
 where
     T: FheType,
@@ -16,20 +16,38 @@ where
     fn graph_cipher_swap_rows(x: FheProgramNode<Cipher<Self>>) -> FheProgramNode<Cipher<Self>>;
 }
 
+/**
+ * Rotates the lanes of the given ciphertext to the left.
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
 pub trait GraphCipherRotateLeft
 where
     Self: FheType,
 {
+    /**
+     * Rotate the lanes in the given ciphertext left by `amount`.
+     */
     fn graph_cipher_rotate_left(
         x: FheProgramNode<Cipher<Self>>,
         amount: u64,
     ) -> FheProgramNode<Cipher<Self>>;
 }
 
+/**
+ * Rotates the lanes of the given ciphertext to the right.
+ *
+ * This trait is an implementation detail of FHE program compilation;
+ * you should not directly call methods on this trait.
+ */
 pub trait GraphCipherRotateRight
 where
     Self: FheType,
 {
+    /**
+     * Rotate the lanes in the given ciphertext right by `amount`.
+     */
     fn graph_cipher_rotate_right(
         x: FheProgramNode<Cipher<Self>>,
         amount: u64,
@@ -0,0 +1,288 @@
+use sunscreen_zkp_backend::FieldSpec;
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::OneHot, poseidon_hash, ConstrainCmp, ConstrainEq, Field, IntoProgramNode,
+        ProgramNode,
+    },
+};
+
+/**
+ * Returns `arr[index]`, where `index` may be a private value.
+ *
+ * # Remarks
+ * This selects the element with a one-hot vector of hidden bits, which
+ * costs about 3.5 constraints per element of `arr`. The proof fails to
+ * validate unless `0 <= index < arr.len()`, so this also range checks
+ * `index`.
+ *
+ * When `index` is a compile-time constant, index the array directly
+ * instead; it's free.
+ *
+ * # Panics
+ * If `arr` is empty.
+ */
+pub fn private_index<F, I>(arr: &[ProgramNode<Field<F>>], index: I) -> ProgramNode<Field<F>>
+where
+    F: FieldSpec,
+    I: IntoProgramNode<Output = Field<F>>,
+{
+    let index = index.into_program_node();
+
+    let selector = invoke_gadget(OneHot::new(arr.len()), index.ids);
+
+    selector
+        .iter()
+        .zip(arr.iter())
+        .map(|(s, x)| ProgramNode::<Field<F>>::new(&[*s]) * *x)
+        .reduce(|acc, x| acc + x)
+        .unwrap()
+}
+
+/**
+ * Asserts `b` is a permutation of `a`; that is, they contain the same
+ * values with the same multiplicities in any order. The proof will fail
+ * to validate if it isn't.
+ *
+ * # Remarks
+ * This uses a grand-product argument: `a` is a permutation of `b` exactly
+ * when `prod(a_i - r) == prod(b_i - r)` as polynomials in `r`. The
+ * challenge `r` is the [`poseidon_hash`] of `a` and `b`, which binds it
+ * to their values (Fiat-Shamir), so a cheating prover succeeds with
+ * negligible probability.
+ *
+ * Hashing dominates the cost at about 240 constraints per element of
+ * `a`. To check several permutations over the same values, hash them
+ * once and call [`constrain_permutation_with_challenge`].
+ *
+ * # Panics
+ * If `a` and `b` have different lengths or `F` isn't the Bulletproofs
+ * scalar field.
+ */
+pub fn constrain_permutation<F: FieldSpec>(
+    a: &[ProgramNode<Field<F>>],
+    b: &[ProgramNode<Field<F>>],
+) {
+    let values = a.iter().chain(b.iter()).copied().collect::<Vec<_>>();
+
+    constrain_permutation_with_challenge(a, b, poseidon_hash(&values));
+}
+
+/**
+ * Asserts `b` is a permutation of `a` with a grand-product argument at
+ * the given challenge. See [`constrain_permutation`].
+ *
+ * # Remarks
+ * For this to be sound, `challenge` must be a hash (e.g.
+ * [`poseidon_hash`]) of every non-constant value in `a` and `b`. The
+ * prover can otherwise pick values that pass at `challenge` without
+ * being a permutation.
+ *
+ * This costs 2 constraints per element.
+ *
+ * # Panics
+ * If `a` and `b` have different lengths.
+ */
+pub fn constrain_permutation_with_challenge<F, C>(
+    a: &[ProgramNode<Field<F>>],
+    b: &[ProgramNode<Field<F>>],
+    challenge: C,
+) where
+    F: FieldSpec,
+    C: IntoProgramNode<Output = Field<F>>,
+{
+    assert_eq!(
+        a.len(),
+        b.len(),
+        "A permutation must have the same number of elements as the original"
+    );
+
+    let challenge = challenge.into_program_node();
+
+    let grand_product = |x: &[ProgramNode<Field<F>>]| {
+        x.iter()
+            .map(|x| *x - challenge)
+            .reduce(|acc, x| acc * x)
+            .unwrap_or_else(|| Field::from(1u8).into_program_node())
+    };
+
+    grand_product(a).constrain_eq(grand_product(b));
+}
+
+/**
+ * Asserts `arr` is sorted in ascending order, where the difference
+ * between adjacent elements is at most `bits` bits.
+ *
+ * # Remarks
+ * Combine this with [`constrain_permutation`] to prove one array is the
+ * sorted version of another.
+ */
+pub fn constrain_sorted_bounded<F: FieldSpec>(arr: &[ProgramNode<Field<F>>], bits: usize) {
+    for x in arr.windows(2) {
+        x[0].constrain_le_bounded(x[1], bits);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::Compiler;
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn can_index_with_private_value() {
+        #[zkp_program]
+        fn lookup<F: FieldSpec>(
+            i: Field<F>,
+            #[public] table: [Field<F>; 5],
+            #[public] x: Field<F>,
+        ) {
+            private_index(&table, i).constrain_eq(x);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(lookup)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(lookup).unwrap();
+
+        let table = [10u8, 20, 30, 40, 50].map(BpField::from);
+
+        let test_case = |i: i64, x: u8, expect_pass: bool| {
+            let public: Vec<ZkpProgramInput> = vec![table.into(), BpField::from(x).into()];
+
+            let result = runtime.prove(
+                program,
+                vec![BpField::from(i).into()],
+                public.clone(),
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, public, Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        for i in 0..5 {
+            test_case(i, 10 * (i as u8 + 1), true);
+        }
+
+        test_case(1, 30, false);
+        test_case(5, 0, false);
+        test_case(-1, 50, false);
+    }
+
+    #[test]
+    fn can_prove_sorted_permutation() {
+        #[zkp_program]
+        fn sort<F: FieldSpec>(a: [Field<F>; 6], sorted: [Field<F>; 6]) {
+            constrain_permutation(&a, &sorted);
+            constrain_sorted_bounded(&sorted, 16);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(sort)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(sort).unwrap();
+
+        let test_case = |a: [u16; 6], sorted: [u16; 6], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![a.map(BpField::from), sorted.map(BpField::from)],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case([5, 3, 9, 3, 0, 100], [0, 3, 3, 5, 9, 100], true);
+        test_case([1, 1, 1, 1, 1, 1], [1, 1, 1, 1, 1, 1], true);
+
+        // Not sorted.
+        test_case([5, 3, 9, 3, 0, 100], [0, 3, 5, 3, 9, 100], false);
+
+        // Sorted, but not a permutation.
+        test_case([5, 3, 9, 3, 0, 100], [0, 3, 5, 5, 9, 100], false);
+        test_case([5, 3, 9, 3, 0, 100], [0, 3, 5, 9, 100, 101], false);
+    }
+
+    #[test]
+    fn can_prove_rows_are_permutations_of_digits() {
+        #[zkp_program]
+        fn digit_rows<F: FieldSpec>(rows: [[Field<F>; 4]; 2]) {
+            let digits = [1u8, 2, 3, 4].map(|x| Field::<F>::from(x).into_program_node());
+            let challenge = poseidon_hash(&rows.iter().flatten().copied().collect::<Vec<_>>());
+
+            for row in rows {
+                constrain_permutation_with_challenge(&row, &digits, challenge);
+            }
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(digit_rows)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(digit_rows).unwrap();
+
+        let test_case = |rows: [[u8; 4]; 2], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![rows.map(|r| r.map(BpField::from))],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case([[4, 2, 1, 3], [1, 2, 3, 4]], true);
+        test_case([[4, 2, 1, 3], [1, 2, 2, 4]], false);
+        test_case([[4, 2, 1, 1], [1, 2, 3, 4]], false);
+    }
+}
@@ -0,0 +1,333 @@
+use std::marker::PhantomData;
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, FieldSpec, Gadget};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{Field, ProgramNode},
+    ZkpResult,
+};
+
+/**
+ * A gadget written as 2 closures rather than an implementation of
+ * [`Gadget`].
+ *
+ * * `witness` computes the hidden inputs from the gadget inputs' values
+ *   when proving. It returns an error if it can't, e.g. if the inputs
+ *   are invalid.
+ * * `constraints` builds the gadget's sub-circuit from the gadget inputs
+ *   and hidden inputs, and returns the gadget's outputs.
+ *
+ * The number of gadget inputs `I`, hidden inputs `H` and outputs `O` are
+ * inferred from the closures' array types.
+ *
+ * # Remarks
+ * In debug builds, proving checks the witness satisfies the gadget's
+ * constraints, so a bad witness function fails with an error naming the
+ * gadget rather than an unsatisfied constraint somewhere in the program.
+ *
+ * # Example
+ * ```rust
+ * # use sunscreen::{types::zkp::{ClosureGadget, ConstrainEq, Field, ProgramNode}, FieldSpec};
+ * // Returns the inverse of a non-zero x.
+ * fn inverse<F: FieldSpec>(x: ProgramNode<Field<F>>) -> ProgramNode<Field<F>> {
+ *     let [x_inv] = ClosureGadget::<F, _, _, 1, 1, 1>::new(
+ *         |[x]| Ok([x.inverse_fp(&F::FIELD_MODULUS)]),
+ *         |[x], [x_inv]| {
+ *             (x * x_inv).constrain_eq(Field::from(1u8));
+ *             [x_inv]
+ *         },
+ *     )
+ *     .invoke([x]);
+ *
+ *     x_inv
+ * }
+ * ```
+ */
+pub struct ClosureGadget<F, W, C, const I: usize, const H: usize, const O: usize> {
+    witness: W,
+    constraints: C,
+    _phantom: PhantomData<fn() -> F>,
+}
+
+impl<F, W, C, const I: usize, const H: usize, const O: usize> ClosureGadget<F, W, C, I, H, O>
+where
+    F: FieldSpec,
+    W: Fn([BigInt; I]) -> ZkpResult<[BigInt; H]> + Send + Sync + 'static,
+    C: Fn([ProgramNode<Field<F>>; I], [ProgramNode<Field<F>>; H]) -> [ProgramNode<Field<F>>; O]
+        + Send
+        + Sync
+        + 'static,
+{
+    /**
+     * Creates a gadget from its witness and constraint functions.
+     */
+    pub fn new(witness: W, constraints: C) -> Self {
+        Self {
+            witness,
+            constraints,
+            _phantom: PhantomData,
+        }
+    }
+
+    /**
+     * Adds this gadget to the current ZKP program and returns its
+     * outputs.
+     *
+     * # Panics
+     * Calling this function inside a [`with_zkp_ctx`](crate::with_zkp_ctx)
+     * callback.
+     */
+    pub fn invoke(self, inputs: [ProgramNode<Field<F>>; I]) -> [ProgramNode<Field<F>>; O] {
+        let ids = inputs.map(|x| x.ids[0]);
+
+        let outputs = invoke_gadget(self, &ids);
+
+        std::array::from_fn(|i| ProgramNode::new(&[outputs[i]]))
+    }
+
+    fn nodes<const N: usize>(ids: &[NodeIndex]) -> [ProgramNode<Field<F>>; N] {
+        std::array::from_fn(|i| ProgramNode::new(&[ids[i]]))
+    }
+
+    /**
+     * Builds a standalone program whose private inputs are the gadget's
+     * inputs followed by its hidden inputs and runs it on the given
+     * values.
+     */
+    #[cfg(debug_assertions)]
+    fn check_witness(&self, inputs: &[BigInt], hidden_inputs: &[BigInt]) -> ZkpResult<()> {
+        use std::{mem::transmute, panic};
+
+        use bumpalo::Bump;
+        use sunscreen_zkp_backend::jit_prover;
+
+        use crate::{
+            zkp::{compile, with_zkp_ctx, ZkpContextOps},
+            ZkpContext, ZkpData, ZkpError, CURRENT_ZKP_CTX, INDEX_ARENA,
+        };
+
+        let mut context = ZkpContext::new(ZkpData::new());
+
+        // The caller may be building a program on this thread, so save its
+        // context and arena and restore them afterwards.
+        let prev_arena = INDEX_ARENA.with(|allocator| allocator.replace(Bump::new()));
+
+        CURRENT_ZKP_CTX.with(|ctx| {
+            // Transmute away the lifetime to 'static. This is safe because
+            // we restore the previous context before returning.
+            let prev_ctx = ctx.replace(Some(unsafe { transmute(&mut context) }));
+
+            let panic_res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+                let ids = with_zkp_ctx(|ctx| {
+                    (0..I + H)
+                        .map(|_| ctx.add_private_input())
+                        .collect::<Vec<_>>()
+                });
+
+                (self.constraints)(Self::nodes(&ids[..I]), Self::nodes(&ids[I..]));
+            }));
+
+            ctx.replace(prev_ctx);
+            INDEX_ARENA.with(|allocator| allocator.replace(prev_arena));
+
+            if let Err(err) = panic_res {
+                panic::resume_unwind(err);
+            }
+        });
+
+        let program = compile(&context.graph);
+
+        let vals = inputs
+            .iter()
+            .chain(hidden_inputs.iter())
+            .map(|x| F::BackendField::try_from(*x))
+            .collect::<ZkpResult<Vec<_>>>()?;
+
+        jit_prover::<F>(&program, &[], &[], &vals).map_err(|e| {
+            ZkpError::gadget_error(&format!(
+                "Witness for {} doesn't satisfy its constraints: {:?}",
+                self.debug_name(),
+                e
+            ))
+        })?;
+
+        Ok(())
+    }
+}
+
+impl<F, W, C, const I: usize, const H: usize, const O: usize> Gadget
+    for ClosureGadget<F, W, C, I, H, O>
+where
+    F: FieldSpec,
+    W: Fn([BigInt; I]) -> ZkpResult<[BigInt; H]> + Send + Sync + 'static,
+    C: Fn([ProgramNode<Field<F>>; I], [ProgramNode<Field<F>>; H]) -> [ProgramNode<Field<F>>; O]
+        + Send
+        + Sync
+        + 'static,
+{
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let inputs = std::array::from_fn(|i| gadget_inputs[i]);
+
+        let hidden_inputs = (self.witness)(inputs)?;
+
+        #[cfg(debug_assertions)]
+        self.check_witness(gadget_inputs, &hidden_inputs)?;
+
+        Ok(hidden_inputs.to_vec())
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[NodeIndex],
+        hidden_inputs: &[NodeIndex],
+    ) -> Vec<NodeIndex> {
+        let outputs = (self.constraints)(Self::nodes(gadget_inputs), Self::nodes(hidden_inputs));
+
+        outputs.iter().map(|x| x.ids[0]).collect()
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        I
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        H
+    }
+
+    fn debug_name(&self) -> &'static str {
+        std::any::type_name::<W>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::{
+        types::zkp::{ConstrainEq, IntoProgramNode},
+        Compiler, ZkpError,
+    };
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    /**
+     * Computes floor(x / 2^8) and x mod 2^8 for 16-bit x, with a
+     * range check on the remainder.
+     */
+    fn split_byte<F: FieldSpec>(x: ProgramNode<Field<F>>) -> [ProgramNode<Field<F>>; 2] {
+        ClosureGadget::<F, _, _, 1, 9, 2>::new(
+            |[x]| {
+                if x >= BigInt::from(1u32 << 16) {
+                    return Err(ZkpError::gadget_error("Value too large."));
+                }
+
+                let q = BigInt::from(x.shr_vartime(8));
+
+                Ok(std::array::from_fn(|i| match i {
+                    0 => q,
+                    _ => BigInt::from(x.bit_vartime(i - 1)),
+                }))
+            },
+            |[x], [q, bits @ ..]| {
+                let zero = Field::from(0u8).into_program_node();
+                let one = Field::from(1u8).into_program_node();
+
+                let mut r = zero;
+
+                for (i, b) in bits.iter().enumerate() {
+                    (*b * (*b - one)).constrain_eq(zero);
+                    r = r + *b * Field::from(1u32 << i).into_program_node();
+                }
+
+                (q * Field::from(256u32).into_program_node() + r).constrain_eq(x);
+
+                [q, r]
+            },
+        )
+        .invoke([x])
+    }
+
+    #[test]
+    fn can_use_closure_gadget() {
+        #[zkp_program]
+        fn split<F: FieldSpec>(x: Field<F>, #[public] q: Field<F>, #[public] r: Field<F>) {
+            let [actual_q, actual_r] = split_byte(x);
+
+            actual_q.constrain_eq(q);
+            actual_r.constrain_eq(r);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(split)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(split).unwrap();
+
+        let test_case = |x: u32, q: u32, r: u32, expect_pass: bool| {
+            let public = vec![BpField::from(q), BpField::from(r)];
+
+            let result = runtime.prove(program, vec![BpField::from(x)], public.clone(), vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, public, Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(0x1234, 0x12, 0x34, true);
+        test_case(0xff, 0, 0xff, true);
+        test_case(0x1234, 0x12, 0x35, false);
+        test_case(0x10000, 0x100, 0, false);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn debug_check_rejects_bad_witness() {
+        #[zkp_program]
+        fn square_root<F: FieldSpec>(x: Field<F>) {
+            let [root] = ClosureGadget::<F, _, _, 1, 1, 1>::new(
+                // Wrong: returns x rather than its square root.
+                |[x]| Ok([x]),
+                |[x], [root]| {
+                    (root * root).constrain_eq(x);
+                    [root]
+                },
+            )
+            .invoke([x]);
+
+            root.constrain_eq(Field::from(3u8));
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(square_root)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(square_root).unwrap();
+
+        let result = runtime.prove(program, vec![BpField::from(9u8)], vec![], vec![]);
+
+        let err = format!("{:?}", result.unwrap_err());
+
+        assert!(err.contains("doesn't satisfy its constraints"));
+    }
+}
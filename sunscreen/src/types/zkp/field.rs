@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use subtle::{Choice, ConditionallySelectable};
 use sunscreen_compiler_macros::TypeName;
-use sunscreen_zkp_backend::{BigInt, FieldSpec};
+use sunscreen_zkp_backend::{BigInt, FieldSpec, Gadget};
 
 use crate::{
     invoke_gadget,
@@ -11,8 +11,9 @@ use crate::{
 };
 
 use crate::types::zkp::{
-    gadgets::ToUInt, ConstrainEqVarVar, IntoProgramNode, MulVar, NegVar, NumFieldElements,
-    ToNativeFields, ZkpType,
+    gadgets::{Inverse, IsZero, ToUInt},
+    select, ConstrainEqVarVar, DivRemVarVar, DivVar, IntoProgramNode, MulVar, NegVar,
+    NumFieldElements, ToField, ToNativeFields, ZkpBool, ZkpType,
 };
 
 use crate as sunscreen;
@@ -195,6 +196,66 @@ impl<F: FieldSpec> NegVar for Field<F> {
     }
 }
 
+impl<F: FieldSpec> DivVar for Field<F> {
+    fn div(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // Inverse constrains rhs * rhs^-1 == 1, so the proof fails when
+        // dividing by zero.
+        let rhs_inv = invoke_gadget(Inverse::new(F::FIELD_MODULUS), rhs.ids);
+
+        with_zkp_ctx(|ctx| {
+            let o = ctx.add_multiplication(lhs.ids[0], rhs_inv[0]);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+impl<F: FieldSpec> DivRemVarVar for Field<F> {
+    fn div_rem_bounded(
+        lhs: ProgramNode<Self>,
+        rhs: ProgramNode<Self>,
+        bits: usize,
+    ) -> (ProgramNode<Self>, ProgramNode<Self>) {
+        Self::div_rem_with(SignedModulus::new(F::FIELD_MODULUS, bits), lhs, rhs, bits)
+    }
+}
+
+impl<F: FieldSpec> Field<F> {
+    /**
+     * Divides `lhs` by `rhs` with `gadget`, which outputs a quotient and
+     * remainder constrained as [`SignedModulus`] does, then bounds the
+     * quotient to `bits + 1` bits.
+     */
+    fn div_rem_with<G: Gadget>(
+        gadget: G,
+        lhs: ProgramNode<Self>,
+        rhs: ProgramNode<Self>,
+        bits: usize,
+    ) -> (ProgramNode<Self>, ProgramNode<Self>) {
+        assert!(
+            2 * bits + 2 < F::FIELD_MODULUS.bits_vartime(),
+            "Operands with {} bits may overflow the field.",
+            bits
+        );
+
+        let outputs = invoke_gadget(gadget, &[lhs.ids[0], rhs.ids[0]]);
+
+        let q = ProgramNode::<Self>::new(&[outputs[0]]);
+        let r = ProgramNode::<Self>::new(&[outputs[1]]);
+
+        // SignedModulus bounds the remainder, but every remainder has some
+        // quotient satisfying rhs * q + r == lhs in the field. Bounding
+        // the quotient to [-2^bits, 2^bits) as well ensures the equation
+        // holds over the integers, so q and r are unique.
+        let offset = Field::<F>::from(BigInt::from(BigInt::ONE.shl_vartime(bits)));
+        let q_offset = q + offset.into_program_node();
+
+        invoke_gadget(ToUInt::new(bits + 1), q_offset.ids);
+
+        (q, r)
+    }
+}
+
 impl<F: FieldSpec> ConstrainEqVarVar for Field<F> {
     fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
         with_zkp_ctx(|ctx| {
@@ -321,6 +382,252 @@ impl<F: FieldSpec> ToBinary<F> for ProgramNode<Field<F>> {
     }
 }
 
+/**
+ * Comparisons that return their result as a boolean rather than
+ * constraining it to be true.
+ *
+ * # Remarks
+ * For the bounded comparisons, `bits` is the maximum number of bits
+ * required to represent `abs(rhs - lhs)` as an unsigned value. This value
+ * plus one must be less than the number of bits needed to represent the
+ * field modulus. If the operands differ by too much, the proof will fail
+ * to validate.
+ */
+pub trait Compare<F: FieldSpec> {
+    /**
+     * Returns whether this value is strictly less than `rhs`.
+     */
+    fn lt_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns whether this value is less than or equal to `rhs`.
+     */
+    fn le_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns whether this value is strictly greater than `rhs`.
+     */
+    fn gt_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns whether this value is greater than or equal to `rhs`.
+     */
+    fn ge_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns whether this value equals `rhs`.
+     */
+    fn eq<R>(self, rhs: R) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns whether this value is zero.
+     */
+    fn is_zero(self) -> ProgramNode<ZkpBool<F>>;
+
+    /**
+     * Returns the lesser of this value and `rhs`.
+     */
+    fn min_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<Field<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Returns the greater of this value and `rhs`.
+     */
+    fn max_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<Field<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>;
+}
+
+impl<F: FieldSpec> Compare<F> for ProgramNode<Field<F>> {
+    fn lt_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        let lhs_plus_1 = self + Field::from(1u8).into_program_node();
+
+        lhs_plus_1.le_bounded(rhs, bits)
+    }
+
+    fn le_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        // rhs - lhs + 2^bits lies in [0, 2^(bits + 1)) and its top bit is
+        // set exactly when rhs - lhs >= 0.
+        let offset = Field::<F>::from(BigInt::from(BigInt::ONE.shl_vartime(bits)));
+        let diff = rhs.into_program_node() - self + offset.into_program_node();
+
+        let diff_bits = invoke_gadget(ToUInt::new(bits + 1), diff.ids);
+
+        ProgramNode::new(&[diff_bits[bits]])
+    }
+
+    fn gt_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        rhs.into_program_node().lt_bounded(self, bits)
+    }
+
+    fn ge_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        rhs.into_program_node().le_bounded(self, bits)
+    }
+
+    fn eq<R>(self, rhs: R) -> ProgramNode<ZkpBool<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        (self - rhs.into_program_node()).is_zero()
+    }
+
+    fn is_zero(self) -> ProgramNode<ZkpBool<F>> {
+        let out = invoke_gadget(IsZero::new(F::FIELD_MODULUS), self.ids);
+
+        ProgramNode::new(&out)
+    }
+
+    fn min_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<Field<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        let rhs = rhs.into_program_node();
+
+        select(self.lt_bounded(rhs, bits), self, rhs)
+    }
+
+    fn max_bounded<R>(self, rhs: R, bits: usize) -> ProgramNode<Field<F>>
+    where
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        let rhs = rhs.into_program_node();
+
+        select(self.lt_bounded(rhs, bits), rhs, self)
+    }
+}
+
+/**
+ * Bitwise operations on values interpreted as `N`-bit unsigned integers.
+ *
+ * # Remarks
+ * Each operation decomposes its operands with [`ToBinary::to_unsigned`],
+ * so the proof fails to validate if an operand doesn't fit in `N` bits.
+ */
+pub trait BitwiseUnsigned<F: FieldSpec> {
+    /**
+     * Computes `self & rhs`.
+     */
+    fn and_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes `self | rhs`.
+     */
+    fn or_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes `self ^ rhs`.
+     */
+    fn xor_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes `self << shift`, discarding bits shifted beyond `N`.
+     */
+    fn shl_unsigned<const N: usize>(&self, shift: usize) -> ProgramNode<Field<F>>;
+
+    /**
+     * Computes `self >> shift`.
+     */
+    fn shr_unsigned<const N: usize>(&self, shift: usize) -> ProgramNode<Field<F>>;
+}
+
+/**
+ * Sums `bit * 2^power` over the given bits.
+ */
+pub(crate) fn from_unsigned_bits<F: FieldSpec>(
+    bits: impl Iterator<Item = (usize, ProgramNode<Field<F>>)>,
+) -> ProgramNode<Field<F>> {
+    let mut sum = Field::<F>::from(0u8).into_program_node();
+
+    for (power, bit) in bits {
+        let scale = Field::<F>::from(BigInt::from(BigInt::ONE.shl_vartime(power)));
+
+        sum = sum + scale.into_program_node() * bit;
+    }
+
+    sum
+}
+
+fn bitwise_unsigned<F: FieldSpec, const N: usize>(
+    lhs: &ProgramNode<Field<F>>,
+    rhs: &ProgramNode<Field<F>>,
+    op: impl Fn(ProgramNode<ZkpBool<F>>, ProgramNode<ZkpBool<F>>) -> ProgramNode<ZkpBool<F>>,
+) -> ProgramNode<Field<F>> {
+    let lhs_bits = lhs.to_unsigned::<N>();
+    let rhs_bits = rhs.to_unsigned::<N>();
+
+    // to_unsigned already asserts each bit is binary.
+    from_unsigned_bits(
+        lhs_bits
+            .iter()
+            .zip(rhs_bits.iter())
+            .enumerate()
+            .map(|(i, (a, b))| {
+                let bit = op(ProgramNode::new(a.ids), ProgramNode::new(b.ids));
+
+                (i, bit.to_field())
+            }),
+    )
+}
+
+impl<F: FieldSpec> BitwiseUnsigned<F> for ProgramNode<Field<F>> {
+    fn and_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>> {
+        bitwise_unsigned::<F, N>(self, rhs, |a, b| a & b)
+    }
+
+    fn or_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>> {
+        bitwise_unsigned::<F, N>(self, rhs, |a, b| a | b)
+    }
+
+    fn xor_unsigned<const N: usize>(&self, rhs: &Self) -> ProgramNode<Field<F>> {
+        bitwise_unsigned::<F, N>(self, rhs, |a, b| a ^ b)
+    }
+
+    fn shl_unsigned<const N: usize>(&self, shift: usize) -> ProgramNode<Field<F>> {
+        let bits = self.to_unsigned::<N>();
+
+        from_unsigned_bits(
+            bits.into_iter()
+                .enumerate()
+                .take(N.saturating_sub(shift))
+                .map(|(i, bit)| (i + shift, bit)),
+        )
+    }
+
+    fn shr_unsigned<const N: usize>(&self, shift: usize) -> ProgramNode<Field<F>> {
+        let bits = self.to_unsigned::<N>();
+
+        from_unsigned_bits(
+            bits.into_iter()
+                .enumerate()
+                .skip(shift)
+                .map(|(i, bit)| (i - shift, bit)),
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::{Add, Mul, Neg, Sub};
@@ -330,10 +637,13 @@ mod tests {
     use sunscreen_runtime::{Runtime, ZkpProgramInput};
     use sunscreen_zkp_backend::{
         bulletproofs::{BulletproofsBackend, BulletproofsFieldSpec},
-        ZkpBackend, ZkpInto,
+        CompiledZkpProgram, ZkpBackend, ZkpInto,
     };
 
-    use crate::{types::zkp::ConstrainCmp, Compiler};
+    use crate::{
+        types::zkp::{ConstrainBool, ConstrainCmp, ConstrainEq, DivRemBounded},
+        Compiler,
+    };
 
     use super::*;
 
@@ -630,4 +940,528 @@ mod tests {
         test_case(-2, -1, false);
         test_case(5, 6, false);
     }
+
+    #[test]
+    fn can_divide_fields() {
+        #[zkp_program]
+        fn div<F: FieldSpec>(x: Field<F>, y: Field<F>, z: Field<F>) {
+            (x / y).constrain_eq(z);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(div)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(div).unwrap();
+
+        let test_case = |x: i64, y: i64, z: BigInt, expect_pass: bool| {
+            type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+            let result = runtime.prove(
+                program,
+                vec![BpField::from(x), BpField::from(y), BpField::from(z)],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let field_val = |x: i64| Field::<BulletproofsFieldSpec>::from(x).val;
+
+        // 7 / 2 is the field element that doubles to 7.
+        let seven_halves: BigInt = (Scalar::from(7u8) * Scalar::from(2u8).invert()).zkp_into();
+
+        test_case(12, 4, field_val(3), true);
+        test_case(12, -4, field_val(-3), true);
+        test_case(0, 5, field_val(0), true);
+        test_case(7, 2, seven_halves, true);
+        test_case(7, 2, field_val(3), false);
+        test_case(12, 4, field_val(4), false);
+        test_case(12, 0, field_val(0), false);
+        test_case(0, 0, field_val(0), false);
+    }
+
+    #[test]
+    fn can_div_rem_bounded() {
+        #[zkp_program]
+        fn div_rem<F: FieldSpec>(x: Field<F>, m: Field<F>, q: Field<F>, r: Field<F>) {
+            let (q_actual, r_actual) = x.div_rem_bounded(m, 16);
+
+            q_actual.constrain_eq(q);
+            r_actual.constrain_eq(r);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(div_rem)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(div_rem).unwrap();
+
+        let test_case = |x: i64, m: i64, q: i64, r: i64, expect_pass: bool| {
+            type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+            let result = runtime.prove(
+                program,
+                vec![
+                    BpField::from(x),
+                    BpField::from(m),
+                    BpField::from(q),
+                    BpField::from(r),
+                ],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(47, 22, 2, 3, true);
+        test_case(-47, 22, -3, 19, true);
+        test_case(44, 22, 2, 0, true);
+        test_case(-44, 22, -2, 0, true);
+        test_case(0, 5, 0, 0, true);
+        test_case(65535, 1, 65535, 0, true);
+        test_case(47, 22, 1, 25, false);
+        test_case(47, 22, 2, 4, false);
+        test_case(-47, 22, -2, -3, false);
+        test_case(47, 0, 0, 47, false);
+        test_case(47, -22, -3, -19, false);
+        test_case(1 << 20, 3, 349525, 1, false);
+
+        // Only the quotient is bounded, not the operands themselves.
+        test_case(1 << 20, 1 << 15, 32, 0, true);
+    }
+
+    /**
+     * How [`ForgedDivRem`] tampers with an honest quotient and remainder.
+     * Each forgery still satisfies `m * q + r == x` in the field.
+     */
+    #[derive(Clone, Copy)]
+    enum Forgery {
+        None,
+        ShiftRemainder,
+        WrapQuotient,
+    }
+
+    /**
+     * Wraps [`SignedModulus`] but returns a forged quotient and remainder,
+     * as a malicious prover would.
+     */
+    struct ForgedDivRem {
+        inner: SignedModulus,
+        field_modulus: BigInt,
+        forgery: Forgery,
+    }
+
+    impl Gadget for ForgedDivRem {
+        fn gen_circuit(
+            &self,
+            gadget_inputs: &[petgraph::stable_graph::NodeIndex],
+            hidden_inputs: &[petgraph::stable_graph::NodeIndex],
+        ) -> Vec<petgraph::stable_graph::NodeIndex> {
+            self.inner.gen_circuit(gadget_inputs, hidden_inputs)
+        }
+
+        fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> crate::ZkpResult<Vec<BigInt>> {
+            let p = &self.field_modulus;
+            let x = gadget_inputs[0];
+            let m = gadget_inputs[1];
+
+            let honest = self.inner.compute_hidden_inputs(gadget_inputs)?;
+            let (q, r) = (honest[0], honest[1]);
+
+            let (q, r) = match self.forgery {
+                Forgery::None => (q, r),
+                // m * (q - 1) + (r + m) == x
+                Forgery::ShiftRemainder => (
+                    BigInt::from(
+                        q.wrapping_add(p)
+                            .wrapping_sub(&BigInt::ONE)
+                            .reduce(p)
+                            .unwrap(),
+                    ),
+                    BigInt::from(r.wrapping_add(&m)),
+                ),
+                // Any remainder in [0, m) has a quotient (x - r) / m in the
+                // field; for r + 1 it lies far outside [-2^bits, 2^bits).
+                Forgery::WrapQuotient => {
+                    let r = BigInt::from(r.wrapping_add(&BigInt::ONE));
+                    let x_min_r = x.wrapping_add(p).wrapping_sub(&r).reduce(p).unwrap();
+                    let q = x_min_r.wrapping_mul(&m.inverse_fp(p)).reduce(p).unwrap();
+
+                    (BigInt::from(q), r)
+                }
+            };
+
+            Ok(vec![q, r])
+        }
+
+        fn gadget_input_count(&self) -> usize {
+            self.inner.gadget_input_count()
+        }
+
+        fn hidden_input_count(&self) -> usize {
+            self.inner.hidden_input_count()
+        }
+    }
+
+    fn forged_div_rem<F: FieldSpec>(
+        x: ProgramNode<Field<F>>,
+        m: ProgramNode<Field<F>>,
+        forgery: Forgery,
+    ) {
+        let gadget = ForgedDivRem {
+            inner: SignedModulus::new(F::FIELD_MODULUS, 16),
+            field_modulus: F::FIELD_MODULUS,
+            forgery,
+        };
+
+        Field::div_rem_with(gadget, x, m, 16);
+    }
+
+    #[test]
+    fn div_rem_bounded_rejects_forged_witnesses() {
+        #[zkp_program]
+        fn honest<F: FieldSpec>(x: Field<F>, m: Field<F>) {
+            forged_div_rem(x, m, Forgery::None);
+        }
+
+        #[zkp_program]
+        fn shifted_remainder<F: FieldSpec>(x: Field<F>, m: Field<F>) {
+            forged_div_rem(x, m, Forgery::ShiftRemainder);
+        }
+
+        #[zkp_program]
+        fn wrapped_quotient<F: FieldSpec>(x: Field<F>, m: Field<F>) {
+            forged_div_rem(x, m, Forgery::WrapQuotient);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(honest)
+            .zkp_program(shifted_remainder)
+            .zkp_program(wrapped_quotient)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+        let prove = |program: &CompiledZkpProgram| {
+            runtime.prove(
+                program,
+                vec![BpField::from(47), BpField::from(22)],
+                vec![],
+                vec![],
+            )
+        };
+
+        // The wrapper itself doesn't break honest proofs.
+        let honest_program = app.get_zkp_program(honest).unwrap();
+        let proof = prove(honest_program).unwrap();
+
+        runtime
+            .verify(
+                honest_program,
+                &proof,
+                vec![],
+                Vec::<ZkpProgramInput>::new(),
+            )
+            .unwrap();
+
+        // Caught by SignedModulus's r < m check.
+        assert!(prove(app.get_zkp_program(shifted_remainder).unwrap()).is_err());
+
+        // Only the quotient range check catches this one.
+        assert!(prove(app.get_zkp_program(wrapped_quotient).unwrap()).is_err());
+    }
+
+    #[test]
+    fn can_div_and_rem_bounded_separately() {
+        #[zkp_program]
+        fn div_and_rem<F: FieldSpec>(x: Field<F>, m: Field<F>, q: Field<F>, r: Field<F>) {
+            x.div_bounded(m, 8).constrain_eq(q);
+            x.rem_bounded(m, 8).constrain_eq(r);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(div_and_rem)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(div_and_rem).unwrap();
+
+        let test_case = |x: i64, m: i64, q: i64, r: i64, expect_pass: bool| {
+            type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+            let result = runtime.prove(
+                program,
+                vec![
+                    BpField::from(x),
+                    BpField::from(m),
+                    BpField::from(q),
+                    BpField::from(r),
+                ],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(100, 7, 14, 2, true);
+        test_case(-100, 7, -15, 5, true);
+        test_case(100, 7, 14, 3, false);
+        test_case(100, 7, 13, 2, false);
+    }
+
+    #[test]
+    fn can_compute_bitwise_unsigned() {
+        #[zkp_program]
+        fn bitwise<F: FieldSpec>(
+            x: Field<F>,
+            y: Field<F>,
+            and: Field<F>,
+            or: Field<F>,
+            xor: Field<F>,
+            shl: Field<F>,
+            shr: Field<F>,
+        ) {
+            x.and_unsigned::<8>(&y).constrain_eq(and);
+            x.or_unsigned::<8>(&y).constrain_eq(or);
+            x.xor_unsigned::<8>(&y).constrain_eq(xor);
+            x.shl_unsigned::<8>(3).constrain_eq(shl);
+            x.shr_unsigned::<8>(3).constrain_eq(shr);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(bitwise)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(bitwise).unwrap();
+
+        let test_case = |vals: [u32; 7], expect_pass: bool| {
+            type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+            let result = runtime.prove(
+                program,
+                vals.iter().map(|x| BpField::from(*x)).collect::<Vec<_>>(),
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let expected = |x: u32, y: u32| [x, y, x & y, x | y, x ^ y, (x << 3) & 0xFF, x >> 3];
+
+        test_case(expected(0b1011_0110, 0b0110_1101), true);
+        test_case(expected(0, 255), true);
+        test_case(expected(255, 255), true);
+
+        let mut wrong = expected(0b1011_0110, 0b0110_1101);
+        wrong[2] ^= 1;
+        test_case(wrong, false);
+
+        let mut wrong = expected(0b1011_0110, 0b0110_1101);
+        wrong[5] = 0b1011_0110 << 3;
+        test_case(wrong, false);
+
+        // 256 doesn't fit in 8 bits.
+        test_case(expected(256, 1), false);
+    }
+
+    #[test]
+    fn can_compare_to_bit() {
+        #[zkp_program]
+        fn cmp<F: FieldSpec>(
+            x: Field<F>,
+            y: Field<F>,
+            lt: ZkpBool<F>,
+            le: ZkpBool<F>,
+            gt: ZkpBool<F>,
+            ge: ZkpBool<F>,
+            eq: ZkpBool<F>,
+            min: Field<F>,
+            max: Field<F>,
+        ) {
+            x.lt_bounded(y, 16).constrain_eq(lt);
+            x.le_bounded(y, 16).constrain_eq(le);
+            x.gt_bounded(y, 16).constrain_eq(gt);
+            x.ge_bounded(y, 16).constrain_eq(ge);
+            x.eq(y).constrain_eq(eq);
+            x.min_bounded(y, 16).constrain_eq(min);
+            x.max_bounded(y, 16).constrain_eq(max);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(cmp)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(cmp).unwrap();
+
+        let test_case =
+            |x: i64, y: i64, flags: [bool; 5], min: i64, max: i64, expect_pass: bool| {
+                type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+                type BpBool = ZkpBool<<BulletproofsBackend as ZkpBackend>::Field>;
+
+                let mut inputs = vec![
+                    ZkpProgramInput::from(BpField::from(x)),
+                    BpField::from(y).into(),
+                ];
+                inputs.extend(
+                    flags
+                        .iter()
+                        .map(|f| ZkpProgramInput::from(BpBool::from(*f))),
+                );
+                inputs.push(BpField::from(min).into());
+                inputs.push(BpField::from(max).into());
+
+                let result = runtime.prove(program, inputs, vec![], vec![]);
+
+                let proof = if expect_pass {
+                    result.unwrap()
+                } else {
+                    assert!(result.is_err());
+                    return;
+                };
+
+                runtime
+                    .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                    .unwrap();
+            };
+
+        let expected = |x: i64, y: i64| [x < y, x <= y, x > y, x >= y, x == y];
+
+        for (x, y) in [
+            (5, 6),
+            (6, 5),
+            (5, 5),
+            (-3, 2),
+            (2, -3),
+            (-7, -7),
+            (0, 65535),
+        ] {
+            test_case(x, y, expected(x, y), x.min(y), x.max(y), true);
+            test_case(x, y, expected(x, y), x.max(y), x.min(y), x == y);
+
+            for i in 0..5 {
+                let mut flags = expected(x, y);
+                flags[i] = !flags[i];
+                test_case(x, y, flags, x.min(y), x.max(y), false);
+            }
+        }
+
+        // The operands differ by more than 16 bits.
+        test_case(0, 65536, expected(0, 65536), 0, 65536, false);
+    }
+
+    #[test]
+    fn can_count_values_over_threshold() {
+        #[zkp_program]
+        fn count_over<F: FieldSpec>(xs: [Field<F>; 4], count: Field<F>) {
+            let threshold = Field::from(10u8);
+            let mut total = Field::from(0u8).into_program_node();
+
+            for x in xs {
+                total = total + x.gt_bounded(threshold, 8).to_field();
+            }
+
+            total.constrain_eq(count);
+            xs[0].is_zero().constrain_false();
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(count_over)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(count_over).unwrap();
+
+        let test_case = |xs: [i64; 4], count: i64, expect_pass: bool| {
+            type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+            let mut inputs = xs.iter().map(|x| BpField::from(*x)).collect::<Vec<_>>();
+            inputs.push(BpField::from(count));
+
+            let result = runtime.prove(program, inputs, vec![], vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case([3, 11, 10, 200], 2, true);
+        test_case([12, 13, 14, 15], 4, true);
+        test_case([1, 2, 3, 4], 0, true);
+        test_case([3, 11, 10, 200], 3, false);
+        test_case([0, 11, 10, 200], 2, false);
+    }
 }
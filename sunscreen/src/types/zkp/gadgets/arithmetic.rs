@@ -180,6 +180,68 @@ impl Gadget for Inverse {
     }
 }
 
+/**
+ * For value x, output 1 if x == 0 and 0 otherwise. We do this by:
+ * * Compute x_inv = x^-1 if x != 0 and 0 otherwise.
+ * * Compute out = 1 - x * x_inv.
+ * * Constrain x * out == 0.
+ */
+pub struct IsZero {
+    field_modulus: BigInt,
+}
+
+impl IsZero {
+    pub fn new(field_modulus: BigInt) -> Self {
+        if field_modulus == BigInt::ZERO {
+            panic!("Field modulus cannot be zero.");
+        }
+
+        Self { field_modulus }
+    }
+}
+
+impl Gadget for IsZero {
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let x = gadget_inputs[0];
+
+        if x == BigInt::ZERO {
+            return Ok(vec![BigInt::ZERO]);
+        }
+
+        Ok(vec![x.inverse_fp(&self.field_modulus)])
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        1
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        1
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[petgraph::stable_graph::NodeIndex],
+        hidden_inputs: &[petgraph::stable_graph::NodeIndex],
+    ) -> Vec<petgraph::stable_graph::NodeIndex> {
+        let x = gadget_inputs[0];
+        let x_inv = hidden_inputs[0];
+
+        with_zkp_ctx(|ctx| {
+            let one = ctx.add_constant(&BigInt::ONE);
+            let prod = ctx.add_multiplication(x, x_inv);
+            let out = ctx.add_subtraction(one, prod);
+
+            // Assert x * out == 0. When x != 0, this forces out == 0.
+            // When x == 0, out == 1 regardless of x_inv.
+            let check = ctx.add_multiplication(x, out);
+            ctx.add_constraint(check, &BigInt::ZERO);
+
+            vec![out]
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sunscreen_compiler_macros::zkp_program;
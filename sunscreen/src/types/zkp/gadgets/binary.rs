@@ -151,6 +151,92 @@ impl Gadget for AssertBinary {
     }
 }
 
+/**
+ * Expands an index `i` into `n` bits where only bit `i` is set. We do
+ * this by:
+ * * Constraining each bit to be binary.
+ * * Constraining the bits to sum to 1.
+ * * Constraining the sum of `j * bit_j` to equal `i`.
+ *
+ * The proof fails if `i >= n`, so this also range checks `i`.
+ */
+pub struct OneHot {
+    n: usize,
+}
+
+impl OneHot {
+    /**
+     * Creates a new [`OneHot`] gadget.
+     *
+     * # Panics
+     * * If n == 0
+     */
+    pub fn new(n: usize) -> Self {
+        if n == 0 {
+            panic!("Cannot create a 0-bit one-hot vector.");
+        }
+
+        Self { n }
+    }
+}
+
+impl Gadget for OneHot {
+    fn compute_hidden_inputs(&self, gadget_inputs: &[BigInt]) -> ZkpResult<Vec<BigInt>> {
+        let val = gadget_inputs[0];
+
+        if val >= BigInt::from(self.n as u64) {
+            return Err(ZkpError::gadget_error(&format!(
+                "Index out of bounds for {} elements.",
+                self.n
+            )));
+        }
+
+        Ok((0..self.n)
+            .map(|i| BigInt::from((val == BigInt::from(i as u64)) as u64))
+            .collect())
+    }
+
+    fn gen_circuit(
+        &self,
+        gadget_inputs: &[petgraph::stable_graph::NodeIndex],
+        hidden_inputs: &[petgraph::stable_graph::NodeIndex],
+    ) -> Vec<petgraph::stable_graph::NodeIndex> {
+        let val = gadget_inputs[0];
+
+        for i in hidden_inputs {
+            invoke_gadget(AssertBinary, &[*i]);
+        }
+
+        with_zkp_ctx(|ctx| {
+            let mut sum = hidden_inputs[0];
+            let mut index = ctx.add_constant(&BigInt::ZERO);
+
+            for (i, bit) in hidden_inputs.iter().enumerate().skip(1) {
+                sum = ctx.add_addition(sum, *bit);
+
+                let i = ctx.add_constant(&BigInt::from(i as u64));
+                let weighted = ctx.add_multiplication(*bit, i);
+                index = ctx.add_addition(index, weighted);
+            }
+
+            ctx.add_constraint(sum, &BigInt::ONE);
+
+            let sub = ctx.add_subtraction(index, val);
+            ctx.add_constraint(sub, &BigInt::ZERO);
+        });
+
+        hidden_inputs.to_owned()
+    }
+
+    fn gadget_input_count(&self) -> usize {
+        1
+    }
+
+    fn hidden_input_count(&self) -> usize {
+        self.n
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use sunscreen_runtime::{Runtime, ZkpProgramInput};
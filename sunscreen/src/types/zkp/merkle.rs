@@ -0,0 +1,340 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::AssertBinary, poseidon::PoseidonNative, poseidon_hash, select, ConstrainEq, Field,
+        IntoProgramNode, NumFieldElements, ProgramNode, ToNativeFields, ZkpBool,
+    },
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use crate as sunscreen;
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * The authentication path from a leaf to the root of a binary Merkle tree
+ * with `DEPTH` levels, as produced by [`MerkleTree::path`].
+ *
+ * # Remarks
+ * Leaves are hashed as `poseidon_hash([leaf])` and interior nodes as
+ * `poseidon_hash([left, right])`. Since [`poseidon_hash`] separates inputs
+ * of different lengths, a leaf can't masquerade as an interior node.
+ *
+ * Program inputs of this type constrain `directions` to be binary.
+ */
+pub struct MerklePath<F: FieldSpec, const DEPTH: usize> {
+    /**
+     * The sibling of the node on the path at each level, starting with
+     * the leaf's sibling.
+     */
+    pub siblings: [Field<F>; DEPTH],
+
+    /**
+     * Whether the node on the path at each level is a right child,
+     * starting with the leaf. These are the bits of the leaf's index,
+     * least significant first.
+     */
+    pub directions: [bool; DEPTH],
+}
+
+// Can't #[derive()], as that would require F: Copy.
+impl<F: FieldSpec, const DEPTH: usize> Copy for MerklePath<F, DEPTH> {}
+
+impl<F: FieldSpec, const DEPTH: usize> NumFieldElements for MerklePath<F, DEPTH> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 2 * DEPTH;
+
+    fn constrain_input(ids: &[NodeIndex]) {
+        for id in &ids[DEPTH..] {
+            invoke_gadget(AssertBinary, &[*id]);
+        }
+    }
+}
+
+impl<F: FieldSpec, const DEPTH: usize> ToNativeFields for MerklePath<F, DEPTH> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        self.siblings
+            .iter()
+            .map(|x| x.val)
+            .chain(self.directions.iter().map(|x| BigInt::from(*x as u64)))
+            .collect()
+    }
+}
+
+impl<F: FieldSpec, const DEPTH: usize> IntoProgramNode for MerklePath<F, DEPTH> {
+    type Output = MerklePath<F, DEPTH>;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        let vals = self.to_native_fields();
+
+        with_zkp_ctx(|ctx| {
+            let ids = vals.iter().map(|x| ctx.add_constant(x)).collect::<Vec<_>>();
+
+            ProgramNode::new(&ids)
+        })
+    }
+}
+
+/**
+ * Methods for proving membership in a Merkle tree.
+ */
+pub trait MerkleMembership<F: FieldSpec> {
+    /**
+     * Computes the root of the tree containing `leaf` at this path.
+     */
+    fn root<L>(&self, leaf: L) -> ProgramNode<Field<F>>
+    where
+        L: IntoProgramNode<Output = Field<F>>;
+
+    /**
+     * Asserts `leaf` is in the tree with the given `root` at this path.
+     * The proof will fail to validate if it isn't.
+     */
+    fn constrain_membership<L, R>(&self, leaf: L, root: R)
+    where
+        L: IntoProgramNode<Output = Field<F>>,
+        R: IntoProgramNode<Output = Field<F>>;
+}
+
+impl<F: FieldSpec, const DEPTH: usize> MerkleMembership<F> for ProgramNode<MerklePath<F, DEPTH>> {
+    fn root<L>(&self, leaf: L) -> ProgramNode<Field<F>>
+    where
+        L: IntoProgramNode<Output = Field<F>>,
+    {
+        let mut cur = poseidon_hash(&[leaf.into_program_node()]);
+
+        for i in 0..DEPTH {
+            let sibling = ProgramNode::<Field<F>>::new(&[self.ids[i]]);
+            let is_right = ProgramNode::<ZkpBool<F>>::new(&[self.ids[DEPTH + i]]);
+
+            let left = select(is_right, sibling, cur);
+            let right = cur + sibling - left;
+
+            cur = poseidon_hash(&[left, right]);
+        }
+
+        cur
+    }
+
+    fn constrain_membership<L, R>(&self, leaf: L, root: R)
+    where
+        L: IntoProgramNode<Output = Field<F>>,
+        R: IntoProgramNode<Output = Field<F>>,
+    {
+        self.root(leaf).constrain_eq(root);
+    }
+}
+
+/**
+ * A binary Merkle tree with `DEPTH` levels over Poseidon, built outside a
+ * ZKP program. This produces the roots and [`MerklePath`]s
+ * [`MerkleMembership`] checks.
+ *
+ * # Remarks
+ * The tree holds up to `2^DEPTH` leaves. Missing leaves take the value
+ * 0 rather than the hash of a leaf, so they can't be proven members.
+ * Memory use is proportional to the number of leaves rather than
+ * `2^DEPTH`.
+ */
+pub struct MerkleTree<F: FieldSpec, const DEPTH: usize> {
+    /**
+     * The hashes at each level, starting with the leaves' hashes. Nodes
+     * past the end of a level are the roots of empty subtrees.
+     */
+    levels: Vec<Vec<Field<F>>>,
+
+    /**
+     * The root of an empty subtree of each height.
+     */
+    empty: Vec<Field<F>>,
+}
+
+impl<F: FieldSpec, const DEPTH: usize> MerkleTree<F, DEPTH> {
+    /**
+     * Builds the tree containing `leaves`, in order.
+     *
+     * # Panics
+     * If there are more than `2^DEPTH` leaves or `F` isn't the
+     * Bulletproofs scalar field.
+     */
+    pub fn new(leaves: &[Field<F>]) -> Self {
+        assert!(
+            DEPTH < usize::BITS as usize && leaves.len() <= 1 << DEPTH,
+            "A Merkle tree of depth {} can't hold {} leaves",
+            DEPTH,
+            leaves.len()
+        );
+
+        let poseidon = PoseidonNative::<F>::new();
+
+        let mut empty = vec![Field::from(BigInt::ZERO)];
+
+        for i in 0..DEPTH {
+            empty.push(poseidon.hash(&[empty[i], empty[i]]));
+        }
+
+        let mut levels = vec![leaves
+            .iter()
+            .map(|x| poseidon.hash(&[*x]))
+            .collect::<Vec<_>>()];
+
+        for i in 0..DEPTH {
+            let level = levels[i]
+                .chunks(2)
+                .map(|x| poseidon.hash(&[x[0], x.get(1).copied().unwrap_or(empty[i])]))
+                .collect();
+
+            levels.push(level);
+        }
+
+        Self { levels, empty }
+    }
+
+    /**
+     * The number of leaves in the tree.
+     */
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /**
+     * Whether the tree has no leaves.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /**
+     * The tree's root.
+     */
+    pub fn root(&self) -> Field<F> {
+        self.levels[DEPTH]
+            .first()
+            .copied()
+            .unwrap_or(self.empty[DEPTH])
+    }
+
+    /**
+     * The path from the leaf at `index` to the root.
+     *
+     * # Panics
+     * If `index` is out of bounds.
+     */
+    pub fn path(&self, index: usize) -> MerklePath<F, DEPTH> {
+        assert!(
+            index < self.len(),
+            "Leaf index {} out of bounds for a Merkle tree with {} leaves",
+            index,
+            self.len()
+        );
+
+        MerklePath {
+            siblings: std::array::from_fn(|i| {
+                let sibling = (index >> i) ^ 1;
+
+                self.levels[i]
+                    .get(sibling)
+                    .copied()
+                    .unwrap_or(self.empty[i])
+            }),
+            directions: std::array::from_fn(|i| (index >> i) & 0x1 == 1),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::{types::zkp::poseidon_hash_native, Compiler};
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn native_tree_matches_manual_hashes() {
+        let leaves = [3u8, 5, 7].map(BpField::from);
+        let tree = MerkleTree::<_, 2>::new(&leaves);
+
+        let h = |x: &[BpField]| poseidon_hash_native(x);
+        let l = leaves.map(|x| h(&[x]));
+
+        let expected = h(&[h(&[l[0], l[1]]), h(&[l[2], BpField::from(0u8)])]);
+
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.root().val, expected.val);
+
+        let path = tree.path(2);
+
+        assert_eq!(path.siblings[0].val, BigInt::ZERO);
+        assert_eq!(path.siblings[1].val, h(&[l[0], l[1]]).val);
+        assert_eq!(path.directions, [false, true]);
+    }
+
+    #[test]
+    fn can_prove_membership() {
+        #[zkp_program]
+        fn member<F: FieldSpec>(leaf: Field<F>, path: MerklePath<F, 4>, #[public] root: Field<F>) {
+            path.constrain_membership(leaf, root);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(member)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(member).unwrap();
+
+        let leaves = (0..11u64)
+            .map(|x| BpField::from(x * x + 1))
+            .collect::<Vec<_>>();
+        let tree = MerkleTree::<_, 4>::new(&leaves);
+
+        let test_case = |leaf: BpField, path: MerklePath<_, 4>, root: BpField, expect_pass| {
+            let result = runtime.prove(
+                program,
+                vec![ZkpProgramInput::from(leaf), path.into()],
+                vec![root.into()],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![root], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            test_case(*leaf, tree.path(i), tree.root(), true);
+        }
+
+        // Wrong leaf, path or root.
+        test_case(BpField::from(3u8), tree.path(1), tree.root(), false);
+        test_case(leaves[1], tree.path(2), tree.root(), false);
+        test_case(leaves[1], tree.path(1), leaves[1], false);
+
+        // Padding leaves aren't members.
+        let mut path = tree.path(10);
+        path.directions[0] = true;
+        path.siblings[0] = poseidon_hash_native(&[leaves[10]]);
+
+        test_case(BpField::from(0u8), path, tree.root(), false);
+    }
+}
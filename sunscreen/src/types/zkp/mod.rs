@@ -1,17 +1,33 @@
+mod array;
+mod closure_gadget;
 mod field;
 mod gadgets;
+mod merkle;
+mod poseidon;
 mod program_node;
 mod rns_polynomial;
+mod zkp_bool;
+mod zkp_fixed;
+mod zkp_int;
+mod zkp_struct;
 
+pub use array::*;
+pub use closure_gadget::*;
 pub use field::*;
+pub use merkle::*;
 // N.B. `NodeIndex` is actually common to both FHE and ZKP, but it's really only leaked as an
 // implementation detail on the ZKP side (via gadgets). So I think it makes sense to export under
 // sunscreen::types::zkp.
 pub use petgraph::stable_graph::NodeIndex;
+pub use poseidon::*;
 pub use program_node::*;
 pub use rns_polynomial::*;
 use sunscreen_compiler_common::TypeName;
 pub use sunscreen_zkp_backend::{BigInt, Gadget};
+pub use zkp_bool::*;
+pub use zkp_fixed::*;
+pub use zkp_int::*;
+pub use zkp_struct::*;
 
 pub use sunscreen_runtime::{ToNativeFields, ZkpProgramInputTrait};
 
@@ -67,6 +83,38 @@ where
     fn rem(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self>;
 }
 
+/**
+ * A trait for integer division of 2 zkp values.
+ */
+pub trait DivRemVarVar
+where
+    Self: Sized + ZkpType,
+{
+    /**
+     * Computes the quotient and remainder of lhs / rhs, interpreting
+     * lhs as a signed integer. The quotient rounds towards negative
+     * infinity, so the remainder lies in `[0, rhs)`.
+     *
+     * # Remarks
+     * `bits` should be the maximum number of bits required to represent
+     * `abs(lhs)` and `rhs` as unsigned values. `2 * bits + 2` must be
+     * less than the number of bits needed to represent the field
+     * modulus.
+     *
+     * The operands themselves aren't range checked. The proof fails
+     * unless `rhs` lies in `[1, 2^(bits + 1))`, the remainder in
+     * `[0, min(rhs, 2^bits))` and the quotient in `[-2^bits, 2^bits)`.
+     * As a result, some `lhs` exceeding `bits` (e.g. `2^20 / 2^15` with
+     * `bits = 16`) still produce valid proofs. Constrain the operands
+     * separately if you rely on their bounds.
+     */
+    fn div_rem_bounded(
+        lhs: ProgramNode<Self>,
+        rhs: ProgramNode<Self>,
+        bits: usize,
+    ) -> (ProgramNode<Self>, ProgramNode<Self>);
+}
+
 /**
  * A trait for subtracting 2 zkp values.
  */
@@ -93,6 +141,58 @@ where
     fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self>;
 }
 
+/**
+ * A trait for computing the logical and of 2 zkp values.
+ */
+pub trait AndVar
+where
+    Self: Sized + ZkpType,
+{
+    /**
+     * Compute lhs & rhs.
+     */
+    fn and(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self>;
+}
+
+/**
+ * A trait for computing the logical or of 2 zkp values.
+ */
+pub trait OrVar
+where
+    Self: Sized + ZkpType,
+{
+    /**
+     * Compute lhs | rhs.
+     */
+    fn or(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self>;
+}
+
+/**
+ * A trait for computing the logical exclusive or of 2 zkp values.
+ */
+pub trait XorVar
+where
+    Self: Sized + ZkpType,
+{
+    /**
+     * Compute lhs ^ rhs.
+     */
+    fn xor(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self>;
+}
+
+/**
+ * A trait for computing the logical negation of a zkp value.
+ */
+pub trait NotVar
+where
+    Self: Sized + ZkpType,
+{
+    /**
+     * Compute !lhs.
+     */
+    fn not(lhs: ProgramNode<Self>) -> ProgramNode<Self>;
+}
+
 /**
  * A trait for adding an equality constraint to a type.
  */
@@ -188,6 +288,16 @@ pub trait NumFieldElements {
      * The number of native field elements needed to represent this type.
      */
     const NUM_NATIVE_FIELD_ELEMENTS: usize;
+
+    /**
+     * Adds constraints proving the native field elements `ids` of a
+     * program input hold a valid value of this type (e.g. that a
+     * boolean is 0 or 1). By default, any field elements are valid.
+     *
+     * # Remarks
+     * Called when creating each program input of this type.
+     */
+    fn constrain_input(_ids: &[NodeIndex]) {}
 }
 
 /**
@@ -218,6 +328,16 @@ where
     T: ZkpType,
 {
     const NUM_NATIVE_FIELD_ELEMENTS: usize = T::NUM_NATIVE_FIELD_ELEMENTS * N;
+
+    fn constrain_input(ids: &[NodeIndex]) {
+        if T::NUM_NATIVE_FIELD_ELEMENTS == 0 {
+            return;
+        }
+
+        for x in ids.chunks(T::NUM_NATIVE_FIELD_ELEMENTS) {
+            T::constrain_input(x);
+        }
+    }
 }
 
 impl<T> Coerce for T
@@ -0,0 +1,241 @@
+// @generated by sunscreen/utils/poseidon_params.py. Do not edit.
+
+/**
+ * The Ristretto scalar field modulus these parameters were generated for.
+ */
+#[rustfmt::skip]
+pub const MODULUS: [u64; 4] = [0x5812631a5cf5d3ed, 0x14def9dea2f79cd6, 0x0000000000000000, 0x1000000000000000];
+
+/**
+ * The number of full rounds.
+ */
+pub const FULL_ROUNDS: usize = 8;
+
+/**
+ * The number of partial rounds.
+ */
+pub const PARTIAL_ROUNDS: usize = 57;
+
+/**
+ * The round constants, 3 per round, as little-endian limbs.
+ */
+#[rustfmt::skip]
+pub const ROUND_CONSTANTS: [[u64; 4]; 195] = [
+    [0x64be4803d0f23e31, 0xdf311947b1ae6ff8, 0x16ea08e4a9b159c6, 0x02d3c9c8d37dfdbf],
+    [0x84be345e03d11cf5, 0x206fccd407f20d61, 0xba6dcc3179f1f9ce, 0x0152cc27c1941aa2],
+    [0xf9b5b2e371aabc9d, 0xe61fc8362eb0118e, 0x493a2023d7f980c4, 0x0fcd0b14cc65c19f],
+    [0x8df408b84b7185e5, 0x46550c4e6b5d09b4, 0x733603f5e1f8d509, 0x03346c604118c7bc],
+    [0x3b14be239913bcec, 0x7319b22dc026ced3, 0x07e3f0278af21223, 0x02765b3452ae6a9d],
+    [0xe5d1cff207728f3f, 0xc1c8b7d88eeb460f, 0x0c933c02eb4a417f, 0x0a1b59ca8620568f],
+    [0xae773f22bdd4acca, 0x7def37048c6b23fe, 0xcd013169c49d7e1e, 0x00f6d68caa668802],
+    [0x1619f79d2a8a1bc0, 0xadfbe6b68bd994f4, 0x83230a770a77e2b3, 0x0c17cd9da933cdd1],
+    [0xc34705e196b13dc8, 0x8d36faa6a38d9922, 0x3009fcddb4ca133d, 0x08d3932a70e4163d],
+    [0xb9b29e5d482a5ca1, 0xbb52adfb98986b29, 0x831ceec310383793, 0x057aa8ba7ec5c291],
+    [0x529c4b3d94001edd, 0xfa4aa3ec0955891a, 0xcb1d1d908353c4d0, 0x0b338abce4cb872f],
+    [0x554ac51cf131381f, 0x97082b65ae87854a, 0x108a1bf9b8cea49f, 0x0ce534f3dda0711e],
+    [0xa7a0f0c37e158db5, 0x51ed4061d6deb93e, 0xace943a10f4abd99, 0x047f64aa3df1552c],
+    [0x41703930036cc31e, 0xf1fe5a06f1898760, 0xf37dc62b68f7dbba, 0x0baf6d8f9dee7ef6],
+    [0xb4cc2c79adc27dc5, 0xacc7ee62b584e423, 0x2a63a59aaf331f5c, 0x0b5e2200d20dc3e0],
+    [0x348c121c6eeb1c89, 0xe03a4d141cd315bb, 0x15e0f60656648a5c, 0x00a1426c7a5964ca],
+    [0x9c6aa0f645edb71b, 0x912edb62ec280625, 0x3e74ece438bd55a9, 0x05de22a5c6d86cc6],
+    [0xbc43193123c66400, 0x5cd53cfea1b4a2b5, 0xaf93c5104461195a, 0x00286bf8a4822739],
+    [0xbcb184636a5ef111, 0xcce4bb7940318952, 0x47a5732ee65cd61a, 0x04088c26347a46dc],
+    [0x631f021696e98067, 0xddf8416aec471a18, 0x0ab72ea896390dd9, 0x0d49975f62645490],
+    [0x314a6e22a546bdef, 0x2e81dfabc9c3d972, 0x5c0dfed6b6906014, 0x0b4d154336843f9f],
+    [0xb9dc5ff7ecce19fa, 0xc6bfdd0073a91a7d, 0x06d800e987870e59, 0x05831c8f80a39390],
+    [0x8791201476d367af, 0x341edf28e9c3b001, 0xa3321b3a52fc8d46, 0x0051d1146a453781],
+    [0xa94462f10e58f4fa, 0xae1c02665ee221cd, 0x8fb8cabbdb069b46, 0x0e5613b193887248],
+    [0x322179613aab9ba6, 0xca123e763e3b68b5, 0xee91e629551da622, 0x07b1cc64e0a98193],
+    [0x26e3ae5894f27ee5, 0x2a0257a20bb22271, 0x876a4c1a0b7a10af, 0x0cf7444f8ac5c756],
+    [0x324d0618371304c5, 0x9d3b5027281466bb, 0x383593d3d2c352d0, 0x0fd646caebe3b287],
+    [0xf5a01fd53e24d493, 0x953ced742dea39f4, 0x5d936be083722567, 0x07fcf71131c1c2e3],
+    [0xb49ea409426c064b, 0xcc7044e88d6696bb, 0xbd7a76545490b25b, 0x05ba8687d9066dcf],
+    [0xda69347f61166037, 0x78a39c700318cdfc, 0xc921525a1807fe11, 0x0af0a53479e3f12d],
+    [0xc2655620171a81ad, 0x8aec8463bd6adede, 0xeb8c86ea1db897a9, 0x00829f85f2e2ad13],
+    [0x024d6e33c2a64ae9, 0xd34845e7d726972e, 0xa2291b92d7890993, 0x08e2728bc7a65e93],
+    [0x24b9bae82973395c, 0x72e23d06c76fe900, 0x87f15d3ae2057054, 0x061cf96ed33fbc15],
+    [0xeb27f1e90b202e4a, 0x131e3808841850de, 0xa8152128063c929f, 0x0342017328315cb0],
+    [0xf981a24b29894f58, 0x3874b7f0bd4ea04b, 0xdb81b1a525355272, 0x07f8609469e9cc1d],
+    [0xb4551ffa43de81ab, 0xacafd5a687809c55, 0xdea60c4ff8f54307, 0x0aa2f7fa2b4db440],
+    [0x1f7cfd05ddb86771, 0xa287c67a7f8ceb8f, 0xd8462e93a520eb68, 0x0123a9ba8db4362b],
+    [0x52823fc627e774d2, 0x36e988f5264cec3d, 0x10312313314dc27f, 0x028ac154de6721e7],
+    [0x850f10308377a15a, 0x4c4b790aa55ef0d3, 0xc0d3060e9ade11f1, 0x0d2f0ca186c1024c],
+    [0x89d8b015eb7cafce, 0x4bfcc11cf1da7ec1, 0xbf808f9a9e20e703, 0x0789f727bb227f3e],
+    [0xbc050fd0520b2b3c, 0x09f295b1efa11b39, 0x369d37fcc7ae4fa6, 0x06f0fff88cf3a444],
+    [0x0dfee8b9cf031127, 0x5a5cab53723b726b, 0x015eadf9e49a7422, 0x0c3ea3de0b9e2c3c],
+    [0x9ec080e4bd888e51, 0xdbe8879e26ec68cc, 0x6009c753dba927b9, 0x006b4837a7902c5d],
+    [0xd80da9b50a99db2e, 0xc7e34c44c9586d9e, 0xb87e584c2e07cec7, 0x035116403482a9bd],
+    [0xbf9905e32ee624e5, 0x3f798bc65c8f9346, 0xbe0b96bca01c3b83, 0x0dfc0209ba3b632d],
+    [0x6933fc1a346863aa, 0x4861bd17c6431254, 0x4839e33e60b3dd24, 0x03bde4fafd9e242c],
+    [0xa471c5f4dee304ba, 0xf1d0509dd3f522ec, 0x17ceee654e3e218b, 0x0640a94e9cbba35a],
+    [0x8d139b0c854eccb8, 0x7a20ca29d9c19282, 0x0a99e674e395c77b, 0x0b99039546fe4d00],
+    [0x36ed5b73c8cae623, 0x611313ad989c21f4, 0x172d0ac89991d5af, 0x0dfe540962facbc4],
+    [0xda79ff9141a85587, 0x7d096c864158c0b6, 0x3f1ffd1ae5532ab3, 0x06ff170c4baadb14],
+    [0x7d99bda3e23f322d, 0x4dc057b17e8a2802, 0xaf5c32af4f869d36, 0x01cad94341dfba69],
+    [0x6551a5150defe6d3, 0x5cee9590b83b668d, 0x3b7af3410de8790a, 0x080fff49040db0a4],
+    [0xdcf49085f0760c1b, 0x2c4f14eaa65be45a, 0x8d609137f6b3d64b, 0x0bbbdb6655e9a4d6],
+    [0x097fa30ded4e61a3, 0x2ce839acb340dc26, 0x589e522a49ec9b8b, 0x0dfaed4d4ba5de3f],
+    [0x5bf090e8858c691a, 0x4302169e02bdbe60, 0x3fd6e0f58f9de8ad, 0x0fec4a8b7641355d],
+    [0x3fbab5be93a5f1a1, 0x22ea3b2b82d8d4c5, 0xb886364dc7590ec6, 0x04a87af56c6fc688],
+    [0x90499770020b2c51, 0xc650db891e0d32d9, 0xc9cfd11f11e95898, 0x016dab43a65c496c],
+    [0x3733873518bee636, 0xb9a01a9924194522, 0xd8cc11e75b1e407b, 0x06935e4d1e44d042],
+    [0xd201ab9c2e4bd961, 0xfda0b4aa79db1350, 0x64b3011dffdd8042, 0x099014a8d90cf517],
+    [0x43abf1157adf70af, 0x536f20c975bdd893, 0x9eda6ac949064a1c, 0x0313c78ccd2c52d8],
+    [0x5b19f8699fb38e18, 0x62243362cb9fe3c6, 0x7206498ec6c5b46e, 0x019261d4151f80ba],
+    [0xb855cb69daa39fa1, 0x3335b6dfe93427b2, 0xfe2d39f450b59524, 0x02b47c377df1e0ff],
+    [0xa030a706f7406417, 0x1963b746c258489b, 0xb88c87e38c01f412, 0x0e4f3d756e034e13],
+    [0xea9f9d9c7f34b825, 0x9eb6d8b12a8f3c2e, 0xd9eb070bba618d79, 0x0c72badd3cf0245e],
+    [0x018f05080d7eb364, 0x2d4380b1ef4527f4, 0x6342a3d3fcb8ff30, 0x0cd194a624be85aa],
+    [0xbd6c35c213895ff1, 0xd681787f5f9c2b7e, 0xd8e9ebe480f8b614, 0x0f48ebe2a8d0f571],
+    [0x0a9dea68da21f17c, 0xc4b8cd2cc40ae2c1, 0x54b08cf704744dcc, 0x0ddafdc0c3a800c9],
+    [0xcd1d4c46466a0374, 0x06c31bf136896ccf, 0xb49aa42cf2f314e9, 0x0fc39dd5843fd92b],
+    [0x8648a68adc1fb147, 0x149faefa711685e9, 0x3a6cbb3fe15ea137, 0x0c74f8bc4ab9c9de],
+    [0xda6c3ede71608338, 0x4777950ed1238a4d, 0xf772d8d0ef4b311c, 0x06152b6761e9ac53],
+    [0xb8155c87b080bf9c, 0x6d0c98c0d8f58b96, 0xa60c40336ab1c71d, 0x0c2938c60d7ee3f0],
+    [0x6a6538670b25cb57, 0xa3a2d3e0f10cbeb1, 0x9e0081f47ba8829d, 0x042fe1ef955b0fe7],
+    [0x3514a8fbaa7217b6, 0x4440794b437a9dce, 0x00d3962ebd9ca714, 0x07da33dbcaf662fc],
+    [0x6046ae8322462287, 0x34a6bb370be6f360, 0xca5434f78690bf60, 0x002f7e4ab11a9583],
+    [0x606fa70712a319a7, 0x803fadc3006bd6c7, 0x34b6a487a6f22247, 0x076943ad20515484],
+    [0x347f3bdacdf113a0, 0x837f58a7a4315fa3, 0x0d2fb480ab5d5be6, 0x082c7f73d07db5ba],
+    [0x75892e44f208c5e1, 0x7c7cc331f7bf3ba8, 0xfafee5292eae574e, 0x0f18bec6657d3e8d],
+    [0x2610ac1e3c035c75, 0x3b86ea45e9c75e11, 0xfd1aabd946e42340, 0x07815d9c99a69ad2],
+    [0xaaaf708fd7925efb, 0xaf2bdf316d502a90, 0x6c19629b9dce6912, 0x0ec115c9e7211d23],
+    [0x5e8e405030b93d87, 0x9a46d1e45f9aba37, 0x898f0caf35d0d37b, 0x0fb164cb96e72870],
+    [0x1cb29d983ea0234e, 0x44be424e5e20f68a, 0x5a109cd3c277ee6e, 0x08ebf1624427ad5b],
+    [0x7a6b4a1e011bbe74, 0x583e89b48b3efa91, 0x6205fda22938b72b, 0x0c74e0d8e8e4c3ac],
+    [0x0e8fa1356bd5109a, 0xd0cb52c6feb89bd8, 0x4125d35418fdef9c, 0x0208e0e39e6d37cf],
+    [0x13eba55c6f03d5b2, 0xeecc25cb8e128409, 0x03fb575f42185cba, 0x038136af31323484],
+    [0xeae435e8af1517ef, 0xe753a961db3dba96, 0x596d5b8e14444732, 0x0f7955236ced401b],
+    [0x55872bb0f471b1ec, 0x9703956209a94074, 0x1bfe0f6d7bffd361, 0x018442e461f849a0],
+    [0xd7bb1f0c5be64db1, 0x93f0cbc31b52e11c, 0x0e20aca6b2596fea, 0x0f972b5d0b4dea78],
+    [0x3374fcf2b4f134c0, 0xad41895ae7692947, 0xe937ce3de9e5d280, 0x00a53bff641b1b50],
+    [0x24ad5c57307cc29a, 0x57d1264ca1e6398e, 0x65dd0381387f8ed8, 0x00178f1b227dc35f],
+    [0x13a7840ed10ce35b, 0x7dc8b12c1417d0e8, 0xac16e414f4d70202, 0x0ff839d71e1706e8],
+    [0x0eeb41bfb9cc5a9d, 0x751b6f6f55a0135c, 0x701e8283dba0b14e, 0x0f73f4e232699259],
+    [0x1a9c76817e4a2bfd, 0xa0a08cfe2a9d8284, 0x3d47d2c4e6e28c8d, 0x0ec567146956dd87],
+    [0x5ab12f15f82aedc9, 0xf40a02097b1f41ae, 0x056e899e261d0f0b, 0x0dcb3cca8a4bd66c],
+    [0xdb437a478e5263c9, 0xda27f1e373908992, 0x168225e6be946bf1, 0x080f050248bfdba4],
+    [0x2a6dc2a6d2dfc5b8, 0xa29ace04900fb9e6, 0xed4926be32270c0d, 0x0c0a2a6cb945e218],
+    [0x14c5947c7cfc804c, 0xbd3b0241e0df953d, 0x1ca96e93ae485f04, 0x038484aac6ec7b83],
+    [0x165cd8c11f341a26, 0x210df67b276877c2, 0xfd793faacd4b6869, 0x09c4f650aff4c9c6],
+    [0x48b17b9db7491b41, 0x05ad1a9db317980c, 0xa848f956b90a1c92, 0x0cc70937ac7189cb],
+    [0xc678044b0c918798, 0xabdaa3267ef4a0b0, 0x7ee63d535bf8c282, 0x037e89f5585457a8],
+    [0x13bee376dbb79f39, 0x07c9b9f6ff98d9a9, 0x0d29a6a170e55cc5, 0x06d7188008afbdbb],
+    [0xb33fb2d7a144d3d6, 0x3ed1b8f8e93bfdd2, 0x8ea1aaea439af01b, 0x0216ed4adc0570f1],
+    [0x9257ebbdaefebf3c, 0xedf8079fe48ab5c8, 0xd74a50ef108a7ef7, 0x095ed0b283fdf360],
+    [0x5020f614dd9ccc3b, 0x99b2c94b99189eac, 0x9ba96d3fb98d906f, 0x01522ee44e25ad10],
+    [0x484a191722d14cb2, 0x472303889cb557b8, 0xae7eea9c24f6ca7d, 0x0e76834d42fc6afc],
+    [0x330422bc2c7c4203, 0x4e8bafed351fa480, 0xc1e65770acb16619, 0x04842d33fd89694f],
+    [0xc751a80e1144eefb, 0x67adba6ef6011343, 0x15dc2842330e04e6, 0x0b1fa79c9385cf38],
+    [0x446a57f70d468a2f, 0xbfa87bb532f66ccd, 0x4b73a929722a5ca4, 0x084c1a2cac17ebb9],
+    [0xc2384cbb527fdecb, 0xd5f9fd4e78ddbed2, 0xff0e9bc828a15a42, 0x054b479a9cab2aa5],
+    [0xcf1916962829fbb9, 0xeaae276a83e283b1, 0x3092e0fef87cca33, 0x0d116d0cf7137188],
+    [0xcd0edb9ed0de6045, 0x801d0d0dda5d5aae, 0x35363944fc5da0db, 0x0617314ba78b51c4],
+    [0x99aa459c3bad4e12, 0x238e95f232157e56, 0x858ae7ca5a3385cc, 0x055d6d13046afb0f],
+    [0xf105c8145f57cc5b, 0x4852622f9a0a88c1, 0xf3e957fb76dd5a7a, 0x054ac0aad2675131],
+    [0xff1f5285248b68e3, 0x9d5bd9b84d0c9505, 0x6b22fa2985536488, 0x01de53b4bf5d4957],
+    [0x937ff73728639546, 0xfd281a2356df411c, 0xb3962526ce5adcf2, 0x0026bbb845f50fdf],
+    [0xffd1fcfa60e53d00, 0xaadd4358f923cb0a, 0x4d1ae9d2ba6e641b, 0x06aed7db8d0ec1a6],
+    [0x830d022c9cb98823, 0xbd20de51fb0a6166, 0x030fb00571cb1f6d, 0x0568c06f2b7d6b39],
+    [0xab308c8b12935b88, 0xa4fb4dd307d53bd8, 0xa64e1de4c4b10b4c, 0x05fdc7303943c85a],
+    [0xd74fc5524499433b, 0x81369fc353e1aa2b, 0xaa3dd605077ab336, 0x05d89655be5605f0],
+    [0x925e63c62d04b07a, 0xd0a206e2070e118f, 0xdabdd1a8ced0a16a, 0x01847efa889ebc92],
+    [0xcf6f7767c0a10388, 0x5dfa2cd29ad0962a, 0x7f000a54f8fe4a20, 0x05a7f516357d3535],
+    [0x898050877f948fc0, 0xbfa479a18f084047, 0xd73ddf9300d45f80, 0x065f07f90c55f5bb],
+    [0x95e8b1fc2b98b91a, 0xf3d25e74490fd065, 0x7bcbb9c140660308, 0x0a84bcfc684dcc6b],
+    [0xd626d97d761649ea, 0xbda12c641f3e80b1, 0x067f0cd6e4e58929, 0x06913d90fda31679],
+    [0xcdaf88b6f380c5b8, 0x46baf56471e1aa70, 0xdf49be9a90fbee5a, 0x0e97916a09188bb8],
+    [0x903faa3c30d64da2, 0x5fe068ddf622e691, 0x1961b5c80e66b918, 0x052668bb7684610e],
+    [0xcfab3045d9cb8777, 0xe0b35ba52263dca1, 0x50fb36734e7d117e, 0x00d9be680bfe3b7e],
+    [0x89718d9e12ba2f4e, 0xa7d1cb0b321c1748, 0xd870ccc4e8f35d76, 0x06c36ac7586a309d],
+    [0x791a61c0910af551, 0x1a8b3e5e41b23bdd, 0x8270f4d14ec09df9, 0x0c2a36f6d5be54bb],
+    [0xfe29a40c06fc1a05, 0x31b866bb4595daf7, 0x6f219b02ddb06363, 0x0571e6a43c6db37b],
+    [0xe07305431385fe17, 0x464f3c47aa643089, 0xaab0282ed721c031, 0x03e470578a447fd9],
+    [0xb314cf49dc57f242, 0xeb412d5a5d07ba0e, 0x5092c9162bb06855, 0x0e66001fa62b7248],
+    [0x095d90b9ca0c0387, 0x06d7a99a949689be, 0x9418261a49e94fdb, 0x08c3711aec7e0146],
+    [0xebfeea1ab723bd33, 0x7f90a9229f2465e8, 0x26ba0cfec3503919, 0x0e8d221c4572f2f0],
+    [0x3639a55da3a4aa9c, 0x17cd4d30bd19e365, 0xff46c2654dee7401, 0x05bb8c0025d5385f],
+    [0x28719628a482794a, 0xa7130a764c94fbc1, 0xe3500667007972f6, 0x0482ea4277bc339f],
+    [0x1f62912788cb2c06, 0x86b4df2fd2cfb3a5, 0xad3300cc5c6d8089, 0x0a57111b2cf107f5],
+    [0x2e6d7127e06dc8bb, 0x2439b5a3b72db4fa, 0x215e3f7a32e24bb6, 0x057f83b32daba114],
+    [0x75440ef87dfe9a97, 0x2ecb03cea22c6651, 0x3054ad13985df34f, 0x03ea183b5049e34a],
+    [0xee59de645531ab94, 0x2cbbad68d950ffda, 0xd5d085acde9fe662, 0x0a02a977c6a1ae4b],
+    [0x492fe6cde14536fe, 0xb1ec219e1d65865c, 0xaafc8e0780408f2b, 0x09d0ee5819a3b3cf],
+    [0xdcf183297b58ab01, 0xb3ed1180936ad707, 0x6aa2ae4d44b116cf, 0x01da1408bba215aa],
+    [0x2b62dfb3b0c3ea86, 0xf0880a21133bdef9, 0x844b5218636d35b8, 0x0c72bb255d75bf5d],
+    [0x89865db13be364b7, 0xffe2d5eb666c0a2f, 0xf0d0fc3f0b8544de, 0x057ad6a206132300],
+    [0x66b4bb094dac810c, 0xe7cb625165e8047d, 0xb0a56e7fa79f0742, 0x0a02426dcd9b3c26],
+    [0xba3e0c8d2cda839b, 0xae4f80b014431e94, 0x9e2ed6cc4c075665, 0x03c40ba4184ea2b1],
+    [0x2cb961bb3043fad9, 0x807e59d3cad23a84, 0x19e0531d907efab4, 0x0c30e1430754d02f],
+    [0x755c4776c8fa3e8b, 0x169cb69c9371a17d, 0xbff7f1520dfec122, 0x0c689f70423af462],
+    [0xfc6541354aca2917, 0x08958e0e63e195d5, 0x48bcd49cb5c4d5cc, 0x02dcd57f3c73bd22],
+    [0x0d1744833950e9b6, 0x41e5f6137dbda965, 0xe54e67a1341fb86d, 0x07dda0098ac5f3d4],
+    [0x609e45766642a6a1, 0xb45111763bd972ec, 0xf0fcb05db6ce27a4, 0x02856f5151967283],
+    [0x044ab1b922bc7bca, 0xac32a454a416f697, 0x1bfca0087c2294ce, 0x02148724ef1f0e29],
+    [0x2cc513bbe47bd548, 0x8b0b9c9cd6a72996, 0x462476daabc340b9, 0x0d9e81a2645ae168],
+    [0xccd3732b5e24e076, 0x1a8bb0c07b5ff8d5, 0x3e4c46e6aadffe69, 0x0ed559899790a570],
+    [0x17681f866a7482fa, 0x3972fb6ef051cd3b, 0x10046006527cfb27, 0x0829ef7483febe30],
+    [0x03914b40bb2cdc48, 0x8599e01715bf8781, 0x53c605f9dd5a77e8, 0x0e2d1e624f530c22],
+    [0xf7a2b0c21e91ffc8, 0x8ab5367816822147, 0x8b1184bdba89e0ee, 0x09af7e7936144103],
+    [0xd71950160908f16d, 0x5991ed98ff50aba9, 0xf6d542840a275ab2, 0x06534a7c7102b247],
+    [0xab83dc01aca8ba3c, 0xf86ac3790f028352, 0x4b8c93c0514cc9f6, 0x0e35da8e426ad85e],
+    [0x45a99a64d35a64b5, 0xc966b6f7927356f0, 0xf700b9222062eb13, 0x08f26c6b76ed9f3e],
+    [0x611d3b0d2fe43008, 0xa09d93b8524b12e0, 0xba48bf5f7109d99a, 0x08057f36daf740bf],
+    [0xfefb0238e7458ad1, 0x8a7b0a22215fa90c, 0x9b0c732a4e817650, 0x02fca0e81c54830b],
+    [0x69a8d37c5826bde5, 0xe2614972aa94ca6b, 0x29bd2b3de8574c86, 0x0f61cd8b97b27b6f],
+    [0x88359ce97b5e34f0, 0x3684080509ceb4b8, 0x312d6bd310d22459, 0x0528d23efa445630],
+    [0x3360cc2e346adc9a, 0xf11a4908796f98e6, 0xd146bf7d62fd5931, 0x02ad25c5cea5957b],
+    [0x61193680222eb050, 0x4ca1d0a081b743bf, 0x6052f42aa1784c60, 0x0e85828165d54180],
+    [0x4b275442dcf9df27, 0x295eb5fcde38466e, 0x61a5df832a4f2e4b, 0x004b71016df33b4c],
+    [0xb3389e38802e6881, 0x9d5612a86df7b5f6, 0xe551a738bbb12aec, 0x09a9c6685177a6c2],
+    [0x96f736e9b76fc14f, 0x25fe367c455c5799, 0xf731a4db5362fbf0, 0x0171b7ef02eb5324],
+    [0xe3b86e998d04b2e8, 0xf3a4304605cfeddd, 0xd7e5b111bed4fa0c, 0x01054b3c3d745a14],
+    [0xd0b78d37ef6fed6a, 0x23486b32225c37b7, 0x06791e104b195a48, 0x0429610124d45446],
+    [0xaea411b3ad4570fe, 0x2c9ef03988132c50, 0xbbd3ef2dea752a57, 0x082ca5bb603bb82e],
+    [0x0b74ef3c89494f3e, 0x687a7287b4eaf601, 0x38a963319a74e7f8, 0x03b065443d9cc3a7],
+    [0x3f5fb4c71f60cfa3, 0x15d042798af16b3b, 0x8eb9e3b8e4e3ef63, 0x096538758e2b1272],
+    [0x8cca731c50ad2755, 0x9e5f166cbb62e52c, 0xaf452f92f9460718, 0x09b4fd5b5ae10dc4],
+    [0x98b5e26d00355aec, 0x41809aa635ff38f0, 0xb795cb1dd28973a5, 0x06df2896ef7e9144],
+    [0xc393b551297c0a27, 0x06debee1e05ead67, 0x1c224be917a7355b, 0x0b6bfb62333fc967],
+    [0xcb5807432520f3ab, 0xd573075cf697c541, 0x4691b95371dbeecf, 0x0e5d69dd1f0158eb],
+    [0xafd03f55dd42f6a0, 0x98dad38d2be7e696, 0xf1630e3523932f94, 0x08e40c20e8dfbe83],
+    [0x5df86139148fcdf7, 0x7b5029efad0ed28a, 0x581231fe379f357b, 0x00ea5fa7164d9b0c],
+    [0x0df593795b11eb74, 0xde075d0dc0caa404, 0xf52fa1e978fc91d4, 0x0717a2ec6a8d5bb3],
+    [0xcb14df08388a1586, 0x9b48b8825c991583, 0xe5d5157be77ec8d8, 0x02b663893097a3f8],
+    [0xe951f777ba22e39e, 0xcc48c18a7b2fc508, 0xbb353a5934842da8, 0x0bc32042d531fb34],
+    [0x45b24ca20f88f13c, 0x3cd1f61928cbe85f, 0xf242aa6205e37c30, 0x01bcf0b6323dcff0],
+    [0xa705386ff650cb61, 0xa420f032f8c0abe3, 0xc56819f75b801430, 0x062d752812d0c368],
+    [0xb59eede574cb0de7, 0x53888ceb3a969427, 0xce298914305065cf, 0x00a878614ddac5c3],
+    [0xabdb6aedc94d74ed, 0xa32cfd69e62c2403, 0x054f3d2f22e86896, 0x03b25954aeb504e2],
+    [0x198ef4ad60ef9553, 0x0444907ed9472e73, 0x0cf471ed26bc3911, 0x060367c407233fb6],
+    [0x974579b04f5922cc, 0xd43467655a968d13, 0xc89184999b686fed, 0x006074f4af9991c3],
+    [0x3149f307b556e60b, 0x41c35ddc5054cd2e, 0xf0fd702909036ac1, 0x0ad0a12e27138c81],
+    [0x71ae0319ada9c91a, 0x63d82b17deb381c2, 0x50b6fb77f8fb62f0, 0x07087877c03b2f82],
+    [0x09fda00a07a5ea70, 0x5b8a67fc1a8bfa12, 0x7c45c0cf5a8b7f87, 0x032b61cba36e9719],
+    [0x4344d94ad2f2a336, 0x77df09be847dbd55, 0x4631fff676018c1d, 0x0d8b99c7df229966],
+    [0x0852680c7c488ea6, 0x0cd670b8cb7b7b31, 0x5014c4d361679c85, 0x0ebcc7e937e4b673],
+    [0xeecfe2492667e1fa, 0x17329aad67a3f92b, 0xe7344074d5628185, 0x088ad35fa37fa097],
+    [0x2e2707dc87900c6a, 0x876f23e3da4a57a4, 0x837093fe3eec8281, 0x09b5237749d9d799],
+];
+
+/**
+ * The MDS matrix, as little-endian limbs.
+ */
+#[rustfmt::skip]
+pub const MDS: [[[u64; 4]; 3]; 3] = [
+    [
+        [0x951356b383d653c1, 0x584750ad8c25d93b, 0x9028dcc680564bfb, 0x04202072079398ea],
+        [0x1a2f04cfe217daa6, 0x86a0bada78d7ca31, 0xfdda423fd7553d3a, 0x0cbb83cea019c84e],
+        [0x4378c3945dac745b, 0x9661255ba27ae413, 0x784b6aa64365f223, 0x0c27907d2f266f87],
+    ],
+    [
+        [0x458ea5ac71b3442f, 0xae2f67b66b199042, 0xd9cdf782ba5487a4, 0x04063cc64e5aac0c],
+        [0xe1fe9c346e6ba071, 0x361e2b0648255ca0, 0xbaa02867642ce59f, 0x0fec3a0edfd697b9],
+        [0xea3b8b44dd011f9b, 0xf4ac6ea2aa85b1d2, 0x9c593c2617a177ca, 0x0a4b5e94f8274572],
+    ],
+    [
+        [0x2aee56806741e982, 0x5c669e056b7f2315, 0x1bb43cd4b785288a, 0x09095cdfab2ccd84],
+        [0xcebdcf6fdccaae27, 0x046fc6cda5650018, 0x7a0a6130be9d2bde, 0x0542fae8b9875563],
+        [0x0782c9f5f8132f12, 0xfe4e6c79f7e2f1fe, 0xafdef358d2c12e60, 0x04c88f4dd4daadb9],
+    ],
+];
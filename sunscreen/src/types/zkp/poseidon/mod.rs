@@ -0,0 +1,451 @@
+mod constants;
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_zkp_backend::{BigInt, FieldSpec, ZkpInto};
+
+use crate::{
+    types::zkp::{Field, ProgramNode},
+    zkp::{with_zkp_ctx, ZkpContext, ZkpContextOps},
+};
+
+use constants::{FULL_ROUNDS, MDS, MODULUS, PARTIAL_ROUNDS, ROUND_CONSTANTS};
+
+/**
+ * The number of field elements in the Poseidon state.
+ */
+pub const POSEIDON_WIDTH: usize = 3;
+
+/**
+ * The number of field elements [`poseidon_hash`] absorbs per permutation.
+ */
+pub const POSEIDON_RATE: usize = 2;
+
+const ROUNDS: usize = FULL_ROUNDS + PARTIAL_ROUNDS;
+
+fn limbs_to_bigint(x: &[u64; 4]) -> BigInt {
+    BigInt::from_words([x[0], x[1], x[2], x[3], 0, 0, 0, 0])
+}
+
+fn assert_field<F: FieldSpec>() {
+    assert_eq!(
+        F::FIELD_MODULUS,
+        limbs_to_bigint(&MODULUS),
+        "Poseidon parameters are only defined for the Bulletproofs scalar field"
+    );
+}
+
+fn round_constant(round: usize, i: usize) -> BigInt {
+    limbs_to_bigint(&ROUND_CONSTANTS[round * POSEIDON_WIDTH + i])
+}
+
+fn mds(i: usize, j: usize) -> BigInt {
+    limbs_to_bigint(&MDS[i][j])
+}
+
+/**
+ * Full rounds apply the S-box to every state element, partial rounds only
+ * to the first. Half the full rounds come before the partial rounds and
+ * half after.
+ */
+fn is_full_round(round: usize) -> bool {
+    round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + PARTIAL_ROUNDS
+}
+
+/**
+ * The capacity element's initial value, which separates the domains of
+ * different input lengths.
+ */
+fn capacity_iv(len: usize) -> BigInt {
+    BigInt::from(BigInt::from(len as u64).shl_vartime(64))
+}
+
+/**
+ * Applies the Poseidon permutation to `state` in a ZKP program.
+ *
+ * # Remarks
+ * Uses the parameters in `sunscreen/utils/poseidon_params.py`: width 3,
+ * `x^5` S-boxes, 8 full rounds and 57 partial rounds. Each S-box costs 3
+ * multiplication constraints, for 243 constraints per permutation.
+ *
+ * # Panics
+ * If `F` isn't the Bulletproofs scalar field.
+ */
+pub fn poseidon_permutation<F: FieldSpec>(
+    state: [ProgramNode<Field<F>>; POSEIDON_WIDTH],
+) -> [ProgramNode<Field<F>>; POSEIDON_WIDTH] {
+    assert_field::<F>();
+
+    let state = state.map(|x| x.ids[0]);
+
+    let state = with_zkp_ctx(|ctx| {
+        let mut state = state;
+
+        for round in 0..ROUNDS {
+            for (i, x) in state.iter_mut().enumerate() {
+                let c = ctx.add_constant(&round_constant(round, i));
+                *x = ctx.add_addition(*x, c);
+            }
+
+            if is_full_round(round) {
+                for x in state.iter_mut() {
+                    *x = sbox(ctx, *x);
+                }
+            } else {
+                state[0] = sbox(ctx, state[0]);
+            }
+
+            state = mix(ctx, &state);
+        }
+
+        state
+    });
+
+    state.map(|x| ProgramNode::new(&[x]))
+}
+
+fn sbox(ctx: &mut ZkpContext, x: NodeIndex) -> NodeIndex {
+    let x_2 = ctx.add_multiplication(x, x);
+    let x_4 = ctx.add_multiplication(x_2, x_2);
+
+    ctx.add_multiplication(x_4, x)
+}
+
+fn mix(ctx: &mut ZkpContext, state: &[NodeIndex; POSEIDON_WIDTH]) -> [NodeIndex; POSEIDON_WIDTH] {
+    let mut out = [NodeIndex::default(); POSEIDON_WIDTH];
+
+    for (i, o) in out.iter_mut().enumerate() {
+        let terms = state
+            .iter()
+            .enumerate()
+            .map(|(j, x)| {
+                let m = ctx.add_constant(&mds(i, j));
+                ctx.add_multiplication(m, *x)
+            })
+            .collect::<Vec<_>>();
+
+        *o = terms[1..]
+            .iter()
+            .fold(terms[0], |acc, x| ctx.add_addition(acc, *x));
+    }
+
+    out
+}
+
+/**
+ * Hashes `inputs` to a single field element in a ZKP program using the
+ * Poseidon sponge.
+ *
+ * # Remarks
+ * The sponge has a rate of 2 and a capacity of 1. The capacity element
+ * starts as `inputs.len() * 2^64`, so inputs of different lengths never
+ * collide through zero padding. Inputs are added into the rate elements 2
+ * at a time with a permutation after each chunk, and the first rate
+ * element is the output. An empty input still runs 1 permutation.
+ *
+ * [`poseidon_hash_native`] computes the same hash outside a ZKP program.
+ *
+ * # Panics
+ * If `F` isn't the Bulletproofs scalar field.
+ */
+pub fn poseidon_hash<F: FieldSpec>(inputs: &[ProgramNode<Field<F>>]) -> ProgramNode<Field<F>> {
+    assert_field::<F>();
+
+    let mut state = with_zkp_ctx(|ctx| {
+        let iv = ctx.add_constant(&capacity_iv(inputs.len()));
+        let zero = ctx.add_constant(&BigInt::ZERO);
+
+        [iv, zero, zero].map(|x| ProgramNode::new(&[x]))
+    });
+
+    let mut absorb = |chunk: &[ProgramNode<Field<F>>]| {
+        for (i, x) in chunk.iter().enumerate() {
+            state[i + 1] = state[i + 1] + *x;
+        }
+
+        state = poseidon_permutation(state);
+    };
+
+    if inputs.is_empty() {
+        absorb(&[]);
+    } else {
+        for chunk in inputs.chunks(POSEIDON_RATE) {
+            absorb(chunk);
+        }
+    }
+
+    state[1]
+}
+
+/**
+ * The Poseidon parameters in the backend's native field, for hashing
+ * outside ZKP programs.
+ */
+pub(crate) struct PoseidonNative<F: FieldSpec> {
+    round_constants: Vec<F::BackendField>,
+    mds: Vec<F::BackendField>,
+}
+
+impl<F: FieldSpec> PoseidonNative<F> {
+    /**
+     * # Panics
+     * If `F` isn't the Bulletproofs scalar field.
+     */
+    pub fn new() -> Self {
+        assert_field::<F>();
+
+        let to_backend = |x: &[u64; 4]| F::BackendField::try_from(limbs_to_bigint(x)).unwrap();
+
+        Self {
+            round_constants: ROUND_CONSTANTS.iter().map(to_backend).collect(),
+            mds: MDS.iter().flatten().map(to_backend).collect(),
+        }
+    }
+
+    fn to_backend(x: &Field<F>) -> F::BackendField {
+        let x = BigInt::from(x.val.reduce(&F::FIELD_MODULUS).unwrap());
+
+        F::BackendField::try_from(x).unwrap()
+    }
+
+    fn from_backend(x: F::BackendField) -> Field<F> {
+        let x: BigInt = x.zkp_into();
+
+        Field::from(x)
+    }
+
+    fn permute_backend(
+        &self,
+        mut state: [F::BackendField; POSEIDON_WIDTH],
+    ) -> [F::BackendField; POSEIDON_WIDTH] {
+        let sbox = |x: F::BackendField| {
+            let x_2 = x.clone() * x.clone();
+            let x_4 = x_2.clone() * x_2;
+
+            x_4 * x
+        };
+
+        for round in 0..ROUNDS {
+            for (i, x) in state.iter_mut().enumerate() {
+                *x = x.clone() + self.round_constants[round * POSEIDON_WIDTH + i].clone();
+            }
+
+            if is_full_round(round) {
+                state = state.map(sbox);
+            } else {
+                state[0] = sbox(state[0].clone());
+            }
+
+            state = std::array::from_fn(|i| {
+                let m = &self.mds[i * POSEIDON_WIDTH..(i + 1) * POSEIDON_WIDTH];
+
+                (1..POSEIDON_WIDTH).fold(m[0].clone() * state[0].clone(), |acc, j| {
+                    acc + m[j].clone() * state[j].clone()
+                })
+            });
+        }
+
+        state
+    }
+
+    /**
+     * See [`poseidon_permutation_native`].
+     */
+    pub fn permute(&self, state: [Field<F>; POSEIDON_WIDTH]) -> [Field<F>; POSEIDON_WIDTH] {
+        self.permute_backend(state.map(|x| Self::to_backend(&x)))
+            .map(Self::from_backend)
+    }
+
+    /**
+     * See [`poseidon_hash_native`].
+     */
+    pub fn hash(&self, inputs: &[Field<F>]) -> Field<F> {
+        let zero = F::BackendField::try_from(BigInt::ZERO).unwrap();
+        let iv = F::BackendField::try_from(capacity_iv(inputs.len())).unwrap();
+
+        let mut state = [iv, zero.clone(), zero];
+
+        let mut absorb = |chunk: &[Field<F>]| {
+            for (i, x) in chunk.iter().enumerate() {
+                state[i + 1] = state[i + 1].clone() + Self::to_backend(x);
+            }
+
+            state = self.permute_backend(state.clone());
+        };
+
+        if inputs.is_empty() {
+            absorb(&[]);
+        } else {
+            for chunk in inputs.chunks(POSEIDON_RATE) {
+                absorb(chunk);
+            }
+        }
+
+        let [_, out, _] = state;
+
+        Self::from_backend(out)
+    }
+}
+
+/**
+ * Applies the Poseidon permutation to `state` outside a ZKP program. This
+ * matches [`poseidon_permutation`].
+ *
+ * # Panics
+ * If `F` isn't the Bulletproofs scalar field.
+ */
+pub fn poseidon_permutation_native<F: FieldSpec>(
+    state: [Field<F>; POSEIDON_WIDTH],
+) -> [Field<F>; POSEIDON_WIDTH] {
+    PoseidonNative::new().permute(state)
+}
+
+/**
+ * Hashes `inputs` to a single field element using the Poseidon sponge.
+ * This matches [`poseidon_hash`], so you can use it to compute public
+ * inputs to ZKP programs that hash their private inputs.
+ *
+ * # Panics
+ * If `F` isn't the Bulletproofs scalar field.
+ */
+pub fn poseidon_hash_native<F: FieldSpec>(inputs: &[Field<F>]) -> Field<F> {
+    PoseidonNative::new().hash(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::{types::zkp::ConstrainEq, Compiler};
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    fn from_hex(hex: &str) -> BpField {
+        Field::from_be_hex(&format!("{:0>128}", hex))
+    }
+
+    // Generated with `python3 sunscreen/utils/poseidon_params.py vectors`.
+    #[test]
+    fn native_permutation_matches_test_vector() {
+        let state = [0u8, 1, 2].map(BpField::from);
+
+        let expected = [
+            "049abb9f7c070769f9898f76d909c8222bfd673707d478060fa22f6f4f1cf492",
+            "0c7fb271fa6155bd59f13f66cc46758823013d18659774be6b7f7ec99cb098f1",
+            "05c5b0d3faec2a833557a26c2f3cac9722e95f06263a33f367f18deacbc66f52",
+        ]
+        .map(from_hex);
+
+        let actual = poseidon_permutation_native(state);
+
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert_eq!(a.val, e.val);
+        }
+    }
+
+    #[test]
+    fn native_hash_matches_test_vectors() {
+        let test_case = |inputs: &[BpField], expected: &str| {
+            assert_eq!(poseidon_hash_native(inputs).val, from_hex(expected).val);
+        };
+
+        test_case(
+            &[],
+            "0f290fc04c4d0b9d5082c6efe4cf0977fafc1dc37bbfe34db4ab3967994a7656",
+        );
+        test_case(
+            &[BpField::from(1u8)],
+            "0696b1b79db673fe10f2649e069c099dbf959656cfd32b488fb97aebf3b56a61",
+        );
+        test_case(
+            &[1u8, 2].map(BpField::from),
+            "051c053b496e2621e2d944d547c3ca56657595484daed81258dd8a21a7d11a66",
+        );
+        test_case(
+            &[1u8, 2, 3].map(BpField::from),
+            "0b409c0d9076ba2f0a8ffe6056dc1c29e8bf23d0ec5111a7f1b2d9cf07b97748",
+        );
+        test_case(
+            &[-1i64, 42, 7, 9].map(BpField::from),
+            "0f1ee2afd919d63f7e66edd729b0e1f4b6490c8257f47cc9560a7b552bff352a",
+        );
+    }
+
+    #[test]
+    fn can_hash_in_zkp_program() {
+        #[zkp_program]
+        fn commitment<F: FieldSpec>(a: Field<F>, b: Field<F>, c: Field<F>, #[public] h: Field<F>) {
+            poseidon_hash(&[a, b, c]).constrain_eq(h);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(commitment)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(commitment).unwrap();
+
+        let test_case = |inputs: [BpField; 3], h: BpField, expect_pass: bool| {
+            let result = runtime.prove(program, inputs.to_vec(), vec![h], vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![h], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let inputs = [1u8, 2, 3].map(BpField::from);
+        let h = poseidon_hash_native(&inputs);
+
+        test_case(inputs, h, true);
+        test_case([1u8, 2, 4].map(BpField::from), h, false);
+        test_case(inputs, poseidon_hash_native(&inputs[..2]), false);
+
+        let inputs = [-5i64, 0, 1 << 40].map(BpField::from);
+
+        test_case(inputs, poseidon_hash_native(&inputs), true);
+    }
+
+    #[test]
+    fn in_circuit_permutation_matches_native() {
+        #[zkp_program]
+        fn permute<F: FieldSpec>(x: [Field<F>; 3], #[public] y: [Field<F>; 3]) {
+            let out = poseidon_permutation(x);
+
+            for (o, y) in out.iter().zip(y.iter()) {
+                o.constrain_eq(*y);
+            }
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(permute)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(permute).unwrap();
+
+        let x = [7u8, 8, 9].map(BpField::from);
+        let y = poseidon_permutation_native(x);
+
+        let proof = runtime.prove(program, vec![x], vec![y], vec![]).unwrap();
+
+        runtime
+            .verify(program, &proof, vec![y], Vec::<ZkpProgramInput>::new())
+            .unwrap();
+    }
+}
@@ -3,16 +3,19 @@ use sunscreen_zkp_backend::FieldSpec;
 
 use std::{
     marker::PhantomData,
-    ops::{Add, Div, Mul, Neg, Rem, Sub},
+    ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Sub},
 };
 
 use crate::{
-    types::zkp::{AddVar, DivVar, IntoProgramNode, MulVar, NegVar, RemVar, SubVar, ZkpType},
+    types::zkp::{
+        AddVar, AndVar, DivVar, IntoProgramNode, MulVar, NegVar, NotVar, OrVar, RemVar, SubVar,
+        XorVar, ZkpType,
+    },
     zkp::{with_zkp_ctx, ZkpContextOps},
     INDEX_ARENA,
 };
 
-use super::{ConstrainCmpVarVar, ConstrainEqVarVar, Field};
+use super::{ConstrainCmpVarVar, ConstrainEqVarVar, DivRemVarVar, Field};
 
 #[derive(Clone, Copy)]
 /**
@@ -139,6 +142,8 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_public_input()));
         }
 
+        T::constrain_input(&ids);
+
         Self::new(&ids)
     }
 
@@ -149,6 +154,8 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_private_input()));
         }
 
+        T::constrain_input(&ids);
+
         Self::new(&ids)
     }
 
@@ -159,6 +166,8 @@ where
             ids.push(with_zkp_ctx(|ctx| ctx.add_constant_input()));
         }
 
+        T::constrain_input(&ids);
+
         Self::new(&ids)
     }
 }
@@ -229,6 +238,50 @@ where
     }
 }
 
+impl<T> BitAnd<ProgramNode<T>> for ProgramNode<T>
+where
+    T: AndVar + ZkpType,
+{
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        <T as AndVar>::and(self, rhs)
+    }
+}
+
+impl<T> BitOr<ProgramNode<T>> for ProgramNode<T>
+where
+    T: OrVar + ZkpType,
+{
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        <T as OrVar>::or(self, rhs)
+    }
+}
+
+impl<T> BitXor<ProgramNode<T>> for ProgramNode<T>
+where
+    T: XorVar + ZkpType,
+{
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        <T as XorVar>::xor(self, rhs)
+    }
+}
+
+impl<T> Not for ProgramNode<T>
+where
+    T: NotVar + ZkpType,
+{
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        <T as NotVar>::not(self)
+    }
+}
+
 impl<T> IntoProgramNode for ProgramNode<T>
 where
     T: ZkpType,
@@ -354,3 +407,72 @@ where
         V::constrain_gt_bounded(self.into_program_node(), rhs.into_program_node(), bits);
     }
 }
+
+/**
+ * Integer division of bounded values (i.e. rounding quotients rather
+ * than multiplying by the inverse).
+ */
+pub trait DivRemBounded<Rhs> {
+    /**
+     * The return values will be `ProgramNode<Self::Output>`
+     */
+    type Output: ZkpType;
+
+    /**
+     * Computes the quotient and remainder of dividing this value,
+     * interpreted as a signed integer, by the RHS. The quotient rounds
+     * towards negative infinity, so the remainder lies in `[0, rhs)`.
+     *
+     * # Remarks
+     * The number of bits should be the maximum number of bits required
+     * to represent `abs(self)` and `rhs` as unsigned integers. This
+     * bounds the quotient to `[-2^bits, 2^bits)` and the RHS to
+     * `[1, 2^(bits + 1))`, but the operands aren't otherwise range
+     * checked; see [`DivRemVarVar::div_rem_bounded`].
+     *
+     * `2 * bits + 2` must be less than the number of bits in the backend
+     * field's modulus.
+     */
+    fn div_rem_bounded(
+        self,
+        rhs: Rhs,
+        bits: usize,
+    ) -> (ProgramNode<Self::Output>, ProgramNode<Self::Output>);
+
+    /**
+     * Computes the quotient of [`div_rem_bounded`](Self::div_rem_bounded).
+     */
+    fn div_bounded(self, rhs: Rhs, bits: usize) -> ProgramNode<Self::Output>
+    where
+        Self: Sized,
+    {
+        self.div_rem_bounded(rhs, bits).0
+    }
+
+    /**
+     * Computes the remainder of [`div_rem_bounded`](Self::div_rem_bounded).
+     */
+    fn rem_bounded(self, rhs: Rhs, bits: usize) -> ProgramNode<Self::Output>
+    where
+        Self: Sized,
+    {
+        self.div_rem_bounded(rhs, bits).1
+    }
+}
+
+impl<T, U, V> DivRemBounded<T> for U
+where
+    T: Sized + IntoProgramNode<Output = V>,
+    U: IntoProgramNode<Output = V> + Sized,
+    V: ZkpType + Sized + DivRemVarVar,
+{
+    type Output = V;
+
+    fn div_rem_bounded(
+        self,
+        rhs: T,
+        bits: usize,
+    ) -> (ProgramNode<Self::Output>, ProgramNode<Self::Output>) {
+        V::div_rem_bounded(self.into_program_node(), rhs.into_program_node(), bits)
+    }
+}
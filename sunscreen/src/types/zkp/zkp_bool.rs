@@ -0,0 +1,388 @@
+use std::marker::PhantomData;
+
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        gadgets::AssertBinary, AndVar, ConstrainEqVarVar, Field, IntoProgramNode, NotVar,
+        NumFieldElements, OrVar, ProgramNode, ToNativeFields, XorVar,
+    },
+    zkp::{with_zkp_ctx, ZkpContextOps},
+};
+
+use crate as sunscreen;
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A boolean in a ZKP program, represented as a native field element that
+ * is 0 or 1.
+ *
+ * # Remarks
+ * Program inputs of this type are constrained to be binary, so the proof
+ * fails for any other value.
+ */
+pub struct ZkpBool<F: FieldSpec> {
+    /**
+     * The boolean's value.
+     */
+    pub val: bool,
+
+    _phantom: PhantomData<F>,
+}
+
+// Can't #[derive()] due to PhantomData.
+impl<F: FieldSpec> Copy for ZkpBool<F> {}
+
+impl<F: FieldSpec> From<bool> for ZkpBool<F> {
+    fn from(val: bool) -> Self {
+        Self {
+            val,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<F: FieldSpec> From<ZkpBool<F>> for bool {
+    fn from(x: ZkpBool<F>) -> Self {
+        x.val
+    }
+}
+
+impl<F: FieldSpec> NumFieldElements for ZkpBool<F> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+
+    fn constrain_input(ids: &[NodeIndex]) {
+        invoke_gadget(AssertBinary, ids);
+    }
+}
+
+impl<F: FieldSpec> ToNativeFields for ZkpBool<F> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        vec![BigInt::from(self.val as u64)]
+    }
+}
+
+impl<F: FieldSpec> IntoProgramNode for ZkpBool<F> {
+    type Output = ZkpBool<F>;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        let val = BigInt::from(self.val as u64);
+
+        with_zkp_ctx(|ctx| ProgramNode::new(&[ctx.add_constant(&val)]))
+    }
+}
+
+impl<F: FieldSpec> AndVar for ZkpBool<F> {
+    fn and(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| {
+            let o = ctx.add_multiplication(lhs.ids[0], rhs.ids[0]);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+impl<F: FieldSpec> OrVar for ZkpBool<F> {
+    fn or(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // a | b == a + b - ab
+        with_zkp_ctx(|ctx| {
+            let sum = ctx.add_addition(lhs.ids[0], rhs.ids[0]);
+            let prod = ctx.add_multiplication(lhs.ids[0], rhs.ids[0]);
+            let o = ctx.add_subtraction(sum, prod);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+impl<F: FieldSpec> XorVar for ZkpBool<F> {
+    fn xor(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // a ^ b == a + b - 2ab
+        with_zkp_ctx(|ctx| {
+            let sum = ctx.add_addition(lhs.ids[0], rhs.ids[0]);
+            let prod = ctx.add_multiplication(lhs.ids[0], rhs.ids[0]);
+            let two_prod = ctx.add_addition(prod, prod);
+            let o = ctx.add_subtraction(sum, two_prod);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+impl<F: FieldSpec> NotVar for ZkpBool<F> {
+    fn not(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| {
+            let one = ctx.add_constant(&BigInt::ONE);
+            let o = ctx.add_subtraction(one, lhs.ids[0]);
+
+            ProgramNode::new(&[o])
+        })
+    }
+}
+
+impl<F: FieldSpec> ConstrainEqVarVar for ZkpBool<F> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        with_zkp_ctx(|ctx| {
+            let sub = ctx.add_subtraction(lhs.ids[0], rhs.ids[0]);
+
+            let constraint = ctx.add_constraint(sub, &BigInt::ZERO);
+
+            ProgramNode::new(&[constraint])
+        })
+    }
+}
+
+/**
+ * Returns `a` if `cond` is true and `b` otherwise.
+ */
+pub fn select<F, A, B>(cond: ProgramNode<ZkpBool<F>>, a: A, b: B) -> ProgramNode<Field<F>>
+where
+    F: FieldSpec,
+    A: IntoProgramNode<Output = Field<F>>,
+    B: IntoProgramNode<Output = Field<F>>,
+{
+    let a = a.into_program_node();
+    let b = b.into_program_node();
+
+    // b + cond * (a - b)
+    with_zkp_ctx(|ctx| {
+        let diff = ctx.add_subtraction(a.ids[0], b.ids[0]);
+        let scaled = ctx.add_multiplication(cond.ids[0], diff);
+        let o = ctx.add_addition(b.ids[0], scaled);
+
+        ProgramNode::new(&[o])
+    })
+}
+
+/**
+ * Methods for asserting the value of booleans.
+ */
+pub trait ConstrainBool {
+    /**
+     * Asserts this value is true.
+     */
+    fn constrain_true(&self);
+
+    /**
+     * Asserts this value is false.
+     */
+    fn constrain_false(&self);
+}
+
+impl<F: FieldSpec> ConstrainBool for ProgramNode<ZkpBool<F>> {
+    fn constrain_true(&self) {
+        with_zkp_ctx(|ctx| {
+            ctx.add_constraint(self.ids[0], &BigInt::ONE);
+        });
+    }
+
+    fn constrain_false(&self) {
+        with_zkp_ctx(|ctx| {
+            ctx.add_constraint(self.ids[0], &BigInt::ZERO);
+        });
+    }
+}
+
+/**
+ * Methods for converting values to their native field representation.
+ */
+pub trait ToField<F: FieldSpec> {
+    /**
+     * Returns the field element representing this value. Booleans
+     * become 1 if true and 0 otherwise.
+     */
+    fn to_field(&self) -> ProgramNode<Field<F>>;
+}
+
+impl<F: FieldSpec> ToField<F> for ProgramNode<ZkpBool<F>> {
+    fn to_field(&self) -> ProgramNode<Field<F>> {
+        ProgramNode::new(self.ids)
+    }
+}
+
+/**
+ * Methods for converting field elements into booleans.
+ */
+pub trait ToBool<F: FieldSpec> {
+    /**
+     * Interprets 1 as true and 0 as false. If the value is neither, the
+     * proof will fail to validate.
+     */
+    fn to_bool(&self) -> ProgramNode<ZkpBool<F>>;
+}
+
+impl<F: FieldSpec> ToBool<F> for ProgramNode<Field<F>> {
+    fn to_bool(&self) -> ProgramNode<ZkpBool<F>> {
+        invoke_gadget(AssertBinary, self.ids);
+
+        ProgramNode::new(self.ids)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::{types::zkp::ConstrainEq, Compiler};
+
+    use super::*;
+
+    type BpBool = ZkpBool<<BulletproofsBackend as ZkpBackend>::Field>;
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn can_compute_logic_ops() {
+        #[zkp_program]
+        fn logic<F: FieldSpec>(
+            a: ZkpBool<F>,
+            b: ZkpBool<F>,
+            and: ZkpBool<F>,
+            or: ZkpBool<F>,
+            xor: ZkpBool<F>,
+            not_a: ZkpBool<F>,
+        ) {
+            (a & b).constrain_eq(and);
+            (a | b).constrain_eq(or);
+            (a ^ b).constrain_eq(xor);
+            (!a).constrain_eq(not_a);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(logic)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(logic).unwrap();
+
+        let test_case = |vals: [bool; 6], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vals.iter().map(|x| BpBool::from(*x)).collect::<Vec<_>>(),
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        for a in [false, true] {
+            for b in [false, true] {
+                test_case([a, b, a & b, a | b, a ^ b, !a], true);
+                test_case([a, b, !(a & b), a | b, a ^ b, !a], false);
+                test_case([a, b, a & b, !(a | b), a ^ b, !a], false);
+                test_case([a, b, a & b, a | b, !(a ^ b), !a], false);
+                test_case([a, b, a & b, a | b, a ^ b, a], false);
+            }
+        }
+    }
+
+    #[test]
+    fn can_select_and_constrain() {
+        #[zkp_program]
+        fn policy<F: FieldSpec>(is_admin: ZkpBool<F>, is_owner: ZkpBool<F>, limit: Field<F>) {
+            (is_admin | is_owner).constrain_true();
+            (is_admin & is_owner).constrain_false();
+
+            select(is_admin, Field::from(100u8), Field::from(10u8)).constrain_eq(limit);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(policy)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(policy).unwrap();
+
+        let test_case = |is_admin: bool, is_owner: bool, limit: u8, expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![
+                    ZkpProgramInput::from(BpBool::from(is_admin)),
+                    BpBool::from(is_owner).into(),
+                    BpField::from(limit).into(),
+                ],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(true, false, 100, true);
+        test_case(false, true, 10, true);
+        test_case(true, false, 10, false);
+        test_case(false, true, 100, false);
+        test_case(false, false, 10, false);
+        test_case(true, true, 100, false);
+    }
+
+    #[test]
+    fn to_bool_rejects_non_binary() {
+        #[zkp_program]
+        fn as_bool<F: FieldSpec>(x: Field<F>) {
+            x.to_bool().to_field().constrain_eq(x);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(as_bool)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(as_bool).unwrap();
+
+        let test_case = |x: i64, expect_pass: bool| {
+            let result = runtime.prove(program, vec![BpField::from(x)], vec![], vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(0, true);
+        test_case(1, true);
+        test_case(2, false);
+        test_case(-1, false);
+    }
+}
@@ -0,0 +1,355 @@
+use crypto_bigint::U512;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::types::zkp::{
+    AddVar, ConstrainCmpVarVar, ConstrainEqVarVar, DivRemBounded, Field, IntoProgramNode, MulVar,
+    NegVar, NumFieldElements, ProgramNode, SubVar, ToField, ToNativeFields,
+};
+
+use crate as sunscreen;
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed fixed-point number in a ZKP program with `FRAC_BITS` bits of
+ * precision after the binary point. Values are stored as
+ * `round(x * 2^FRAC_BITS)` in a single native field element.
+ *
+ * # Remarks
+ * This mirrors [`Fractional`](crate::types::bfv::Fractional) on the FHE
+ * side. Addition and subtraction are exact. Multiplication rescales the
+ * product by truncating its low `FRAC_BITS` bits, rounding towards
+ * negative infinity.
+ *
+ * Rescaling requires the product of the operands' scaled representations
+ * to fit in roughly half the bits of the backend field's modulus (e.g.
+ * 125 bits for Bulletproofs); the proof fails to validate otherwise.
+ */
+pub struct ZkpFixed<F: FieldSpec, const FRAC_BITS: usize> {
+    val: Field<F>,
+}
+
+// Can't #[derive()], as that would require F: Copy.
+impl<F: FieldSpec, const FRAC_BITS: usize> Copy for ZkpFixed<F, FRAC_BITS> {}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> From<f64> for ZkpFixed<F, FRAC_BITS> {
+    /**
+     * Rounds `x` to the nearest multiple of `2^-FRAC_BITS`.
+     *
+     * # Panics
+     * If `x` isn't finite or `x * 2^FRAC_BITS` needs more than 127 bits.
+     */
+    fn from(x: f64) -> Self {
+        let scaled = (x * 2f64.powi(FRAC_BITS as i32)).round();
+
+        assert!(
+            scaled.is_finite() && scaled.abs() < 2f64.powi(127),
+            "{} can't be represented with {} fractional bits",
+            x,
+            FRAC_BITS
+        );
+
+        let abs_val = BigInt::from(U512::from_u128(scaled.abs() as u128));
+
+        let val = if scaled < 0.0 {
+            BigInt::from(F::FIELD_MODULUS.wrapping_sub(&abs_val))
+        } else {
+            abs_val
+        };
+
+        Self { val: val.into() }
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> NumFieldElements for ZkpFixed<F, FRAC_BITS> {
+    const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ToNativeFields for ZkpFixed<F, FRAC_BITS> {
+    fn to_native_fields(&self) -> Vec<BigInt> {
+        vec![self.val.val]
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> IntoProgramNode for ZkpFixed<F, FRAC_BITS> {
+    type Output = ZkpFixed<F, FRAC_BITS>;
+
+    fn into_program_node(self) -> ProgramNode<Self> {
+        ProgramNode::new(self.val.into_program_node().ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ToField<F> for ProgramNode<ZkpFixed<F, FRAC_BITS>> {
+    fn to_field(&self) -> ProgramNode<Field<F>> {
+        ProgramNode::new(self.ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> AddVar for ZkpFixed<F, FRAC_BITS> {
+    fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        ProgramNode::new((lhs.to_field() + rhs.to_field()).ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> SubVar for ZkpFixed<F, FRAC_BITS> {
+    fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        ProgramNode::new((lhs.to_field() - rhs.to_field()).ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> NegVar for ZkpFixed<F, FRAC_BITS> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        ProgramNode::new((-lhs.to_field()).ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> MulVar for ZkpFixed<F, FRAC_BITS> {
+    fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // The largest bound div_rem_bounded accepts for this field.
+        let bits = (F::FIELD_MODULUS.bits_vartime() - 3) / 2;
+
+        assert!(
+            FRAC_BITS < bits,
+            "{} fractional bits is too many for the backend field.",
+            FRAC_BITS
+        );
+
+        let prod = lhs.to_field() * rhs.to_field();
+        let scale = Field::<F>::from(BigInt::from(BigInt::ONE.shl_vartime(FRAC_BITS)));
+
+        ProgramNode::new(prod.div_bounded(scale, bits).ids)
+    }
+}
+
+impl<F: FieldSpec, const FRAC_BITS: usize> ConstrainEqVarVar for ZkpFixed<F, FRAC_BITS> {
+    fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        let constraint =
+            <Field<F> as ConstrainEqVarVar>::constrain_eq(lhs.to_field(), rhs.to_field());
+
+        ProgramNode::new(constraint.ids)
+    }
+}
+
+// `bits` bounds the difference of the operands' scaled representations,
+// so it should include FRAC_BITS.
+impl<F: FieldSpec, const FRAC_BITS: usize> ConstrainCmpVarVar for ZkpFixed<F, FRAC_BITS> {
+    fn constrain_le_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        <Field<F> as ConstrainCmpVarVar>::constrain_le_bounded(
+            lhs.to_field(),
+            rhs.to_field(),
+            bits,
+        );
+    }
+
+    fn constrain_lt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        <Field<F> as ConstrainCmpVarVar>::constrain_lt_bounded(
+            lhs.to_field(),
+            rhs.to_field(),
+            bits,
+        );
+    }
+
+    fn constrain_ge_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        <Field<F> as ConstrainCmpVarVar>::constrain_ge_bounded(
+            lhs.to_field(),
+            rhs.to_field(),
+            bits,
+        );
+    }
+
+    fn constrain_gt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+        <Field<F> as ConstrainCmpVarVar>::constrain_gt_bounded(
+            lhs.to_field(),
+            rhs.to_field(),
+            bits,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{
+        bulletproofs::{BulletproofsBackend, BulletproofsFieldSpec},
+        ZkpBackend,
+    };
+
+    use crate::{
+        types::zkp::{ConstrainCmp, ConstrainEq},
+        Compiler,
+    };
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+    type BpFixed = ZkpFixed<<BulletproofsBackend as ZkpBackend>::Field, 16>;
+
+    #[test]
+    fn can_encode_f64() {
+        assert_eq!(BpFixed::from(1.5).val.val, BpField::from(98304).val);
+        assert_eq!(BpFixed::from(-0.25).val.val, BpField::from(-16384).val);
+        assert_eq!(BpFixed::from(0.0).val.val, BpField::from(0).val);
+
+        // Rounds to the nearest multiple of 2^-16.
+        assert_eq!(
+            BpFixed::from(0.1).val.val,
+            BpField::from((0.1f64 * 65536.0).round() as i64).val
+        );
+
+        assert_eq!(
+            ZkpFixed::<BulletproofsFieldSpec, 100>::from(-3.0).val.val,
+            BigInt::from(
+                BulletproofsFieldSpec::FIELD_MODULUS
+                    .wrapping_sub(&BigInt::from(3u64).shl_vartime(100))
+            )
+        );
+    }
+
+    #[test]
+    fn can_add_sub_mul_fixed() {
+        #[zkp_program]
+        fn arith<F: FieldSpec>(
+            a: ZkpFixed<F, 16>,
+            b: ZkpFixed<F, 16>,
+            sum: ZkpFixed<F, 16>,
+            diff: ZkpFixed<F, 16>,
+            prod: ZkpFixed<F, 16>,
+        ) {
+            (a + b).constrain_eq(sum);
+            (a - b).constrain_eq(diff);
+            (a * b).constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(arith)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(arith).unwrap();
+
+        let test_case = |vals: [f64; 5], expect_pass: bool| {
+            let result = runtime.prove(program, vals.map(BpFixed::from).to_vec(), vec![], vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let expected = |a: f64, b: f64| [a, b, a + b, a - b, a * b];
+
+        test_case(expected(1.5, 2.25), true);
+        test_case(expected(-1.5, 0.5), true);
+        test_case(expected(-3.125, -4.0), true);
+        test_case(expected(1000.0, 0.0078125), true);
+        test_case([1.5, 2.25, 3.75, -0.75, 3.5], false);
+        test_case([1.5, 2.25, 3.75, -0.5, 3.375], false);
+    }
+
+    #[test]
+    fn mul_truncates_towards_negative_infinity() {
+        #[zkp_program]
+        fn mul<F: FieldSpec>(a: ZkpFixed<F, 16>, b: ZkpFixed<F, 16>, prod: Field<F>) {
+            (a * b).to_field().constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(mul)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(mul).unwrap();
+
+        let test_case = |a: f64, b: f64, prod: i64, expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![
+                    ZkpProgramInput::from(BpFixed::from(a)),
+                    BpFixed::from(b).into(),
+                    BpField::from(prod).into(),
+                ],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let raw = |x: f64| (x * 65536.0).round() as i64;
+
+        // 0.1 * 0.3 isn't a multiple of 2^-16.
+        let prod = raw(0.1) * raw(0.3);
+        test_case(0.1, 0.3, prod >> 16, true);
+        test_case(0.1, 0.3, (prod >> 16) + 1, false);
+        test_case(-0.1, 0.3, -prod >> 16, true);
+        test_case(-0.1, 0.3, -(prod >> 16), false);
+    }
+
+    #[test]
+    fn can_compare_fixed() {
+        #[zkp_program]
+        fn cmp<F: FieldSpec>(price: ZkpFixed<F, 16>, limit: ZkpFixed<F, 16>) {
+            price.constrain_lt_bounded(limit, 48);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(cmp)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(cmp).unwrap();
+
+        let test_case = |price: f64, limit: f64, expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![BpFixed::from(price), BpFixed::from(limit)],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(19.99, 20.0, true);
+        test_case(-0.5, 0.25, true);
+        test_case(20.0, 20.0, false);
+        test_case(20.01, 20.0, false);
+    }
+}
@@ -0,0 +1,691 @@
+use petgraph::stable_graph::NodeIndex;
+use sunscreen_compiler_macros::TypeName;
+use sunscreen_zkp_backend::{BigInt, FieldSpec};
+
+use crate::{
+    invoke_gadget,
+    types::zkp::{
+        from_unsigned_bits, gadgets::ToUInt, AddVar, ConstrainCmpVarVar, ConstrainEqVarVar, Field,
+        IntoProgramNode, MulVar, NegVar, NumFieldElements, ProgramNode, SubVar, ToBinary, ToField,
+        ToNativeFields,
+    },
+};
+
+use crate as sunscreen;
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * An unsigned `BITS`-bit integer in a ZKP program.
+ *
+ * # Remarks
+ * Program inputs of this type are constrained to lie in `[0, 2^BITS)`.
+ * The `+`, `-` and `*` operators constrain their results to this range
+ * as well, so the proof fails on overflow. Use [`WrappingArithmetic`] to
+ * instead reduce results modulo `2^BITS`.
+ *
+ * Multiplication requires `2 * BITS + 1` to be less than the number of
+ * bits needed to represent the backend field's modulus.
+ */
+pub struct ZkpUInt<F: FieldSpec, const BITS: usize> {
+    val: Field<F>,
+}
+
+// Shouldn't need Clone + Copy, but there appears to be a bug in the Rust
+// compiler that prevents ProgramNode from being Copy if we don't.
+// https://github.com/rust-lang/rust/issues/104264
+#[derive(Debug, Clone, TypeName)]
+/**
+ * A signed `BITS`-bit two's complement integer in a ZKP program.
+ *
+ * # Remarks
+ * Program inputs of this type are constrained to lie in
+ * `[-2^(BITS-1), 2^(BITS-1))`. The `+`, `-`, `*` and negation operators
+ * constrain their results to this range as well, so the proof fails on
+ * overflow. Use [`WrappingArithmetic`] to instead wrap results around
+ * like two's complement hardware would.
+ *
+ * Multiplication requires `2 * BITS + 1` to be less than the number of
+ * bits needed to represent the backend field's modulus.
+ */
+pub struct ZkpInt<F: FieldSpec, const BITS: usize> {
+    val: Field<F>,
+}
+
+/**
+ * An unsigned 8-bit integer in a ZKP program.
+ */
+pub type ZkpU8<F> = ZkpUInt<F, 8>;
+
+/**
+ * An unsigned 16-bit integer in a ZKP program.
+ */
+pub type ZkpU16<F> = ZkpUInt<F, 16>;
+
+/**
+ * An unsigned 32-bit integer in a ZKP program.
+ */
+pub type ZkpU32<F> = ZkpUInt<F, 32>;
+
+/**
+ * An unsigned 64-bit integer in a ZKP program.
+ */
+pub type ZkpU64<F> = ZkpUInt<F, 64>;
+
+/**
+ * A signed 8-bit integer in a ZKP program.
+ */
+pub type ZkpI8<F> = ZkpInt<F, 8>;
+
+/**
+ * A signed 16-bit integer in a ZKP program.
+ */
+pub type ZkpI16<F> = ZkpInt<F, 16>;
+
+/**
+ * A signed 32-bit integer in a ZKP program.
+ */
+pub type ZkpI32<F> = ZkpInt<F, 32>;
+
+/**
+ * A signed 64-bit integer in a ZKP program.
+ */
+pub type ZkpI64<F> = ZkpInt<F, 64>;
+
+impl<F: FieldSpec, const BITS: usize> From<u64> for ZkpUInt<F, BITS> {
+    /**
+     * # Panics
+     * If `x` doesn't fit in `BITS` bits.
+     */
+    fn from(x: u64) -> Self {
+        assert!(
+            BITS >= 64 || x >> BITS == 0,
+            "{} doesn't fit in {} bits",
+            x,
+            BITS
+        );
+
+        Self { val: x.into() }
+    }
+}
+
+impl<F: FieldSpec, const BITS: usize> From<i64> for ZkpInt<F, BITS> {
+    /**
+     * # Panics
+     * If `x` doesn't fit in `BITS` bits.
+     */
+    fn from(x: i64) -> Self {
+        assert!(
+            BITS >= 64 || (x >> (BITS - 1) == 0 || x >> (BITS - 1) == -1),
+            "{} doesn't fit in {} bits",
+            x,
+            BITS
+        );
+
+        Self { val: x.into() }
+    }
+}
+
+fn power_of_two<F: FieldSpec>(bits: usize) -> ProgramNode<Field<F>> {
+    Field::<F>::from(BigInt::from(BigInt::ONE.shl_vartime(bits))).into_program_node()
+}
+
+/**
+ * Constrains x to `[0, 2^bits)` if unsigned and `[-2^(bits-1), 2^(bits-1))`
+ * if signed.
+ */
+fn range_check<F: FieldSpec>(x: ProgramNode<Field<F>>, bits: usize, signed: bool) {
+    let x = if signed {
+        x + power_of_two(bits - 1)
+    } else {
+        x
+    };
+
+    invoke_gadget(ToUInt::new(bits), x.ids);
+}
+
+/**
+ * Reduces `raw` into the range of a `bits`-bit integer.
+ *
+ * # Remarks
+ * `raw` must lie in `[-2^(raw_bits - 2), 2^(raw_bits - 2))`. Offsetting it
+ * by `2^(raw_bits - 1)`, a multiple of `2^bits`, makes it non-negative
+ * without changing its value modulo `2^bits`. We then keep the low `bits`
+ * bits.
+ */
+fn wrap<F: FieldSpec>(
+    raw: ProgramNode<Field<F>>,
+    bits: usize,
+    raw_bits: usize,
+    signed: bool,
+) -> ProgramNode<Field<F>> {
+    let mut x = raw + power_of_two(raw_bits - 1);
+
+    if signed {
+        x = x + power_of_two(bits - 1);
+    }
+
+    let digits = invoke_gadget(ToUInt::new(raw_bits), x.ids);
+
+    let wrapped = from_unsigned_bits(
+        digits
+            .iter()
+            .take(bits)
+            .enumerate()
+            .map(|(i, d)| (i, ProgramNode::new(&[*d]))),
+    );
+
+    if signed {
+        wrapped - power_of_two(bits - 1)
+    } else {
+        wrapped
+    }
+}
+
+fn assert_mul_fits<F: FieldSpec>(bits: usize) {
+    assert!(
+        2 * bits + 1 < F::FIELD_MODULUS.bits_vartime(),
+        "Products of {}-bit integers may overflow the field.",
+        bits
+    );
+}
+
+/**
+ * Arithmetic that wraps around at the boundary of the type rather than
+ * failing the proof.
+ */
+pub trait WrappingArithmetic {
+    /**
+     * Computes `self + rhs`, wrapping around on overflow.
+     */
+    fn wrapping_add(self, rhs: Self) -> Self;
+
+    /**
+     * Computes `self - rhs`, wrapping around on overflow.
+     */
+    fn wrapping_sub(self, rhs: Self) -> Self;
+
+    /**
+     * Computes `self * rhs`, wrapping around on overflow.
+     */
+    fn wrapping_mul(self, rhs: Self) -> Self;
+}
+
+/**
+ * Methods for converting field elements into range-checked integers.
+ */
+pub trait ToZkpInt<F: FieldSpec> {
+    /**
+     * Interprets this value as a `BITS`-bit unsigned integer. If it
+     * doesn't fit, the proof will fail to validate.
+     */
+    fn to_zkp_uint<const BITS: usize>(&self) -> ProgramNode<ZkpUInt<F, BITS>>;
+
+    /**
+     * Interprets this value as a `BITS`-bit signed integer. If it
+     * doesn't fit, the proof will fail to validate.
+     */
+    fn to_zkp_int<const BITS: usize>(&self) -> ProgramNode<ZkpInt<F, BITS>>;
+}
+
+impl<F: FieldSpec> ToZkpInt<F> for ProgramNode<Field<F>> {
+    fn to_zkp_uint<const BITS: usize>(&self) -> ProgramNode<ZkpUInt<F, BITS>> {
+        self.to_unsigned::<BITS>();
+
+        ProgramNode::new(self.ids)
+    }
+
+    fn to_zkp_int<const BITS: usize>(&self) -> ProgramNode<ZkpInt<F, BITS>> {
+        (*self + power_of_two(BITS - 1)).to_unsigned::<BITS>();
+
+        ProgramNode::new(self.ids)
+    }
+}
+
+macro_rules! impl_zkp_int {
+    ($ty:ident, $signed:literal) => {
+        // Can't #[derive()], as that would require F: Copy.
+        impl<F: FieldSpec, const BITS: usize> Copy for $ty<F, BITS> {}
+
+        impl<F: FieldSpec, const BITS: usize> NumFieldElements for $ty<F, BITS> {
+            const NUM_NATIVE_FIELD_ELEMENTS: usize = 1;
+
+            fn constrain_input(ids: &[NodeIndex]) {
+                range_check::<F>(ProgramNode::new(ids), BITS, $signed);
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> ToNativeFields for $ty<F, BITS> {
+            fn to_native_fields(&self) -> Vec<BigInt> {
+                vec![self.val.val]
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> IntoProgramNode for $ty<F, BITS> {
+            type Output = $ty<F, BITS>;
+
+            fn into_program_node(self) -> ProgramNode<Self> {
+                ProgramNode::new(self.val.into_program_node().ids)
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> ToField<F> for ProgramNode<$ty<F, BITS>> {
+            fn to_field(&self) -> ProgramNode<Field<F>> {
+                ProgramNode::new(self.ids)
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> $ty<F, BITS> {
+            fn checked(raw: ProgramNode<Field<F>>) -> ProgramNode<Self> {
+                range_check(raw, BITS, $signed);
+
+                ProgramNode::new(raw.ids)
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> AddVar for $ty<F, BITS> {
+            fn add(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                Self::checked(lhs.to_field() + rhs.to_field())
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> SubVar for $ty<F, BITS> {
+            fn sub(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                Self::checked(lhs.to_field() - rhs.to_field())
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> MulVar for $ty<F, BITS> {
+            fn mul(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                // Bounding the product below half the field modulus means
+                // it can't wrap back into range.
+                assert_mul_fits::<F>(BITS);
+
+                Self::checked(lhs.to_field() * rhs.to_field())
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> WrappingArithmetic for ProgramNode<$ty<F, BITS>> {
+            fn wrapping_add(self, rhs: Self) -> Self {
+                let raw = self.to_field() + rhs.to_field();
+
+                ProgramNode::new(wrap(raw, BITS, BITS + 2, $signed).ids)
+            }
+
+            fn wrapping_sub(self, rhs: Self) -> Self {
+                let raw = self.to_field() - rhs.to_field();
+
+                ProgramNode::new(wrap(raw, BITS, BITS + 2, $signed).ids)
+            }
+
+            fn wrapping_mul(self, rhs: Self) -> Self {
+                assert_mul_fits::<F>(BITS);
+
+                let raw = self.to_field() * rhs.to_field();
+
+                ProgramNode::new(wrap(raw, BITS, 2 * BITS + 1, $signed).ids)
+            }
+        }
+
+        impl<F: FieldSpec, const BITS: usize> ConstrainEqVarVar for $ty<F, BITS> {
+            fn constrain_eq(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>) -> ProgramNode<Self> {
+                let constraint =
+                    <Field<F> as ConstrainEqVarVar>::constrain_eq(lhs.to_field(), rhs.to_field());
+
+                ProgramNode::new(constraint.ids)
+            }
+        }
+
+        // The operands are range-checked, so their difference always
+        // fits in BITS + 1 bits.
+        impl<F: FieldSpec, const BITS: usize> ConstrainCmpVarVar for $ty<F, BITS> {
+            fn constrain_le_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                <Field<F> as ConstrainCmpVarVar>::constrain_le_bounded(
+                    lhs.to_field(),
+                    rhs.to_field(),
+                    usize::min(bits, BITS + 1),
+                );
+            }
+
+            fn constrain_lt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                <Field<F> as ConstrainCmpVarVar>::constrain_lt_bounded(
+                    lhs.to_field(),
+                    rhs.to_field(),
+                    usize::min(bits, BITS + 1),
+                );
+            }
+
+            fn constrain_ge_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                <Field<F> as ConstrainCmpVarVar>::constrain_ge_bounded(
+                    lhs.to_field(),
+                    rhs.to_field(),
+                    usize::min(bits, BITS + 1),
+                );
+            }
+
+            fn constrain_gt_bounded(lhs: ProgramNode<Self>, rhs: ProgramNode<Self>, bits: usize) {
+                <Field<F> as ConstrainCmpVarVar>::constrain_gt_bounded(
+                    lhs.to_field(),
+                    rhs.to_field(),
+                    usize::min(bits, BITS + 1),
+                );
+            }
+        }
+    };
+}
+
+impl_zkp_int!(ZkpUInt, false);
+impl_zkp_int!(ZkpInt, true);
+
+impl<F: FieldSpec, const BITS: usize> NegVar for ZkpInt<F, BITS> {
+    fn neg(lhs: ProgramNode<Self>) -> ProgramNode<Self> {
+        // -(-2^(BITS-1)) overflows.
+        Self::checked(-lhs.to_field())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sunscreen_compiler_macros::zkp_program;
+    use sunscreen_runtime::{Runtime, ZkpProgramInput};
+    use sunscreen_zkp_backend::{bulletproofs::BulletproofsBackend, ZkpBackend};
+
+    use crate::{
+        types::zkp::{ConstrainCmp, ConstrainEq},
+        Compiler,
+    };
+
+    use super::*;
+
+    type BpField = Field<<BulletproofsBackend as ZkpBackend>::Field>;
+    type BpU8 = ZkpU8<<BulletproofsBackend as ZkpBackend>::Field>;
+    type BpI8 = ZkpI8<<BulletproofsBackend as ZkpBackend>::Field>;
+
+    #[test]
+    fn unsigned_checked_arithmetic_rejects_overflow() {
+        #[zkp_program]
+        fn checked<F: FieldSpec>(
+            a: ZkpU8<F>,
+            b: ZkpU8<F>,
+            sum: ZkpU8<F>,
+            diff: ZkpU8<F>,
+            prod: ZkpU8<F>,
+        ) {
+            (a + b).constrain_eq(sum);
+            (a - b).constrain_eq(diff);
+            (a * b).constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(checked)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(checked).unwrap();
+
+        // Pass fields so we can feed the prover out of range values.
+        let test_case = |vals: [i64; 5], expect_pass: bool| {
+            let result = runtime.prove(program, vals.map(BpField::from).to_vec(), vec![], vec![]);
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case([25, 10, 35, 15, 250], true);
+        test_case([255, 0, 255, 255, 0], true);
+        test_case([25, 10, 35, 15, 251], false);
+
+        // Overflowing results can't be witnessed with either the true or
+        // the wrapped value.
+        test_case([200, 56, 256, 144, 0], false);
+        test_case([200, 56, 0, 144, 0], false);
+        test_case([10, 20, 30, -10, 200], false);
+        test_case([10, 20, 30, 246, 200], false);
+
+        // Inputs are range checked.
+        test_case([256, 0, 256, 256, 0], false);
+        test_case([-1, 0, -1, -1, 0], false);
+    }
+
+    #[test]
+    fn unsigned_wrapping_arithmetic_wraps() {
+        #[zkp_program]
+        fn wrapping<F: FieldSpec>(
+            a: ZkpU8<F>,
+            b: ZkpU8<F>,
+            sum: ZkpU8<F>,
+            diff: ZkpU8<F>,
+            prod: ZkpU8<F>,
+        ) {
+            a.wrapping_add(b).constrain_eq(sum);
+            a.wrapping_sub(b).constrain_eq(diff);
+            a.wrapping_mul(b).constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(wrapping)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(wrapping).unwrap();
+
+        let test_case = |vals: [u8; 5], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vals.map(|x| BpU8::from(x as u64)).to_vec(),
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let expected = |a: u8, b: u8| {
+            [
+                a,
+                b,
+                a.wrapping_add(b),
+                a.wrapping_sub(b),
+                a.wrapping_mul(b),
+            ]
+        };
+
+        test_case(expected(200, 100), true);
+        test_case(expected(3, 250), true);
+        test_case(expected(255, 255), true);
+        test_case(expected(0, 0), true);
+        test_case([200, 100, 45, 100, 32], false);
+        test_case([200, 100, 44, 100, 0], false);
+    }
+
+    #[test]
+    fn signed_checked_arithmetic_rejects_overflow() {
+        #[zkp_program]
+        fn checked<F: FieldSpec>(a: ZkpI8<F>, b: ZkpI8<F>, sum: ZkpI8<F>, neg: ZkpI8<F>) {
+            (a + b).constrain_eq(sum);
+            (-a).constrain_eq(neg);
+            a.constrain_le_bounded(b, 64);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(checked)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(checked).unwrap();
+
+        let test_case = |vals: [i8; 4], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vals.map(|x| BpI8::from(x as i64)).to_vec(),
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case([-100, 27, -73, 100], true);
+        test_case([-128, 127, -1, 0], false);
+        test_case([-127, 127, 0, 127], true);
+        test_case([-128, -1, 127, -128], false);
+        test_case([100, 28, -128, -100], false);
+        test_case([5, 4, 9, -5], false);
+    }
+
+    #[test]
+    fn signed_wrapping_arithmetic_wraps() {
+        #[zkp_program]
+        fn wrapping<F: FieldSpec>(
+            a: ZkpI8<F>,
+            b: ZkpI8<F>,
+            sum: ZkpI8<F>,
+            diff: ZkpI8<F>,
+            prod: ZkpI8<F>,
+        ) {
+            a.wrapping_add(b).constrain_eq(sum);
+            a.wrapping_sub(b).constrain_eq(diff);
+            a.wrapping_mul(b).constrain_eq(prod);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(wrapping)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(wrapping).unwrap();
+
+        let test_case = |vals: [i8; 5], expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vals.map(|x| BpI8::from(x as i64)).to_vec(),
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        let expected = |a: i8, b: i8| {
+            [
+                a,
+                b,
+                a.wrapping_add(b),
+                a.wrapping_sub(b),
+                a.wrapping_mul(b),
+            ]
+        };
+
+        test_case(expected(100, 100), true);
+        test_case(expected(-100, 100), true);
+        test_case(expected(-128, -1), true);
+        test_case(expected(127, -128), true);
+        test_case(expected(-7, 3), true);
+        test_case([100, 100, -56, 0, 16], true);
+        test_case([100, 100, 200u8 as i8, 0, 17], false);
+        test_case([-128, -1, -128, -127, 127], false);
+    }
+
+    #[test]
+    fn can_convert_fields() {
+        #[zkp_program]
+        fn convert<F: FieldSpec>(x: Field<F>, y: Field<F>) {
+            let a = x.to_zkp_uint::<8>();
+            let b = y.to_zkp_int::<8>();
+
+            (a.to_field() + b.to_field()).constrain_eq(x + y);
+        }
+
+        let app = Compiler::new()
+            .zkp_backend::<BulletproofsBackend>()
+            .zkp_program(convert)
+            .compile()
+            .unwrap();
+
+        let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+        let program = app.get_zkp_program(convert).unwrap();
+
+        let test_case = |x: i64, y: i64, expect_pass: bool| {
+            let result = runtime.prove(
+                program,
+                vec![BpField::from(x), BpField::from(y)],
+                vec![],
+                vec![],
+            );
+
+            let proof = if expect_pass {
+                result.unwrap()
+            } else {
+                assert!(result.is_err());
+                return;
+            };
+
+            runtime
+                .verify(program, &proof, vec![], Vec::<ZkpProgramInput>::new())
+                .unwrap();
+        };
+
+        test_case(255, -128, true);
+        test_case(0, 127, true);
+        test_case(256, 0, false);
+        test_case(-1, 0, false);
+        test_case(0, 128, false);
+        test_case(0, -129, false);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_panics_when_out_of_range() {
+        let _ = BpU8::from(256);
+    }
+}
@@ -0,0 +1,89 @@
+use petgraph::stable_graph::NodeIndex;
+
+use crate::types::zkp::{ProgramNode, ZkpType};
+
+/**
+ * Implemented by user-defined composite types created with
+ * `#[derive(ZkpType)]`. Exposes the individual fields of a struct
+ * during ZKP program construction.
+ *
+ * You should not implement this trait yourself; the derive macro
+ * generates the implementation along with the `Self::ProgramFields`
+ * companion type.
+ */
+pub trait ZkpStruct: ZkpType {
+    /**
+     * A struct with the same field names as `Self`, where each field
+     * is a [`ProgramNode`] (or array of them) of the corresponding type.
+     */
+    type ProgramFields;
+
+    /**
+     * Splits a struct into its fields.
+     */
+    fn program_fields(node: ProgramNode<Self>) -> Self::ProgramFields;
+}
+
+impl<T> ProgramNode<T>
+where
+    T: ZkpStruct,
+{
+    /**
+     * Returns the fields of this struct as individual [`ProgramNode`]s.
+     *
+     * The returned value can be turned back into a `ProgramNode<T>`
+     * with `into()`.
+     */
+    pub fn fields(self) -> T::ProgramFields {
+        T::program_fields(self)
+    }
+}
+
+/**
+ * Converts between a field of a [`ZkpStruct`]'s `ProgramFields` and the
+ * graph nodes backing it.
+ *
+ * # Remarks
+ * For internal use only.
+ */
+pub trait ProgramNodeIds {
+    /**
+     * Creates a value from the given node indices.
+     */
+    fn from_ids(ids: &[NodeIndex]) -> Self;
+
+    /**
+     * The node indices backing this value.
+     */
+    fn to_ids(&self) -> Vec<NodeIndex>;
+}
+
+impl<T> ProgramNodeIds for ProgramNode<T>
+where
+    T: ZkpType,
+{
+    fn from_ids(ids: &[NodeIndex]) -> Self {
+        Self::new(ids)
+    }
+
+    fn to_ids(&self) -> Vec<NodeIndex> {
+        self.ids.to_vec()
+    }
+}
+
+impl<T, const N: usize> ProgramNodeIds for [T; N]
+where
+    T: ProgramNodeIds,
+{
+    fn from_ids(ids: &[NodeIndex]) -> Self {
+        std::array::from_fn(|i| {
+            let len = ids.len() / N;
+
+            T::from_ids(&ids[i * len..(i + 1) * len])
+        })
+    }
+
+    fn to_ids(&self) -> Vec<NodeIndex> {
+        self.iter().flat_map(|x| x.to_ids()).collect()
+    }
+}
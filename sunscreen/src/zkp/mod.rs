@@ -547,7 +547,10 @@ pub(crate) fn compile(program: &ZkpFrontendCompilation) -> CompiledZkpProgram {
                 Operation::Constant(x) => JitOperation::Constant(x),
             };
 
-            NodeInfo { operation }
+            NodeInfo {
+                operation,
+                provenance: n.provenance.clone(),
+            }
         },
         |_, e| *e,
     );
@@ -0,0 +1,110 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, Error, FheApplication, PlainModulusConstraint, Runtime,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn add(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+    a + b
+}
+
+#[fhe_program(scheme = "bfv")]
+fn mul(a: Cipher<Signed>, b: Signed) -> Cipher<Signed> {
+    a * b
+}
+
+fn saved_app() -> (FheApplication, Vec<u8>) {
+    let app = Compiler::new()
+        .fhe_program(add)
+        .fhe_program(mul)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let mut bytes = vec![];
+    app.save(&mut bytes).unwrap();
+
+    (app, bytes)
+}
+
+#[test]
+fn can_roundtrip_application() {
+    let (app, bytes) = saved_app();
+
+    let loaded = FheApplication::load(bytes.as_slice(), &[&add, &mul]).unwrap();
+
+    assert_eq!(loaded.params(), app.params());
+    assert_eq!(
+        loaded.get_fhe_program(mul).unwrap().metadata,
+        app.get_fhe_program(mul).unwrap().metadata
+    );
+
+    let runtime = Runtime::new_fhe(loaded.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            loaded.get_fhe_program(add).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(7));
+}
+
+#[test]
+fn rejects_modified_contents() {
+    let (_, mut bytes) = saved_app();
+
+    let last = bytes.len() - 1;
+    bytes[last] ^= 1;
+
+    assert!(matches!(
+        FheApplication::load(bytes.as_slice(), &[&add, &mul]),
+        Err(Error::IntegrityCheckFailed)
+    ));
+}
+
+#[test]
+fn rejects_other_crate_versions() {
+    let (_, mut bytes) = saved_app();
+
+    let version = env!("CARGO_PKG_VERSION").as_bytes();
+    let offset = bytes
+        .windows(version.len())
+        .position(|w| w == version)
+        .unwrap();
+
+    // Change the major version recorded in the header.
+    bytes[offset] = b'9';
+
+    assert!(matches!(
+        FheApplication::load(bytes.as_slice(), &[&add, &mul]),
+        Err(Error::VersionMismatch(_))
+    ));
+}
+
+#[test]
+fn rejects_non_applications() {
+    assert!(matches!(
+        FheApplication::load(&b"not an application"[..], &[&add]),
+        Err(Error::SerializationError(_))
+    ));
+}
+
+#[test]
+fn rejects_unexpected_programs() {
+    let (_, bytes) = saved_app();
+
+    assert!(matches!(
+        FheApplication::load(bytes.as_slice(), &[&add]),
+        Err(Error::VersionMismatch(_))
+    ));
+}
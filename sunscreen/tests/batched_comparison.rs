@@ -0,0 +1,81 @@
+use sunscreen::{
+    fhe_program,
+    types::{bfv::Batched, Cipher},
+    Compiler, FheProgramFn, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+fn run_binary<F>(program: F, a: Batched<4>, b: Batched<4>) -> Batched<4>
+where
+    F: FheProgramFn + AsRef<str> + Clone + 'static,
+{
+    let app = Compiler::new()
+        .fhe_program(program.clone())
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(65537))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(program).unwrap(), args, &public_key)
+        .unwrap();
+
+    runtime.decrypt(&result[0], &private_key).unwrap()
+}
+
+#[test]
+fn can_compare_batched_eq() {
+    #[fhe_program(scheme = "bfv")]
+    fn eq(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        Batched::eq(a, b)
+    }
+
+    let a = Batched::<4>::from([[1, 2, -3, 400], [0, 7, 7, -9]]);
+    let b = Batched::<4>::from([[1, 3, -3, 401], [0, -7, 7, -9]]);
+
+    let c = run_binary(eq, a, b);
+
+    assert_eq!(c, [[1, 0, 1, 0], [1, 0, 1, 1]].into());
+}
+
+#[test]
+fn can_compare_batched_lt_small_range() {
+    #[fhe_program(scheme = "bfv")]
+    fn lt(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        Batched::lt_small_range(a, b, 8)
+    }
+
+    let a = Batched::<4>::from([[1, 2, -3, 4], [0, 7, 6, -9]]);
+    let b = Batched::<4>::from([[1, 3, -5, 10], [-7, 0, 7, -2]]);
+
+    let c = run_binary(lt, a, b);
+
+    assert_eq!(c, [[0, 1, 0, 1], [0, 0, 1, 1]].into());
+}
+
+#[test]
+fn can_count_matches() {
+    #[fhe_program(scheme = "bfv")]
+    fn count(a: Cipher<Batched<4>>, b: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        let matches = Batched::eq(a, b);
+
+        // Fold every lane in each row into lane 0.
+        let matches = matches + (matches << 2);
+
+        matches + (matches << 1)
+    }
+
+    let a = Batched::<4>::from([[5, 5, 3, 5], [1, 2, 3, 4]]);
+    let b = Batched::<4>::from(5);
+
+    let c = run_binary(count, a, b);
+
+    assert_eq!(c[(0, 0)], 3);
+    assert_eq!(c[(1, 0)], 0);
+}
@@ -29,3 +29,92 @@ fn can_encrypt_decrypt() {
 
     assert_eq!(c, 20.into());
 }
+
+#[test]
+fn can_run_with_typed_ciphertexts() {
+    #[fhe_program(scheme = "bfv")]
+    fn foo(a: Cipher<Signed>, b: Signed, c: [Cipher<Signed>; 2]) -> Cipher<Signed> {
+        a * b + c[0] + c[1]
+    }
+
+    let app = Compiler::new()
+        .fhe_program(foo)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt_typed(Signed::from(15), &public_key)
+        .unwrap();
+    let c = runtime
+        .encrypt_typed([Signed::from(1), Signed::from(2)], &public_key)
+        .unwrap();
+
+    let result = foo
+        .run(
+            &runtime,
+            app.get_fhe_program(foo).unwrap(),
+            a,
+            Signed::from(5),
+            c,
+            &public_key,
+        )
+        .unwrap();
+
+    let result: Signed = runtime.decrypt_typed(&result, &private_key).unwrap();
+
+    assert_eq!(result, 78.into());
+}
+
+#[test]
+fn decrypt_accepts_typed_ciphertexts() {
+    #[fhe_program(scheme = "bfv")]
+    fn foo(a: Cipher<Signed>) -> Cipher<Signed> {
+        a
+    }
+
+    let app = Compiler::new().fhe_program(foo).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt_typed(Signed::from(15), &public_key)
+        .unwrap();
+
+    let val: Signed = runtime.decrypt(&a, &private_key).unwrap();
+    assert_eq!(val, 15.into());
+
+    assert!(matches!(
+        runtime.decrypt::<[Signed; 2]>(&a, &private_key),
+        Err(RuntimeError::TypeMismatch(_))
+    ));
+}
+
+#[test]
+fn typed_ciphertext_rejects_wrong_type() {
+    #[fhe_program(scheme = "bfv")]
+    fn foo(a: Cipher<Signed>) -> Cipher<Signed> {
+        a
+    }
+
+    let app = Compiler::new().fhe_program(foo).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, _) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(15), &public_key).unwrap();
+
+    assert!(TypedCiphertext::<Signed>::try_from(a.clone()).is_ok());
+    assert!(matches!(
+        TypedCiphertext::<[Signed; 2]>::try_from(a),
+        Err(RuntimeError::TypeMismatch(_))
+    ));
+}
@@ -0,0 +1,134 @@
+use sunscreen::{
+    fhe::FheOperation,
+    fhe_function, fhe_program,
+    types::{bfv::Signed, Cipher},
+    Compiler, FheProgramFn, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+#[fhe_function]
+fn square(x: Cipher<Signed>) -> Cipher<Signed> {
+    x * x
+}
+
+#[fhe_function]
+fn sum_and_product(x: Cipher<Signed>, y: Cipher<Signed>) -> (Cipher<Signed>, Cipher<Signed>) {
+    (x + y, x * y)
+}
+
+#[fhe_function]
+fn norm(x: Cipher<Signed>, y: Cipher<Signed>) -> Cipher<Signed> {
+    square(x) + square(y)
+}
+
+#[fhe_program(scheme = "bfv")]
+fn add_scaled(a: Cipher<Signed>, b: Cipher<Signed>, c: Signed) -> Cipher<Signed> {
+    a * c + b
+}
+
+fn compile_and_run<F>(program: F, args: &[i64]) -> Signed
+where
+    F: FheProgramFn + AsRef<str> + Clone + 'static,
+{
+    let app = Compiler::new()
+        .fhe_program(program.clone())
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let args = args
+        .iter()
+        .map(|x| runtime.encrypt(Signed::from(*x), &public_key).unwrap())
+        .collect::<Vec<_>>();
+
+    let result = runtime
+        .run(app.get_fhe_program(program).unwrap(), args, &public_key)
+        .unwrap();
+
+    runtime.decrypt(&result[0], &private_key).unwrap()
+}
+
+#[test]
+fn can_call_fhe_function() {
+    #[fhe_program(scheme = "bfv")]
+    fn sum_of_squares(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        square(a) + square(b)
+    }
+
+    let c = compile_and_run(sum_of_squares, &[3, 4]);
+
+    assert_eq!(c, Signed::from(25));
+}
+
+#[test]
+fn can_call_fhe_function_with_multiple_outputs() {
+    #[fhe_program(scheme = "bfv")]
+    fn combine(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        let (sum, product) = sum_and_product(a, b);
+
+        square(sum) - product
+    }
+
+    let c = compile_and_run(combine, &[3, 4]);
+
+    assert_eq!(c, Signed::from(37));
+}
+
+#[test]
+fn can_inline_fhe_program() {
+    #[fhe_program(scheme = "bfv")]
+    fn caller(a: Cipher<Signed>, b: Cipher<Signed>, c: Signed) -> Cipher<Signed> {
+        add_scaled.inline(square(a), b, c)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(caller)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime.encrypt(Signed::from(3), &public_key).unwrap();
+    let b = runtime.encrypt(Signed::from(4), &public_key).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), b.into(), Signed::from(2).into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(caller).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(22));
+}
+
+#[test]
+fn inlined_nodes_record_provenance() {
+    #[fhe_program(scheme = "bfv")]
+    fn scaled_norm(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        norm(a, b) * a
+    }
+
+    let app = Compiler::new()
+        .fhe_program(scaled_norm)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let graph = scaled_norm.build(app.params()).unwrap();
+
+    let count = |operation: FheOperation, provenance: Option<&str>| {
+        graph
+            .node_weights()
+            .filter(|n| n.operation == operation && n.provenance.as_deref() == provenance)
+            .count()
+    };
+
+    assert_eq!(count(FheOperation::Multiply, Some("norm::square")), 2);
+    assert_eq!(count(FheOperation::Add, Some("norm")), 1);
+    assert_eq!(count(FheOperation::Multiply, None), 1);
+    assert_eq!(count(FheOperation::InputCiphertext, None), 2);
+}
@@ -0,0 +1,126 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Fractional, Signed},
+        Cipher, NumCiphertexts, TypeName,
+    },
+    Compiler, FheProgramInput, FheType, Runtime,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, FheType)]
+struct Order {
+    price: Fractional<64>,
+    qty: Signed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, FheType)]
+struct Trade {
+    order: Order,
+    fee: Signed,
+}
+
+#[test]
+fn derived_type_counts_ciphertexts() {
+    assert_eq!(Order::NUM_CIPHERTEXTS, 2);
+    assert_eq!(Trade::NUM_CIPHERTEXTS, 3);
+    assert!(Cipher::<Order>::type_name().is_encrypted);
+}
+
+#[test]
+fn can_encrypt_decrypt_struct() {
+    #[fhe_program(scheme = "bfv")]
+    fn identity(a: Cipher<Order>) -> Cipher<Order> {
+        a
+    }
+
+    let app = Compiler::new().fhe_program(identity).compile().unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let order = Order {
+        price: Fractional::from(3.5),
+        qty: Signed::from(-4),
+    };
+
+    let a = runtime.encrypt(order, &public_key).unwrap();
+
+    let result = runtime
+        .run(app.get_fhe_program(identity).unwrap(), vec![a], &public_key)
+        .unwrap();
+
+    let c: Order = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, order);
+}
+
+#[test]
+fn can_operate_on_fields() {
+    #[fhe_program(scheme = "bfv")]
+    fn restock(order: Cipher<Order>, amount: Signed) -> Cipher<Order> {
+        let order = order.fields();
+
+        OrderFields {
+            price: order.price * 2.0,
+            qty: order.qty + amount,
+        }
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn total_qty(trade: Cipher<Trade>) -> Cipher<Signed> {
+        let trade = trade.fields();
+        let order = trade.order.fields();
+
+        order.qty * trade.fee
+    }
+
+    let app = Compiler::new()
+        .fhe_program(restock)
+        .fhe_program(total_qty)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let order = Order {
+        price: Fractional::from(1.5),
+        qty: Signed::from(3),
+    };
+
+    let a = runtime.encrypt(order, &public_key).unwrap();
+    let args: Vec<FheProgramInput> = vec![a.into(), Signed::from(7).into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(restock).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Order = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(
+        c,
+        Order {
+            price: Fractional::from(3.0),
+            qty: Signed::from(10),
+        }
+    );
+
+    let trade = Trade {
+        order,
+        fee: Signed::from(2),
+    };
+
+    let a = runtime.encrypt(trade, &public_key).unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(total_qty).unwrap(),
+            vec![a],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(6));
+}
@@ -3,8 +3,11 @@
 use float_cmp::ApproxEq;
 use sunscreen::{
     fhe_program,
-    types::{bfv::Fractional, Cipher},
-    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+    types::{
+        bfv::{Fractional, FractionalValue, PolynomialApproximation},
+        Cipher,
+    },
+    Compiler, FheProgramInput, InputBound, PlainModulusConstraint, Runtime,
 };
 
 use std::ops::*;
@@ -441,3 +444,173 @@ fn can_negate() {
 fn can_create_default() {
     assert_eq!(Into::<f64>::into(Fractional::<64>::default()), 0.0f64);
 }
+
+#[test]
+fn can_divide_cipher_cipher() {
+    const ITERATIONS: usize = 2;
+
+    fn div_impl<V>(a: V, b: V) -> (V, V)
+    where
+        V: FractionalValue<64>,
+    {
+        (
+            Fractional::<64>::reciprocal(b, 2.0..=4.0, ITERATIONS),
+            Fractional::<64>::divide(a, b, 2.0..=4.0, ITERATIONS),
+        )
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn div(a: CipherFractional, b: CipherFractional) -> (CipherFractional, CipherFractional) {
+        div_impl(a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(div)
+        .additional_noise_budget(10)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1 << 20))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let bound = Fractional::<64>::reciprocal_error(2.0..=4.0, ITERATIONS);
+
+    assert!(bound < 2e-5);
+
+    let do_div = |a: f64, b: f64| {
+        let a_p = Fractional::<64>::from(a);
+        let b_p = Fractional::<64>::from(b);
+
+        let args: Vec<FheProgramInput> = vec![
+            runtime.encrypt(a_p, &public_key).unwrap().into(),
+            runtime.encrypt(b_p, &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(div).unwrap(), args, &public_key)
+            .unwrap();
+
+        let recip: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+        let quot: Fractional<64> = runtime.decrypt(&result[1], &private_key).unwrap();
+
+        let (expected_recip, expected_quot) = div_impl(a_p, b_p);
+
+        assert!(recip.approx_eq(*expected_recip, (1e-12, 0)));
+        assert!(quot.approx_eq(*expected_quot, (1e-12, 0)));
+
+        assert!((1.0 - b * *recip).abs() <= bound);
+        assert!((*quot - a / b).abs() <= bound * (a / b).abs());
+    };
+
+    do_div(1.5, 2.0);
+    do_div(1.5, 2.5);
+    do_div(-5.0, 3.0);
+    do_div(-5.0, 4.0);
+}
+
+#[test]
+fn can_approximate_functions() {
+    fn approximations() -> (PolynomialApproximation, PolynomialApproximation) {
+        (
+            PolynomialApproximation::sigmoid(-4.0..=4.0, 7),
+            PolynomialApproximation::exp(-2.0..=2.0, 6),
+        )
+    }
+
+    fn approx_impl<V>(x: V) -> (V, V)
+    where
+        V: FractionalValue<64>,
+    {
+        let (sigmoid, exp) = approximations();
+
+        (sigmoid.evaluate(x), exp.evaluate(x))
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn approx(x: CipherFractional) -> (CipherFractional, CipherFractional) {
+        approx_impl(x)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(approx)
+        .additional_noise_budget(10)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1 << 20))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let (sigmoid, exp) = approximations();
+
+    let sigmoid_error = sigmoid.max_error(|x| 1.0 / (1.0 + (-x).exp()), 1001);
+    let exp_error = exp.max_error(f64::exp, 1001);
+
+    assert!(sigmoid_error < 1e-2);
+    assert!(exp_error < 1e-4);
+
+    for x in [-1.5, -0.25, 0.5, 1.75] {
+        let x_p = Fractional::<64>::from(x);
+
+        let args: Vec<FheProgramInput> = vec![runtime.encrypt(x_p, &public_key).unwrap().into()];
+
+        let result = runtime
+            .run(app.get_fhe_program(approx).unwrap(), args, &public_key)
+            .unwrap();
+
+        let s: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+        let e: Fractional<64> = runtime.decrypt(&result[1], &private_key).unwrap();
+
+        let (expected_s, expected_e) = approx_impl(x_p);
+
+        assert!(s.approx_eq(*expected_s, (1e-12, 0)));
+        assert!(e.approx_eq(*expected_e, (1e-12, 0)));
+
+        assert!((*s - 1.0 / (1.0 + (-x).exp())).abs() <= sigmoid_error + 1e-9);
+        assert!((*e - x.exp()).abs() <= exp_error + 1e-9);
+    }
+}
+
+#[test]
+fn can_choose_plain_modulus_from_input_bounds() {
+    #[fhe_program(scheme = "bfv")]
+    fn mul(a: CipherFractional, b: CipherFractional) -> CipherFractional {
+        a * b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .plain_modulus_constraint(PlainModulusConstraint::Automatic)
+        .input_bounds(
+            mul,
+            &[InputBound::fractional(4, 8), InputBound::fractional(4, 8)],
+        )
+        .compile()
+        .unwrap();
+
+    // Each operand has 12 digits, so product digits reach 12.
+    assert_eq!(app.params().plain_modulus, 32);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Fractional::<64>::from(15.99609375);
+    let b = Fractional::<64>::from(-15.99609375);
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Fractional::from(15.99609375 * -15.99609375));
+}
@@ -0,0 +1,154 @@
+#![allow(clippy::needless_range_loop)]
+
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Fractional, Signed},
+        ops::{GraphCipherAdd, GraphCipherMul},
+        Cipher, FheType, TypeName,
+    },
+    Compiler, FheProgramFn, PlainModulusConstraint, Runtime,
+};
+
+#[fhe_program(scheme = "bfv")]
+fn dot<const N: usize, T>(a: [Cipher<T>; N], b: [Cipher<T>; N]) -> Cipher<T>
+where
+    T: FheType + Copy + GraphCipherMul<Left = T, Right = T> + GraphCipherAdd<Left = T, Right = T>,
+{
+    let mut sum = a[0] * b[0];
+
+    for i in 1..N {
+        sum = sum + a[i] * b[i];
+    }
+
+    sum
+}
+
+#[test]
+fn instantiations_have_distinct_names() {
+    assert_ne!(dot::<2, Signed>().name(), dot::<3, Signed>().name());
+    assert_ne!(dot::<2, Signed>().name(), dot::<2, Fractional<64>>().name());
+    assert_eq!(dot::<2, Signed>().name(), dot::<2, Signed>().name());
+}
+
+#[test]
+fn instantiation_names_use_type_name() {
+    assert_eq!(
+        dot::<2, Fractional<64>>().name(),
+        format!("dot::<2, {}>", Fractional::<64>::type_name().name)
+    );
+    assert_eq!(
+        dot::<3, Signed>().name(),
+        format!("dot::<3, {}>", Signed::type_name().name)
+    );
+}
+
+#[test]
+fn can_run_generic_program() {
+    let app = Compiler::new()
+        .fhe_program(dot::<2, Signed>())
+        .fhe_program(dot::<4, Signed>())
+        .fhe_program(dot::<2, Fractional<64>>())
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = runtime
+        .encrypt([Signed::from(2), Signed::from(3)], &public_key)
+        .unwrap();
+    let b = runtime
+        .encrypt([Signed::from(4), Signed::from(5)], &public_key)
+        .unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(dot::<2, Signed>()).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(23));
+
+    let a = runtime.encrypt([Signed::from(1); 4], &public_key).unwrap();
+    let b = runtime
+        .encrypt(
+            [
+                Signed::from(1),
+                Signed::from(2),
+                Signed::from(3),
+                Signed::from(4),
+            ],
+            &public_key,
+        )
+        .unwrap();
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(dot::<4, Signed>()).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(10));
+
+    let a = runtime
+        .encrypt(
+            [Fractional::<64>::from(1.5), Fractional::<64>::from(2.0)],
+            &public_key,
+        )
+        .unwrap();
+    let b = runtime
+        .encrypt(
+            [Fractional::<64>::from(2.0), Fractional::<64>::from(0.25)],
+            &public_key,
+        )
+        .unwrap();
+
+    let dot_fractional = dot::<2, Fractional<64>>();
+
+    let result = dot_fractional
+        .run(
+            &runtime,
+            app.get_fhe_program(&dot_fractional).unwrap(),
+            runtime
+                .encrypt_typed(
+                    [Fractional::<64>::from(1.5), Fractional::<64>::from(2.0)],
+                    &public_key,
+                )
+                .unwrap(),
+            runtime
+                .encrypt_typed(
+                    [Fractional::<64>::from(2.0), Fractional::<64>::from(0.25)],
+                    &public_key,
+                )
+                .unwrap(),
+            &public_key,
+        )
+        .unwrap();
+
+    let c = runtime.decrypt_typed(&result, &private_key).unwrap();
+
+    assert_eq!(c, Fractional::<64>::from(3.5));
+
+    let result = runtime
+        .run(
+            app.get_fhe_program(dot_fractional).unwrap(),
+            vec![a, b],
+            &public_key,
+        )
+        .unwrap();
+
+    let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Fractional::<64>::from(3.5));
+}
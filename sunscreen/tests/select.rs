@@ -0,0 +1,144 @@
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{Batched, Fractional, Signed},
+        Cipher, Select,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+/// Returns the larger bid given an (externally computed) bit that is 1
+/// when `a` wins.
+fn winning_bid<T>(a_wins: T, a: T, b: T) -> T
+where
+    T: Select,
+{
+    T::select(a_wins, a, b)
+}
+
+#[test]
+fn can_select_signed() {
+    #[fhe_program(scheme = "bfv")]
+    fn select(bit: Cipher<Signed>, a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        Cipher::<Signed>::select(bit, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(select)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Signed::from(-7);
+    let b = Signed::from(12);
+
+    for bit in [0, 1] {
+        let bit = Signed::from(bit);
+
+        let args: Vec<FheProgramInput> = vec![
+            runtime.encrypt(bit, &public_key).unwrap().into(),
+            runtime.encrypt(a, &public_key).unwrap().into(),
+            runtime.encrypt(b, &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(select).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, Signed::select(bit, a, b));
+    }
+}
+
+#[test]
+fn can_select_fractional() {
+    #[fhe_program(scheme = "bfv")]
+    fn select(
+        bit: Cipher<Fractional<64>>,
+        a: Cipher<Fractional<64>>,
+        b: Cipher<Fractional<64>>,
+    ) -> Cipher<Fractional<64>> {
+        winning_bid(bit, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(select)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Fractional::<64>::from(3.25);
+    let b = Fractional::<64>::from(-1.5);
+
+    for bit in [0.0, 1.0] {
+        let bit = Fractional::<64>::from(bit);
+
+        let args: Vec<FheProgramInput> = vec![
+            runtime.encrypt(bit, &public_key).unwrap().into(),
+            runtime.encrypt(a, &public_key).unwrap().into(),
+            runtime.encrypt(b, &public_key).unwrap().into(),
+        ];
+
+        let result = runtime
+            .run(app.get_fhe_program(select).unwrap(), args, &public_key)
+            .unwrap();
+
+        let c: Fractional<64> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+        assert_eq!(c, winning_bid(bit, a, b));
+    }
+}
+
+#[test]
+fn can_select_batched_lanes() {
+    #[fhe_program(scheme = "bfv")]
+    fn select(
+        mask: Cipher<Batched<4>>,
+        a: Cipher<Batched<4>>,
+        b: Cipher<Batched<4>>,
+    ) -> Cipher<Batched<4>> {
+        winning_bid(mask, a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(select)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let mask = Batched::<4>::from([[1, 0, 0, 1], [0, 1, 1, 0]]);
+    let a = Batched::<4>::from([[1, 2, 3, 4], [5, 6, 7, 8]]);
+    let b = Batched::<4>::from([[-1, -2, -3, -4], [-5, -6, -7, -8]]);
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(mask, &public_key).unwrap().into(),
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(select).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Batched<4> = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, winning_bid(mask, a, b));
+    assert_eq!(c, [[1, -2, -3, 4], [-5, 6, 7, -8]].into());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "Selector must be 0 or 1")]
+fn simulation_rejects_invalid_selector() {
+    winning_bid(Signed::from(2), Signed::from(1), Signed::from(0));
+}
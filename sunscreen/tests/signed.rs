@@ -1,7 +1,7 @@
 use sunscreen::{
     fhe_program,
     types::{bfv::Signed, Cipher},
-    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+    Compiler, Error, FheProgramInput, InputBound, PlainModulusConstraint, Runtime,
 };
 
 use std::ops::*;
@@ -517,3 +517,65 @@ fn can_mul_literal_cipher() {
 fn can_create_default() {
     assert_eq!(Into::<i64>::into(Signed::default()), 0);
 }
+
+#[test]
+fn can_choose_plain_modulus_from_input_bounds() {
+    #[fhe_program(scheme = "bfv")]
+    fn poly(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * a * b + b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(poly)
+        .plain_modulus_constraint(PlainModulusConstraint::Automatic)
+        .input_bounds(poly, &[InputBound::signed(10), InputBound::signed(10)])
+        .compile()
+        .unwrap();
+
+    // a * a * b has digits up to 10 * 10 = 100, and adding b makes 101.
+    assert_eq!(app.params().plain_modulus, 272);
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    // Every digit of these values is nonzero, so they reach the bound.
+    let a = Signed::from(-1023);
+    let b = Signed::from(1023);
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(poly).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: Signed = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, Signed::from(1023 * 1023 * 1023 + 1023));
+}
+
+#[test]
+fn automatic_plain_modulus_requires_input_bounds() {
+    #[fhe_program(scheme = "bfv")]
+    fn mul(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+        a * b
+    }
+
+    let result = Compiler::new()
+        .fhe_program(mul)
+        .plain_modulus_constraint(PlainModulusConstraint::Automatic)
+        .compile();
+
+    assert!(matches!(result, Err(Error::InputBoundsError(_))));
+
+    let result = Compiler::new()
+        .fhe_program(mul)
+        .plain_modulus_constraint(PlainModulusConstraint::Automatic)
+        .input_bounds(mul, &[InputBound::signed(10)])
+        .compile();
+
+    assert!(matches!(result, Err(Error::InputBoundsError(_))));
+}
@@ -0,0 +1,82 @@
+use num::BigInt;
+use sunscreen::{
+    fhe_program,
+    types::{
+        bfv::{SignedBig, SignedBig128},
+        Cipher,
+    },
+    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+};
+
+fn big(val: &str) -> SignedBig128 {
+    SignedBig::try_from(val.parse::<BigInt>().unwrap()).unwrap()
+}
+
+#[test]
+fn can_mul_beyond_64_bits() {
+    #[fhe_program(scheme = "bfv")]
+    fn mul(a: Cipher<SignedBig128>, b: Cipher<SignedBig128>) -> Cipher<SignedBig128> {
+        a * b
+    }
+
+    let app = Compiler::new()
+        .fhe_program(mul)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(1024))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = big("-98765432109876543210");
+    let b = SignedBig128::from(1_000_000_000_000);
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(mul).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: SignedBig128 = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, a * b);
+    assert_eq!(c, big("-98765432109876543210000000000000"));
+}
+
+#[test]
+fn can_add_sub_neg_plain_and_literal() {
+    #[fhe_program(scheme = "bfv")]
+    fn combine(a: Cipher<SignedBig128>, b: SignedBig128) -> Cipher<SignedBig128> {
+        -(a - b) + 7 - (3 - a * 2)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(combine)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::Raw(500))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = big("36893488147419103232");
+    let b = big("-18446744073709551616");
+
+    let args: Vec<FheProgramInput> =
+        vec![runtime.encrypt(a, &public_key).unwrap().into(), b.into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(combine).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c: SignedBig128 = runtime.decrypt(&result[0], &private_key).unwrap();
+
+    assert_eq!(c, -(a - b) + 7 - (3 - a * 2));
+}
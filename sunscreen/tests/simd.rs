@@ -1,7 +1,10 @@
 use sunscreen::{
     fhe_program,
-    types::{bfv::Batched, Cipher, SwapRows},
-    Compiler, FheProgramInput, PlainModulusConstraint, Runtime,
+    types::{
+        bfv::{matrix_vector_product, sparse_matrix_vector_product, Batched, LaneVector},
+        Cipher, LaneOps, SwapRows,
+    },
+    Compiler, Error, FheProgramInput, InputBound, PlainModulusConstraint, Runtime,
 };
 
 use std::ops::*;
@@ -301,3 +304,201 @@ fn can_neg_cipher_cipher() {
 
     assert_eq!(c, neg_impl(a));
 }
+
+#[test]
+fn can_reduce_lanes_cipher() {
+    fn reduce_impl<T>(a: T, b: T) -> (T, T, T, T)
+    where
+        T: LaneOps + Copy,
+    {
+        (a.sum_lanes(), a.sum_rows(), a.broadcast_lane(2), a.dot(&b))
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn reduce(
+        a: Cipher<Batched<4>>,
+        b: Cipher<Batched<4>>,
+    ) -> (
+        Cipher<Batched<4>>,
+        Cipher<Batched<4>>,
+        Cipher<Batched<4>>,
+        Cipher<Batched<4>>,
+    ) {
+        reduce_impl(a, b)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(reduce)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let a = Batched::<4>::try_from([vec![1, 2, 3, 4], vec![5, 6, 7, 8]]).unwrap();
+    let b = Batched::<4>::try_from([vec![1, -1, 2, 0], vec![0, 3, 1, -2]]).unwrap();
+
+    let args: Vec<FheProgramInput> = vec![
+        runtime.encrypt(a, &public_key).unwrap().into(),
+        runtime.encrypt(b, &public_key).unwrap().into(),
+    ];
+
+    let result = runtime
+        .run(app.get_fhe_program(reduce).unwrap(), args, &public_key)
+        .unwrap();
+
+    let c = result
+        .iter()
+        .map(|x| runtime.decrypt(x, &private_key).unwrap())
+        .collect::<Vec<Batched<4>>>();
+
+    let (sum_lanes, sum_rows, broadcast, dot) = reduce_impl(a, b);
+
+    assert_eq!(c, vec![sum_lanes, sum_rows, broadcast, dot]);
+    assert_eq!(dot, Batched::from(14));
+}
+
+#[test]
+fn can_multiply_plain_matrix_cipher_vector() {
+    const MATRIX: [[i64; 8]; 8] = [
+        [1, 0, 2, 0, 0, 0, 0, -1],
+        [0, 3, 0, 0, 1, 0, 0, 0],
+        [0, 0, 1, 0, 0, 0, 2, 0],
+        [4, 0, 0, -2, 0, 0, 0, 0],
+        [0, 0, 0, 0, 1, 1, 1, 1],
+        [0, 5, 0, 0, 0, -1, 0, 0],
+        [0, 0, 0, 0, 0, 0, 1, 0],
+        [2, 2, 2, 2, 0, 0, 0, 0],
+    ];
+
+    const SPARSE: [(usize, usize, i64); 3] = [(0, 7, 3), (5, 2, -2), (7, 7, 1)];
+
+    fn product_impl<V>(v: V) -> (V, V)
+    where
+        V: LaneVector<8>,
+    {
+        (
+            matrix_vector_product(&MATRIX, v),
+            sparse_matrix_vector_product(&SPARSE, v),
+        )
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn product(v: Cipher<Batched<8>>) -> (Cipher<Batched<8>>, Cipher<Batched<8>>) {
+        product_impl(v)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(product)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let v = Batched::<8>::from([[1, 2, 3, 4, 5, 6, 7, 8], [-1, 0, 1, 0, -1, 0, 1, 0]]);
+
+    let args: Vec<FheProgramInput> = vec![runtime.encrypt(v, &public_key).unwrap().into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(product).unwrap(), args, &public_key)
+        .unwrap();
+
+    let dense: Batched<8> = runtime.decrypt(&result[0], &private_key).unwrap();
+    let sparse: Batched<8> = runtime.decrypt(&result[1], &private_key).unwrap();
+
+    let (expected_dense, expected_sparse) = product_impl(v);
+
+    assert_eq!(dense, expected_dense);
+    assert_eq!(
+        dense,
+        [[-1, 11, 17, -4, 26, 4, 7, 20], [1, -1, 3, -4, 0, 0, 1, 0]].into()
+    );
+    assert_eq!(sparse, expected_sparse);
+    assert_eq!(
+        sparse,
+        [[24, 0, 0, 0, 0, -6, 0, 8], [0, 0, 0, 0, 0, -2, 0, 0]].into()
+    );
+}
+
+#[test]
+fn can_permute_lanes_cipher() {
+    // Transpose a 2x4 matrix stored row-major in the lanes.
+    const TRANSPOSE: [usize; 8] = [0, 4, 1, 5, 2, 6, 3, 7];
+
+    // Gather with repeated lanes.
+    const GATHER: [usize; 8] = [7, 7, 0, 3, 3, 3, 1, 6];
+
+    fn permute_impl<V>(v: V) -> (V, V)
+    where
+        V: LaneVector<8>,
+    {
+        (
+            Batched::permute(v, &TRANSPOSE),
+            Batched::permute(v, &GATHER),
+        )
+    }
+
+    #[fhe_program(scheme = "bfv")]
+    fn permute(v: Cipher<Batched<8>>) -> (Cipher<Batched<8>>, Cipher<Batched<8>>) {
+        permute_impl(v)
+    }
+
+    let app = Compiler::new()
+        .fhe_program(permute)
+        .additional_noise_budget(5)
+        .plain_modulus_constraint(PlainModulusConstraint::BatchingMinimum(0))
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_fhe(app.params()).unwrap();
+
+    let (public_key, private_key) = runtime.generate_keys().unwrap();
+
+    let v = Batched::<8>::from([[1, 2, 3, 4, 5, 6, 7, 8], [-1, -2, -3, -4, -5, -6, -7, -8]]);
+
+    let args: Vec<FheProgramInput> = vec![runtime.encrypt(v, &public_key).unwrap().into()];
+
+    let result = runtime
+        .run(app.get_fhe_program(permute).unwrap(), args, &public_key)
+        .unwrap();
+
+    let transposed: Batched<8> = runtime.decrypt(&result[0], &private_key).unwrap();
+    let gathered: Batched<8> = runtime.decrypt(&result[1], &private_key).unwrap();
+
+    let (expected_transposed, expected_gathered) = permute_impl(v);
+
+    assert_eq!(transposed, expected_transposed);
+    assert_eq!(
+        transposed,
+        [[1, 5, 2, 6, 3, 7, 4, 8], [-1, -5, -2, -6, -3, -7, -4, -8]].into()
+    );
+    assert_eq!(gathered, expected_gathered);
+    assert_eq!(
+        gathered,
+        [[8, 8, 1, 4, 4, 4, 2, 7], [-8, -8, -1, -4, -4, -4, -2, -7]].into()
+    );
+}
+
+#[test]
+fn automatic_plain_modulus_rejects_batching() {
+    #[fhe_program(scheme = "bfv")]
+    fn broadcast(a: Cipher<Batched<4>>) -> Cipher<Batched<4>> {
+        // Inserts a lane mask literal, which needs a batching plain modulus.
+        a.broadcast_lane(2)
+    }
+
+    let result = Compiler::new()
+        .fhe_program(broadcast)
+        .plain_modulus_constraint(PlainModulusConstraint::Automatic)
+        .input_bounds(broadcast, &[InputBound::signed(4)])
+        .compile();
+
+    assert!(matches!(result, Err(Error::Unsupported(_))));
+}
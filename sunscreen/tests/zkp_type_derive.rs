@@ -0,0 +1,206 @@
+use sunscreen::{
+    types::zkp::{
+        select, ConstrainCmp, Field, IntoProgramNode, NumFieldElements, ProgramNode,
+        ToNativeFields, ZkpBool,
+    },
+    zkp_program, Compiler, Runtime, ZkpType,
+};
+use sunscreen_zkp_backend::{
+    bulletproofs::{BulletproofsBackend, BulletproofsFieldSpec},
+    FieldSpec,
+};
+
+type BPField = Field<BulletproofsFieldSpec>;
+
+#[derive(Debug, Clone, ZkpType)]
+struct Transaction<F: FieldSpec> {
+    amount: Field<F>,
+    nonce: Field<F>,
+    sender: Field<F>,
+    is_deposit: ZkpBool<F>,
+}
+
+#[derive(Debug, Clone, ZkpType)]
+struct Account<F: FieldSpec> {
+    id: Field<F>,
+    nonce: Field<F>,
+    balance: Field<F>,
+}
+
+#[derive(Debug, Clone, ZkpType)]
+struct Ledger<F: FieldSpec> {
+    account: Account<F>,
+    history: [Transaction<F>; 2],
+}
+
+fn tx(
+    amount: u32,
+    nonce: u32,
+    sender: u32,
+    is_deposit: bool,
+) -> Transaction<BulletproofsFieldSpec> {
+    Transaction {
+        amount: BPField::from(amount),
+        nonce: BPField::from(nonce),
+        sender: BPField::from(sender),
+        is_deposit: ZkpBool::from(is_deposit),
+    }
+}
+
+fn account(id: u32, nonce: u32, balance: u32) -> Account<BulletproofsFieldSpec> {
+    Account {
+        id: BPField::from(id),
+        nonce: BPField::from(nonce),
+        balance: BPField::from(balance),
+    }
+}
+
+#[test]
+fn derived_type_counts_field_elements() {
+    assert_eq!(
+        Transaction::<BulletproofsFieldSpec>::NUM_NATIVE_FIELD_ELEMENTS,
+        4
+    );
+    assert_eq!(
+        Ledger::<BulletproofsFieldSpec>::NUM_NATIVE_FIELD_ELEMENTS,
+        11
+    );
+
+    let ledger = Ledger {
+        account: account(7, 1, 100),
+        history: [tx(30, 1, 7, true), tx(50, 2, 7, false)],
+    };
+
+    let expected = [7u32, 1, 100, 30, 1, 7, 1, 50, 2, 7, 0].map(|x| BPField::from(x).val);
+
+    assert_eq!(ledger.to_native_fields(), expected);
+}
+
+#[test]
+fn can_prove_with_struct_inputs() {
+    #[zkp_program]
+    fn apply<F: FieldSpec>(
+        txs: [Transaction<F>; 2],
+        #[public] account: Account<F>,
+        #[public] new_balance: Field<F>,
+        #[constant] limit: Field<F>,
+    ) {
+        let account = account.fields();
+        let mut balance = account.balance;
+
+        for (i, tx) in txs.into_iter().enumerate() {
+            let tx = tx.fields();
+            let nonce = account.nonce + Field::from(i as u64).into_program_node();
+
+            tx.sender.constrain_eq(account.id);
+            tx.nonce.constrain_eq(nonce);
+            tx.amount.constrain_le_bounded(limit, 32);
+
+            balance = balance + select(tx.is_deposit, tx.amount, -tx.amount);
+        }
+
+        balance.constrain_eq(new_balance);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(apply)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(apply).unwrap();
+
+    let limit = BPField::from(40u8);
+
+    let test_case = |txs: [Transaction<_>; 2], new_balance: u32, expect_pass: bool| {
+        let result = runtime
+            .proof_builder(program)
+            .private_input(txs)
+            .public_input(account(7, 1, 100))
+            .public_input(BPField::from(new_balance))
+            .constant_input(limit)
+            .prove();
+
+        let proof = if expect_pass {
+            result.unwrap()
+        } else {
+            assert!(result.is_err());
+            return;
+        };
+
+        runtime
+            .verification_builder(program)
+            .proof(&proof)
+            .public_input(account(7, 1, 100))
+            .public_input(BPField::from(new_balance))
+            .constant_input(limit)
+            .verify()
+            .unwrap();
+    };
+
+    test_case([tx(30, 1, 7, true), tx(20, 2, 7, false)], 110, true);
+    test_case([tx(40, 1, 7, false), tx(0, 2, 7, true)], 60, true);
+
+    // Wrong balance, sender, nonce or an amount over the limit.
+    test_case([tx(30, 1, 7, true), tx(20, 2, 7, false)], 150, false);
+    test_case([tx(30, 1, 7, true), tx(20, 2, 8, false)], 110, false);
+    test_case([tx(30, 2, 7, true), tx(20, 1, 7, false)], 110, false);
+    test_case([tx(30, 1, 7, true), tx(41, 2, 7, false)], 89, false);
+}
+
+#[test]
+fn can_rebuild_struct_from_fields() {
+    fn bump<F: FieldSpec>(account: ProgramNode<Account<F>>) -> ProgramNode<Account<F>> {
+        let account = account.fields();
+
+        AccountFields {
+            nonce: account.nonce + Field::from(1u8).into_program_node(),
+            ..account
+        }
+        .into()
+    }
+
+    #[zkp_program]
+    fn bump_twice<F: FieldSpec>(account: Account<F>, #[public] expected: Account<F>) {
+        let actual = bump(bump(account)).fields();
+        let expected = expected.fields();
+
+        actual.id.constrain_eq(expected.id);
+        actual.nonce.constrain_eq(expected.nonce);
+        actual.balance.constrain_eq(expected.balance);
+    }
+
+    let app = Compiler::new()
+        .zkp_backend::<BulletproofsBackend>()
+        .zkp_program(bump_twice)
+        .compile()
+        .unwrap();
+
+    let runtime = Runtime::new_zkp(BulletproofsBackend::new()).unwrap();
+
+    let program = app.get_zkp_program(bump_twice).unwrap();
+
+    let proof = runtime
+        .proof_builder(program)
+        .private_input(account(3, 5, 9))
+        .public_input(account(3, 7, 9))
+        .prove()
+        .unwrap();
+
+    runtime
+        .verification_builder(program)
+        .proof(&proof)
+        .public_input(account(3, 7, 9))
+        .verify()
+        .unwrap();
+
+    let result = runtime
+        .proof_builder(program)
+        .private_input(account(3, 5, 9))
+        .public_input(account(3, 6, 9))
+        .prove();
+
+    assert!(result.is_err());
+}
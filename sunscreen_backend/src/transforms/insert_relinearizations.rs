@@ -18,8 +18,11 @@ pub fn apply_insert_relinearizations(ir: &mut FheProgram) {
     let insert_relin = |id: NodeIndex, query: FheGraphQuery| {
         let mut transforms = GraphTransforms::new();
 
+        // Relinearizations belong to the same inlined function as the
+        // multiplication they follow.
         let relin_node = transforms.push(Transform::AddNode(NodeInfo {
             operation: Operation::Relinearize,
+            provenance: query.get_node(id).unwrap().provenance.clone(),
         }));
 
         transforms.push(Transform::AddEdge(
@@ -21,6 +21,13 @@ where
      * The operation this node performs.
      */
     pub operation: O,
+
+    /**
+     * For nodes created while inlining a function, the `::`-separated
+     * path of inlined functions that created this node, outermost first.
+     */
+    #[serde(default)]
+    pub provenance: Option<String>,
 }
 
 impl<O> NodeInfo<O>
@@ -31,7 +38,10 @@ where
      * Creates a new [`NodeInfo`].
      */
     pub fn new(operation: O) -> Self {
-        Self { operation }
+        Self {
+            operation,
+            provenance: None,
+        }
     }
 }
 
@@ -219,6 +229,13 @@ where
      * Data given by the consumer.
      */
     pub data: D,
+
+    /**
+     * The names of the functions currently being inlined into the
+     * graph, outermost first.
+     */
+    #[serde(default)]
+    scopes: Vec<String>,
 }
 
 impl<O, D> Context<O, D>
@@ -232,6 +249,7 @@ where
         Self {
             graph: CompilationResult::<O>::new(),
             data,
+            scopes: vec![],
         }
     }
 
@@ -239,7 +257,41 @@ where
      * Add a node to the parse graph.
      */
     pub fn add_node(&mut self, operation: O) -> NodeIndex {
-        self.graph.add_node(NodeInfo { operation })
+        let provenance = if self.scopes.is_empty() {
+            None
+        } else {
+            Some(self.scopes.join("::"))
+        };
+
+        self.graph.add_node(NodeInfo {
+            operation,
+            provenance,
+        })
+    }
+
+    /**
+     * Enter an inlined function named `name`. Nodes added until the
+     * matching [`pop_scope`](Self::pop_scope) record this scope in
+     * their [`NodeInfo::provenance`].
+     */
+    pub fn push_scope(&mut self, name: &str) {
+        self.scopes.push(name.to_owned());
+    }
+
+    /**
+     * Leave the innermost inlined function scope.
+     */
+    pub fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /**
+     * Returns the `::`-separated path of inlined functions that
+     * created the given node, or [`None`] if it was created directly
+     * in the program body.
+     */
+    pub fn node_provenance(&self, node: NodeIndex) -> Option<&str> {
+        self.graph[node].provenance.as_deref()
     }
 
     /**
@@ -594,6 +594,27 @@ mod tests {
         graph
     }
 
+    #[test]
+    fn records_node_provenance() {
+        let mut ir = TestGraph::new(());
+
+        let in_1 = ir.add_node(Operation::In);
+        ir.push_scope("outer");
+        let in_2 = ir.add_node(Operation::In);
+        ir.push_scope("inner");
+        let add = ir.add_binary_operation(Operation::Add, in_1, in_2);
+        ir.pop_scope();
+        let mul = ir.add_binary_operation(Operation::Mul, add, in_2);
+        ir.pop_scope();
+        let in_3 = ir.add_node(Operation::In);
+
+        assert_eq!(ir.node_provenance(in_1), None);
+        assert_eq!(ir.node_provenance(in_2), Some("outer"));
+        assert_eq!(ir.node_provenance(add), Some("outer::inner"));
+        assert_eq!(ir.node_provenance(mul), Some("outer"));
+        assert_eq!(ir.node_provenance(in_3), None);
+    }
+
     #[test]
     fn can_forward_traverse() {
         let ir = create_simple_dag();
@@ -742,9 +763,7 @@ mod tests {
             if n.index() == 2 {
                 let mut transforms: GraphTransforms<NodeInfo<Operation>, EdgeInfo> =
                     GraphTransforms::new();
-                let mul = transforms.push(Transform::AddNode(NodeInfo {
-                    operation: Operation::Mul,
-                }));
+                let mul = transforms.push(Transform::AddNode(NodeInfo::new(Operation::Mul)));
                 transforms.push(Transform::AddEdge(n.into(), mul.into(), EdgeInfo::Left));
                 transforms.push(Transform::AddEdge(
                     NodeIndex::from(1).into(),
@@ -178,7 +178,7 @@ mod tests {
 
     fn get_graph() -> CompilationResult<Operation> {
         fn make_node(operation: Operation) -> NodeInfo<Operation> {
-            NodeInfo { operation }
+            NodeInfo::new(operation)
         }
 
         let mut fe = CompilationResult::new();
@@ -268,7 +268,7 @@ mod tests {
 
     fn get_expected() -> CompilationResult<Operation> {
         fn make_node(operation: Operation) -> NodeInfo<Operation> {
-            NodeInfo { operation }
+            NodeInfo::new(operation)
         }
 
         let mut fe = CompilationResult::new();
@@ -0,0 +1,219 @@
+use crate::fhe_program_transforms::*;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use sunscreen_compiler_common::macros::{extract_fn_arguments, ExtractFnArgumentsError};
+use syn::{
+    parse_macro_input, spanned::Spanned, Block, Error, Generics, Ident, ItemFn, Result, Type,
+};
+
+pub fn fhe_function_impl(
+    metadata: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let item_fn = parse_macro_input!(input as ItemFn);
+
+    if !metadata.is_empty() {
+        return proc_macro::TokenStream::from(Error::into_compile_error(Error::new(
+            Span::call_site(),
+            "FHE functions do not take any parameters.",
+        )));
+    }
+
+    match fhe_function_inner(&item_fn) {
+        Ok(t) => proc_macro::TokenStream::from(t),
+        Err(e) => proc_macro::TokenStream::from(Error::into_compile_error(e)),
+    }
+}
+
+fn fhe_function_inner(item_fn: &ItemFn) -> Result<TokenStream> {
+    let generics = &item_fn.sig.generics;
+
+    if let Some(lifetime) = generics.lifetimes().next() {
+        return Err(Error::new(
+            lifetime.span(),
+            "FHE functions do not support lifetime parameters.",
+        ));
+    }
+
+    let args = extract_fn_arguments(&item_fn.sig.inputs)
+        .map_err(|e| match e {
+            ExtractFnArgumentsError::ContainsSelf(s) => {
+                Error::new(s, "FHE functions must not contain `self`")
+            }
+            ExtractFnArgumentsError::ContainsMut(s) => {
+                Error::new(s, "FHE function arguments cannot be `mut`")
+            }
+            ExtractFnArgumentsError::IllegalPat(s) => Error::new(s, "Expected Identifier"),
+            ExtractFnArgumentsError::IllegalType(s) => Error::new(
+                s,
+                "FHE function arguments must be an array or named struct type",
+            ),
+        })?
+        .into_iter()
+        .map(|(_, ty, name)| (ty, name))
+        .collect::<Vec<_>>();
+
+    let return_types = extract_return_types(&item_fn.sig.output)
+        .map_err(|ExtractReturnTypesError::IllegalType(s)|
+            Error::new(s, "FHE functions may return a single value or a tuple of values. Each type must be an FHE type or array of such.")
+        )?;
+
+    let attrs = &item_fn.attrs;
+    let vis = &item_fn.vis;
+    let name = &item_fn.sig.ident;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let fn_args = emit_inlined_args(&args)?;
+    let fn_return = emit_inlined_return(&return_types)?;
+    let fn_body = emit_inlined_body(
+        &name.to_string(),
+        generics,
+        &args,
+        &return_types,
+        &item_fn.block,
+    )?;
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis fn #name #impl_generics (#(#fn_args)*) -> #fn_return #where_clause {
+            #fn_body
+        }
+    })
+}
+
+/**
+ * The arguments of a function that inlines an FHE program or function
+ * body, e.g. `a: sunscreen::types::intern::FheProgramNode<Cipher<Signed>>,`.
+ */
+pub fn emit_inlined_args(args: &[(&Type, &Ident)]) -> Result<Vec<TokenStream>> {
+    args.iter()
+        .map(|(ty, name)| {
+            let ty = map_qualified_fhe_type(ty).map_err(|MapFheTypeError::IllegalType(s)| {
+                Error::new(
+                    s,
+                    "FHE function arguments must be an array or named struct type",
+                )
+            })?;
+
+            Ok(quote! { #name: #ty, })
+        })
+        .collect()
+}
+
+/**
+ * The return type of a function that inlines an FHE program or
+ * function body.
+ */
+pub fn emit_inlined_return(return_types: &[Type]) -> Result<TokenStream> {
+    let return_types = return_types
+        .iter()
+        .map(map_qualified_fhe_type)
+        .collect::<std::result::Result<Vec<Type>, MapFheTypeError>>()
+        .map_err(|MapFheTypeError::IllegalType(s)| {
+            Error::new(
+                s,
+                "Each return type must be either an array or named struct type.",
+            )
+        })?;
+
+    Ok(pack_into_tuple(&return_types))
+}
+
+/**
+ * Emits a body that appends the graph of `body` to the FHE program
+ * currently under construction. Nodes created by `body` record
+ * `scope_name` in their provenance.
+ */
+pub fn emit_inlined_body(
+    scope_name: &str,
+    generics: &Generics,
+    args: &[(&Type, &Ident)],
+    return_types: &[Type],
+    body: &Block,
+) -> Result<TokenStream> {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
+
+    let inner_args = args
+        .iter()
+        .map(|(ty, name)| {
+            let ty = map_fhe_type(ty).map_err(|MapFheTypeError::IllegalType(s)| {
+                Error::new(
+                    s,
+                    "FHE function arguments must be an array or named struct type",
+                )
+            })?;
+
+            Ok(quote! { #name: #ty, })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let inner_arg_values = args.iter().map(|(_, name)| *name);
+
+    let mapped_return_types = return_types
+        .iter()
+        .map(|t| map_fhe_type(t).unwrap())
+        .collect::<Vec<_>>();
+    let inner_return = pack_into_tuple(&wrap_impl_coerce(&mapped_return_types));
+    let inner_return_idents = mapped_return_types
+        .iter()
+        .enumerate()
+        .map(|(i, t)| Ident::new(&format!("__r_{}", i), t.span()))
+        .collect::<Vec<_>>();
+    let inner_return_values = pack_into_tuple(&inner_return_idents);
+    let inner_return_into_values = pack_into_tuple(&suffix_coerce(&inner_return_idents));
+
+    Ok(quote! {
+        #[allow(unused_imports)]
+        use sunscreen::{fhe::with_fhe_ctx, types::{intern::{FheProgramNode, Coerce}, NumCiphertexts, Type, TypeName, SwapRows, LaneCount, TypeNameInstance}};
+
+        #[allow(clippy::too_many_arguments)]
+        fn internal_inner #impl_generics (#(#inner_args)*) -> #inner_return #where_clause #body
+
+        with_fhe_ctx(|ctx| ctx.push_scope(#scope_name));
+
+        // Leave the scope even if the body panics, as the caller may catch
+        // the panic and keep building.
+        let panic_res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            internal_inner #turbofish ( #(#inner_arg_values),* )
+        }));
+
+        with_fhe_ctx(|ctx| ctx.pop_scope());
+
+        let #inner_return_values = match panic_res {
+            Ok(v) => v,
+            Err(err) => std::panic::resume_unwind(err),
+        };
+
+        #inner_return_into_values
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn can_define_function() {
+        let item_fn = parse_quote! {
+            fn square<T: FheType>(x: Cipher<T>, y: [Cipher<T>; 2]) -> (Cipher<T>, Cipher<T>) {
+                (x * x, y[0])
+            }
+        };
+
+        let output = fhe_function_inner(&item_fn).unwrap();
+
+        syn::parse2::<syn::File>(output).unwrap();
+    }
+
+    #[test]
+    fn disallows_self() {
+        let item_fn = parse_quote! {
+            fn square(&self, x: Cipher<Signed>) -> Cipher<Signed> {
+                x * x
+            }
+        };
+
+        assert!(fhe_function_inner(&item_fn).is_err());
+    }
+}
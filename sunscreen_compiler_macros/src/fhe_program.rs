@@ -1,11 +1,15 @@
 use crate::{
+    fhe_function::{emit_inlined_args, emit_inlined_body, emit_inlined_return},
     fhe_program_transforms::*,
     internals::attr::{FheProgramAttrs, Scheme},
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use sunscreen_compiler_common::macros::{extract_fn_arguments, ExtractFnArgumentsError, FnArgInfo};
-use syn::{parse_macro_input, spanned::Spanned, Error, Ident, ItemFn, Result, Type};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Error, GenericParam, Ident, ItemFn, Result,
+    Type, WhereClause,
+};
 
 pub fn fhe_program_impl(
     metadata: proc_macro::TokenStream,
@@ -39,10 +43,10 @@ impl<'a> FheProgram<'a> {
         let inputs = &item_fn.sig.inputs;
         let ret = &item_fn.sig.output;
 
-        if !generics.params.is_empty() {
+        if let Some(lifetime) = generics.lifetimes().next() {
             return Err(Error::new(
-                generics.params.span(),
-                "FHE programs do not support generics.",
+                lifetime.span(),
+                "FHE programs do not support lifetime parameters.",
             ));
         }
 
@@ -126,7 +130,7 @@ impl<'a> FheProgram<'a> {
             .enumerate()
             .map(|(i, t)| {
                 let var_name = format!("__c_{}", i);
-                create_fhe_program_node(&var_name, t.1)
+                create_fhe_program_node(&var_name, t.1, !self.is_generic())
             })
             .collect()
     }
@@ -154,6 +158,158 @@ impl<'a> FheProgram<'a> {
             .collect()
     }
 
+    fn is_generic(&self) -> bool {
+        !self.item_fn.sig.generics.params.is_empty()
+    }
+
+    // The where clause of the generated `FheProgramFn` impl. For generic programs, we
+    // additionally require the argument and return types support the traits
+    // needed to build the graph and emit a signature, so users only need to
+    // specify the bounds their program body relies on.
+    fn fhe_program_fn_where_clause(&self) -> Option<WhereClause> {
+        let mut generics = self.item_fn.sig.generics.clone();
+
+        if self.is_generic() {
+            let where_clause = generics.make_where_clause();
+
+            for (_, ty, _) in &self.unwrapped_inputs {
+                let mapped = map_qualified_fhe_type(ty).unwrap();
+
+                where_clause.predicates.push(parse_quote! {
+                    #ty: sunscreen::types::TypeName
+                });
+                where_clause.predicates.push(parse_quote! {
+                    #mapped: sunscreen::types::intern::Input<Output = #mapped>
+                });
+            }
+
+            for ty in &self.return_types {
+                let mapped = map_qualified_fhe_type(ty).unwrap();
+
+                where_clause.predicates.push(parse_quote! {
+                    #ty: sunscreen::types::TypeName + sunscreen::types::NumCiphertexts
+                });
+                where_clause.predicates.push(parse_quote! {
+                    #mapped: sunscreen::types::intern::Output
+                });
+            }
+        }
+
+        generics.where_clause
+    }
+
+    // The additional bounds on the generated typed `run` method. Non-generic
+    // programs have concrete types and don't need any.
+    fn runtime_where_predicates(&self) -> Vec<TokenStream> {
+        if !self.is_generic() {
+            return vec![];
+        }
+
+        let args = self.unwrapped_inputs.iter().map(|(_, ty, _)| {
+            let ty = map_runtime_arg_type(ty);
+
+            quote! { #ty: Into<sunscreen::FheProgramInput>, }
+        });
+
+        let returns = self.return_types.iter().map(|ty| {
+            let ty = map_runtime_return_type(ty);
+
+            quote! { #ty: std::convert::TryFrom<sunscreen::Ciphertext, Error = sunscreen::RuntimeError>, }
+        });
+
+        args.chain(returns).collect()
+    }
+
+    // The where clause of the function returning a generic program's
+    // instantiation. Naming the instantiation requires each type argument
+    // be a `TypeName`.
+    fn instantiation_where_clause(&self) -> Option<WhereClause> {
+        let mut generics = self.item_fn.sig.generics.clone();
+        let type_params = self
+            .item_fn
+            .sig
+            .generics
+            .type_params()
+            .map(|t| &t.ident)
+            .collect::<Vec<_>>();
+
+        if !type_params.is_empty() {
+            let where_clause = generics.make_where_clause();
+
+            for ident in type_params {
+                where_clause.predicates.push(parse_quote! {
+                    #ident: sunscreen::types::TypeName
+                });
+            }
+        }
+
+        generics.where_clause
+    }
+
+    // For generic programs, the name of each instantiation includes its generic
+    // arguments so multiple instantiations can coexist in one `Application`.
+    // Type arguments use their `TypeName`, which, unlike
+    // `std::any::type_name`, is stable across compiler versions, so saved
+    // applications keep resolving.
+    fn instantiated_name(&self) -> TokenStream {
+        let name = self.item_fn.sig.ident.to_string();
+
+        let args = self
+            .item_fn
+            .sig
+            .generics
+            .params
+            .iter()
+            .filter_map(|p| match p {
+                GenericParam::Type(t) => {
+                    let ident = &t.ident;
+                    Some(quote! { <#ident as sunscreen::types::TypeName>::type_name().name })
+                }
+                GenericParam::Const(c) => {
+                    let ident = &c.ident;
+                    Some(quote! { #ident.to_string() })
+                }
+                GenericParam::Lifetime(_) => None,
+            });
+
+        quote! {
+            format!("{}::<{}>", #name, [#(#args),*].join(", "))
+        }
+    }
+
+    // The arguments of the generated typed `run` method, e.g. `a: sunscreen::TypedCiphertext<Signed>`
+    fn runtime_args(&self) -> Vec<TokenStream> {
+        self.unwrapped_inputs
+            .iter()
+            .map(|(_, ty, name)| {
+                let ty = map_runtime_arg_type(ty);
+                quote! {
+                    #name: #ty,
+                }
+            })
+            .collect()
+    }
+
+    // Unpacks the `Vec<Ciphertext>` returned from running the program into
+    // the typed return value of the generated `run` method.
+    fn runtime_return_values(&self, outputs_var: &Ident) -> TokenStream {
+        let values = self
+            .return_types
+            .iter()
+            .map(|t| {
+                quote_spanned! {t.span() =>
+                    std::convert::TryFrom::try_from(
+                        #outputs_var
+                            .next()
+                            .ok_or(sunscreen::RuntimeError::IncorrectCiphertextCount)?
+                    )?
+                }
+            })
+            .collect::<Vec<TokenStream>>();
+
+        pack_into_tuple(&values)
+    }
+
     fn output(self) -> TokenStream {
         let input_fn = self.item_fn;
         let attr_params = &self.attr_params;
@@ -189,21 +345,98 @@ impl<'a> FheProgram<'a> {
         let fhe_arg_var_decl = self.fhe_arg_var_decl();
         let fhe_arg_vars = self.fhe_arg_vars();
         let output_var = Ident::new("__v", Span::call_site());
-        let output_capture = emit_output_capture(&output_var, return_types);
+        let output_capture = emit_output_capture(&output_var, return_types, !self.is_generic());
 
         let fhe_program_struct_name =
             Ident::new(&format!("{}_struct", fhe_program_name), Span::call_site());
 
         let fhe_program_name_literal = format!("{}", fhe_program_name);
 
+        let runtime_args = self.runtime_args();
+        let runtime_return = pack_into_tuple(
+            &return_types
+                .iter()
+                .map(map_runtime_return_type)
+                .collect::<Vec<Type>>(),
+        );
+        let runtime_arg_values = unwrapped_inputs.iter().map(|(_, _, name)| *name);
+        let outputs_var = Ident::new("__outputs", Span::call_site());
+        let runtime_return_values = self.runtime_return_values(&outputs_var);
+        let runtime_where_predicates = self.runtime_where_predicates();
+
+        let inlined_args = unwrapped_inputs
+            .iter()
+            .map(|(_, ty, name)| (*ty, *name))
+            .collect::<Vec<_>>();
+        // These types were validated in `new`.
+        let inline_args = emit_inlined_args(&inlined_args).unwrap();
+        let inline_return = emit_inlined_return(return_types).unwrap();
+        let inline_body = emit_inlined_body(
+            &fhe_program_name.to_string(),
+            &input_fn.sig.generics,
+            &inlined_args,
+            return_types,
+            body,
+        )
+        .unwrap();
+
+        let generics = &input_fn.sig.generics;
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let turbofish = ty_generics.as_turbofish();
+        let fhe_program_fn_where_clause = self.fhe_program_fn_where_clause();
+
+        // Generic programs can't be consts, so we instead emit a function of the same
+        // name returning the instantiated program. The instantiation's name is
+        // computed at runtime since it depends on the generic arguments.
+        let (struct_fields, name_impl, fhe_program_item) = if self.is_generic() {
+            let type_params = generics.type_params().map(|t| &t.ident);
+            let instantiated_name = self.instantiated_name();
+            let instantiation_where_clause = self.instantiation_where_clause();
+
+            (
+                quote! {
+                    chain_count: usize,
+                    name: String,
+                    _phantom: std::marker::PhantomData<fn() -> (#(#type_params,)*)>,
+                },
+                quote! {
+                    &self.name
+                },
+                quote! {
+                    #vis fn #fhe_program_name #impl_generics () -> #fhe_program_struct_name #ty_generics #instantiation_where_clause {
+                        #fhe_program_struct_name {
+                            chain_count: #chain_count,
+                            name: #instantiated_name,
+                            _phantom: std::marker::PhantomData,
+                        }
+                    }
+                },
+            )
+        } else {
+            (
+                quote! {
+                    chain_count: usize
+                },
+                quote! {
+                    #fhe_program_name_literal
+                },
+                quote! {
+                    #[allow(non_upper_case_globals)]
+                    #vis const #fhe_program_name: #fhe_program_struct_name = #fhe_program_struct_name {
+                        chain_count: #chain_count
+                    };
+                },
+            )
+        };
+
         quote! {
             #[allow(non_camel_case_types)]
             #[derive(Clone)]
-            #vis struct #fhe_program_struct_name {
-                chain_count: usize
+            #vis struct #fhe_program_struct_name #impl_generics #where_clause {
+                #struct_fields
             }
 
-            impl sunscreen::FheProgramFn for #fhe_program_struct_name {
+            impl #impl_generics sunscreen::FheProgramFn for #fhe_program_struct_name #ty_generics #fhe_program_fn_where_clause {
                 #[allow(unused_imports)]
                 fn build(&self, params: &sunscreen::Params) -> sunscreen::Result<sunscreen::fhe::FheFrontendCompilation> {
                     use std::cell::RefCell;
@@ -222,9 +455,9 @@ impl<'a> FheProgram<'a> {
                         #[allow(clippy::type_complexity)]
                         #[forbid(unused_variables)]
                         let internal = | #(#fhe_program_args)* | -> #fhe_program_return {
-                            fn internal_inner(#(#fhe_program_args)*) -> #inner_return #body
+                            fn internal_inner #impl_generics (#(#fhe_program_args)*) -> #inner_return #where_clause #body
 
-                            let #inner_return_values = internal_inner( #(#inner_arg_values),* );
+                            let #inner_return_values = internal_inner #turbofish ( #(#inner_arg_values),* );
                             #inner_return_into_values
                         };
 
@@ -235,9 +468,11 @@ impl<'a> FheProgram<'a> {
 
                         #(#fhe_arg_var_decl)*
 
-                        let panic_res = std::panic::catch_unwind(|| {
+                        // FheProgramNodes only hold indices into the graph, so asserting unwind
+                        // safety spares generic programs from needing `UnwindSafe` bounds.
+                        let panic_res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
                             internal(#(#fhe_arg_vars),*)
-                        });
+                        }));
 
                         // when panicing or not, we need to collect our indicies arena and
                         // unset the context reference.
@@ -273,7 +508,7 @@ impl<'a> FheProgram<'a> {
                 }
 
                 fn name(&self) -> &str {
-                    #fhe_program_name_literal
+                    #name_impl
                 }
 
                 fn chain_count(&self) -> usize {
@@ -281,7 +516,43 @@ impl<'a> FheProgram<'a> {
                 }
             }
 
-            impl AsRef<str> for #fhe_program_struct_name {
+            impl #impl_generics #fhe_program_struct_name #ty_generics #where_clause {
+                /// Runs the compiled form of this FHE program with arguments
+                /// whose types are checked at compile time against the
+                /// program's signature.
+                #[allow(clippy::too_many_arguments)]
+                #[allow(dead_code)]
+                pub fn run<__T, __B>(
+                    &self,
+                    __runtime: &sunscreen::GenericRuntime<__T, __B>,
+                    __program: &sunscreen::CompiledFheProgram,
+                    #(#runtime_args)*
+                    __public_key: &sunscreen::PublicKey,
+                ) -> sunscreen::Result<#runtime_return>
+                where
+                    __T: sunscreen::marker::Fhe,
+                    #(#runtime_where_predicates)*
+                {
+                    let __arguments: Vec<sunscreen::FheProgramInput> = vec![#(#runtime_arg_values.into()),*];
+
+                    #[allow(unused_mut, unused_variables)]
+                    let mut #outputs_var = __runtime.run(__program, __arguments, __public_key)?.into_iter();
+
+                    Ok(#runtime_return_values)
+                }
+
+                /// Inlines this FHE program into the FHE program currently being
+                /// built. This may only be called from within the body of another
+                /// [`fhe_program`](sunscreen::fhe_program) or
+                /// [`fhe_function`](sunscreen::fhe_function).
+                #[allow(clippy::too_many_arguments)]
+                #[allow(dead_code)]
+                pub fn inline(&self, #(#inline_args)*) -> #inline_return {
+                    #inline_body
+                }
+            }
+
+            impl #impl_generics AsRef<str> for #fhe_program_struct_name #ty_generics #fhe_program_fn_where_clause {
                 fn as_ref(&self) -> &str {
                     use sunscreen::FheProgramFn;
 
@@ -289,10 +560,7 @@ impl<'a> FheProgram<'a> {
                 }
             }
 
-            #[allow(non_upper_case_globals)]
-            #vis const #fhe_program_name: #fhe_program_struct_name = #fhe_program_struct_name {
-                chain_count: #chain_count
-            };
+            #fhe_program_item
         }
     }
 }
@@ -334,13 +602,30 @@ mod test {
     }
 
     #[test]
-    fn disallows_generics() {
+    fn allows_generics() {
+        let attrs = FheProgramAttrs {
+            scheme: Scheme::Bfv,
+            chain_count: 1,
+        };
+        let attempt_fn = parse_quote! {
+            fn dot<const N: usize, T: FheType>(a: [Cipher<T>; N], b: [Cipher<T>; N]) -> Cipher<T> {
+                a[0] * b[0]
+            }
+        };
+
+        let program = FheProgram::new(&attempt_fn, attrs).unwrap();
+
+        syn::parse2::<syn::File>(program.output()).unwrap();
+    }
+
+    #[test]
+    fn disallows_lifetimes() {
         let attrs = FheProgramAttrs {
             scheme: Scheme::Bfv,
             chain_count: 1,
         };
         let attempt_fn = parse_quote! {
-            fn simple_multiply<T>(a: Cipher<T>, b: Cipher<Signed>) -> Cipher<Signed> {
+            fn simple_multiply<'a>(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
                 b
             }
         };
@@ -1,6 +1,9 @@
 use proc_macro2::{Span, TokenStream as TokenStream2};
 use quote::{format_ident, quote, quote_spanned, ToTokens};
-use syn::{parse_quote, parse_quote_spanned, spanned::Spanned, Ident, Index, ReturnType, Type};
+use syn::{
+    parse_quote, parse_quote_spanned, spanned::Spanned, GenericArgument, Ident, Index,
+    PathArguments, ReturnType, Type,
+};
 
 #[derive(Debug)]
 pub enum MapFheTypeError {
@@ -13,10 +16,26 @@ pub enum MapFheTypeError {
  * * [FheProgramNode<T>; N] when T is Array
  */
 pub fn map_fhe_type(arg_type: &Type) -> Result<Type, MapFheTypeError> {
+    map_fhe_type_with(arg_type, &quote! { FheProgramNode })
+}
+
+/**
+ * The same as [`map_fhe_type`], but uses the fully qualified path to
+ * `FheProgramNode`, for use outside the scope of the `use` statements
+ * emitted in generated FHE program bodies (e.g. where clauses).
+ */
+pub fn map_qualified_fhe_type(arg_type: &Type) -> Result<Type, MapFheTypeError> {
+    map_fhe_type_with(
+        arg_type,
+        &quote! { sunscreen::types::intern::FheProgramNode },
+    )
+}
+
+fn map_fhe_type_with(arg_type: &Type, node: &TokenStream2) -> Result<Type, MapFheTypeError> {
     let transformed_type = match arg_type {
-        Type::Path(ty) => parse_quote_spanned! {ty.span() => FheProgramNode<#ty> },
+        Type::Path(ty) => parse_quote_spanned! {ty.span() => #node<#ty> },
         Type::Array(a) => {
-            let inner_type = map_fhe_type(&a.elem)?;
+            let inner_type = map_fhe_type_with(&a.elem, node)?;
             let len = &a.len;
 
             parse_quote_spanned! {a.span() =>
@@ -31,11 +50,95 @@ pub fn map_fhe_type(arg_type: &Type) -> Result<Type, MapFheTypeError> {
     Ok(transformed_type)
 }
 
+/**
+ * Returns `T` if the given type is `Cipher<T>` and `None` otherwise.
+ */
+fn unwrap_cipher(arg_type: &Type) -> Option<&Type> {
+    let ty = match arg_type {
+        Type::Path(ty) => ty,
+        _ => return None,
+    };
+
+    let segment = ty.path.segments.last()?;
+
+    if segment.ident != "Cipher" {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(a) => match a.args.first() {
+            Some(GenericArgument::Type(t)) if a.args.len() == 1 => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/**
+ * Removes the `Cipher` wrapper from the given type, descending into
+ * arrays. E.g. `[Cipher<Signed>; 6]` becomes `[Signed; 6]`. The
+ * returned flag indicates whether any `Cipher` was removed.
+ */
+fn strip_cipher(arg_type: &Type) -> (Type, bool) {
+    if let Some(inner) = unwrap_cipher(arg_type) {
+        return (inner.clone(), true);
+    }
+
+    match arg_type {
+        Type::Array(a) => {
+            let (inner_type, is_encrypted) = strip_cipher(&a.elem);
+            let len = &a.len;
+
+            (
+                parse_quote_spanned! {a.span() => [#inner_type; #len] },
+                is_encrypted,
+            )
+        }
+        _ => (arg_type.clone(), false),
+    }
+}
+
+/**
+ * Given an FHE program argument type, returns the type the generated
+ * `run` method accepts in its place:
+ * * `sunscreen::TypedCiphertext<T>` when the argument is `Cipher<T>`
+ * * `sunscreen::TypedCiphertext<[T; N]>` when the argument is `[Cipher<T>; N]`
+ * * the unmodified type for plaintext arguments.
+ */
+pub fn map_runtime_arg_type(arg_type: &Type) -> Type {
+    match strip_cipher(arg_type) {
+        (inner, true) => {
+            parse_quote_spanned! {arg_type.span() => sunscreen::TypedCiphertext<#inner> }
+        }
+        (_, false) => arg_type.clone(),
+    }
+}
+
+/**
+ * Given an FHE program return type, returns the type the generated
+ * `run` method returns in its place. FHE programs always return
+ * ciphertexts, so this is always a `sunscreen::TypedCiphertext`.
+ */
+pub fn map_runtime_return_type(return_type: &Type) -> Type {
+    let (inner, _) = strip_cipher(return_type);
+
+    parse_quote_spanned! {return_type.span() => sunscreen::TypedCiphertext<#inner> }
+}
+
 /**
  * Emits code to make an FHE program node for the given
  * type T.
+ *
+ * When `assert_types` is set, a local item asserting the mapped type
+ * impls `Input` is emitted to give better error messages. Generic FHE
+ * programs can't use these, as local items can't refer to outer
+ * generic parameters.
  */
-pub fn create_fhe_program_node(var_name: &str, arg_type: &Type) -> TokenStream2 {
+pub fn create_fhe_program_node(
+    var_name: &str,
+    arg_type: &Type,
+    assert_types: bool,
+) -> TokenStream2 {
     let mapped_type = match map_fhe_type(arg_type) {
         Ok(v) => v,
         Err(MapFheTypeError::IllegalType(s)) => {
@@ -47,8 +150,16 @@ pub fn create_fhe_program_node(var_name: &str, arg_type: &Type) -> TokenStream2
 
     let var_name = format_ident!("{}", var_name);
 
+    let assertion = if assert_types {
+        quote_spanned! {arg_type.span() =>
+            { struct _AssertInput where #mapped_type: Input; }
+        }
+    } else {
+        quote! {}
+    };
+
     quote_spanned! {arg_type.span() =>
-        { struct _AssertInput where #mapped_type: Input; }
+        #assertion
         let #var_name: #mapped_type = <#mapped_type as Input>::input();
     }
 }
@@ -127,20 +238,37 @@ pub fn suffix_coerce(is: &[Ident]) -> Vec<TokenStream2> {
         .collect()
 }
 
-pub fn emit_output_capture(var: &Ident, return_types: &[Type]) -> TokenStream2 {
+/**
+ * Emits code capturing each value in `var` as an FHE program output.
+ * See [`create_fhe_program_node`] for the meaning of `assert_types`.
+ */
+pub fn emit_output_capture(var: &Ident, return_types: &[Type], assert_types: bool) -> TokenStream2 {
+    let assertion = |ty: &Type| {
+        if assert_types {
+            quote_spanned! {ty.span() => struct _AssertOutput where FheProgramNode<#ty>: Output; }
+        } else {
+            quote! {}
+        }
+    };
+
     match return_types {
-        [ty] => quote_spanned! { ty.span() => {
-            struct _AssertOutput where FheProgramNode<#ty>: Output;
-            #var.output();
-        }},
+        [ty] => {
+            let assertion = assertion(ty);
+
+            quote_spanned! { ty.span() => {
+                #assertion
+                #var.output();
+            }}
+        }
         _ => return_types
             .iter()
             .enumerate()
             .map(|(i, ty)| {
                 let index = Index::from(i);
+                let assertion = assertion(ty);
 
                 quote_spanned! {ty.span() => {
-                    struct _AssertOutput where FheProgramNode<#ty>: Output;
+                    #assertion
                     #var.#index.output();
                 }}
             })
@@ -149,56 +277,27 @@ pub fn emit_output_capture(var: &Ident, return_types: &[Type]) -> TokenStream2 {
 }
 
 pub fn emit_signature(args: &[Type], return_types: &[Type]) -> TokenStream2 {
-    let arg_type_names = args
-        .iter()
-        .enumerate()
-        .map(|(i, t)| {
-            let alias = format_ident!("T{}", i);
-
-            quote! {
-                type #alias = #t;
-            }
-        })
-        .collect::<Vec<TokenStream2>>();
-
-    let arg_get_types = arg_type_names.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("T{}", i);
-
-        quote! {
-            #alias::type_name(),
-        }
-    });
-
-    let return_type_aliases = return_types.iter().enumerate().map(|(i, t)| {
-        let alias = format_ident!("R{}", i);
-
+    // Use fully qualified paths rather than local type aliases, as the latter
+    // can't refer to the generic parameters of a generic FHE program.
+    let arg_get_types = args.iter().map(|t| {
         quote! {
-            type #alias = #t;
+            <#t as sunscreen::types::TypeName>::type_name(),
         }
     });
 
-    let return_type_names = return_types.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("R{}", i);
-
+    let return_type_names = return_types.iter().map(|t| {
         quote! {
-            #alias ::type_name(),
+            <#t as sunscreen::types::TypeName>::type_name(),
         }
     });
 
-    let return_type_sizes = return_types.iter().enumerate().map(|(i, _)| {
-        let alias = format_ident!("R{}", i);
-
+    let return_type_sizes = return_types.iter().map(|t| {
         quote! {
-            #alias ::NUM_CIPHERTEXTS,
+            <#t as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS,
         }
     });
 
     quote! {
-        use sunscreen::types::TypeName;
-
-        #(#arg_type_names)*
-        #(#return_type_aliases)*
-
         sunscreen::CallSignature {
             arguments: vec![#(#arg_get_types)*],
             returns: vec![#(#return_type_names)*],
@@ -303,6 +402,54 @@ mod test {
         assert_syn_eq(&actual, &expected);
     }
 
+    #[test]
+    fn map_runtime_cipher_type() {
+        let type_name: Type = parse_quote! { Cipher<Signed> };
+
+        let actual = map_runtime_arg_type(&type_name);
+
+        let expected: Type = parse_quote! {
+            sunscreen::TypedCiphertext<Signed>
+        };
+
+        assert_syn_eq(&actual, &expected);
+    }
+
+    #[test]
+    fn map_runtime_cipher_array_type() {
+        let type_name: Type = parse_quote! { [[Cipher<Rational>; 6]; 7] };
+
+        let actual = map_runtime_arg_type(&type_name);
+
+        let expected: Type = parse_quote! {
+            sunscreen::TypedCiphertext<[[Rational; 6]; 7]>
+        };
+
+        assert_syn_eq(&actual, &expected);
+    }
+
+    #[test]
+    fn map_runtime_plain_type_is_unchanged() {
+        let type_name: Type = parse_quote! { [sunscreen::types::bfv::Signed; 6] };
+
+        let actual = map_runtime_arg_type(&type_name);
+
+        assert_syn_eq(&actual, &type_name);
+    }
+
+    #[test]
+    fn map_runtime_return_type_is_typed_ciphertext() {
+        let type_name: Type = parse_quote! { sunscreen::types::Cipher<Fractional<64>> };
+
+        let actual = map_runtime_return_type(&type_name);
+
+        let expected: Type = parse_quote! {
+            sunscreen::TypedCiphertext<Fractional<64> >
+        };
+
+        assert_syn_eq(&actual, &expected);
+    }
+
     #[test]
     fn can_create_simple_fhe_program_node() {
         let type_name = quote! {
@@ -311,7 +458,7 @@ mod test {
 
         let type_name: Type = parse_quote!(#type_name);
 
-        let actual = create_fhe_program_node("horse", &type_name);
+        let actual = create_fhe_program_node("horse", &type_name, true);
 
         let expected = quote! {
             { struct _AssertInput where FheProgramNode<Cipher<Rational> >: Input; }
@@ -329,7 +476,7 @@ mod test {
 
         let type_name: Type = parse_quote!(#type_name);
 
-        let actual = create_fhe_program_node("horse", &type_name);
+        let actual = create_fhe_program_node("horse", &type_name, true);
 
         let expected = quote! {
             { struct _AssertInput where [FheProgramNode<Cipher<Rational> >; 7]: Input; }
@@ -347,7 +494,7 @@ mod test {
 
         let type_name: Type = parse_quote!(#type_name);
 
-        let actual = create_fhe_program_node("horse", &type_name);
+        let actual = create_fhe_program_node("horse", &type_name, true);
 
         let expected = quote! {
             { struct _AssertInput where [[FheProgramNode<Cipher<Rational> >; 7]; 6]: Input; }
@@ -439,7 +586,7 @@ mod test {
         let extracted = extract_return_types(&return_type).unwrap();
 
         let var = format_ident!("__v");
-        let actual = emit_output_capture(&var, &extracted);
+        let actual = emit_output_capture(&var, &extracted, true);
 
         let expected = quote! {
             {
@@ -462,7 +609,7 @@ mod test {
         let extracted = extract_return_types(&return_type).unwrap();
 
         let var = format_ident!("__v");
-        let actual = emit_output_capture(&var, &extracted);
+        let actual = emit_output_capture(&var, &extracted, true);
 
         let expected = quote! {
             {
@@ -0,0 +1,213 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Result};
+
+use crate::type_name::derive_typename_inner;
+
+pub fn derive_fhe_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_fhe_type_inner(input) {
+        Ok(s) => s.into(),
+        Err(e) => proc_macro::TokenStream::from(Error::into_compile_error(e)),
+    }
+}
+
+fn derive_fhe_type_inner(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => {
+                return Err(Error::new(
+                    s.fields.span(),
+                    "FheType can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "FheType can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "FheType structs must contain at least one field",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let fields_name = format_ident!("{}Fields", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics_params = &input.generics.params;
+
+    let field_names = fields
+        .iter()
+        .map(|f| f.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let field_vis = fields.iter().map(|f| &f.vis).collect::<Vec<_>>();
+    let field_types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let type_name = derive_typename_inner(input.clone());
+
+    let fields_doc =
+        format!("The fields of an encrypted [`{name}`] during FHE program construction.");
+
+    Ok(quote! {
+        #type_name
+
+        impl #impl_generics sunscreen::types::NumCiphertexts for #name #ty_generics #where_clause {
+            const NUM_CIPHERTEXTS: usize = 0 #(+ <#field_types as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS)*;
+        }
+
+        impl #impl_generics sunscreen::types::TryIntoPlaintext for #name #ty_generics #where_clause {
+            fn try_into_plaintext(
+                &self,
+                params: &sunscreen::Params,
+            ) -> std::result::Result<sunscreen::Plaintext, sunscreen::RuntimeError> {
+                let mut inner = vec![];
+
+                #(
+                    match sunscreen::types::TryIntoPlaintext::try_into_plaintext(&self.#field_names, params)?.inner {
+                        sunscreen::InnerPlaintext::Seal(p) => inner.extend(p),
+                    };
+                )*
+
+                Ok(sunscreen::Plaintext {
+                    data_type: <Self as sunscreen::types::TypeName>::type_name(),
+                    inner: sunscreen::InnerPlaintext::Seal(inner),
+                })
+            }
+        }
+
+        impl #impl_generics sunscreen::types::TryFromPlaintext for #name #ty_generics #where_clause {
+            #[allow(unused_assignments)]
+            fn try_from_plaintext(
+                plaintext: &sunscreen::Plaintext,
+                params: &sunscreen::Params,
+            ) -> std::result::Result<Self, sunscreen::RuntimeError> {
+                let p = match &plaintext.inner {
+                    sunscreen::InnerPlaintext::Seal(p) => p,
+                };
+
+                if p.len() != <Self as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS {
+                    return Err(sunscreen::RuntimeError::MalformedPlaintext);
+                }
+
+                let mut offset = 0;
+
+                #(
+                    let #field_names = {
+                        let count = <#field_types as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS;
+                        let field = sunscreen::Plaintext {
+                            data_type: <#field_types as sunscreen::types::TypeName>::type_name(),
+                            inner: sunscreen::InnerPlaintext::Seal(p[offset..offset + count].to_owned()),
+                        };
+                        offset += count;
+
+                        <#field_types as sunscreen::types::TryFromPlaintext>::try_from_plaintext(&field, params)?
+                    };
+                )*
+
+                Ok(Self { #(#field_names),* })
+            }
+        }
+
+        impl #impl_generics sunscreen::FheProgramInputTrait for #name #ty_generics #where_clause {}
+
+        impl #impl_generics sunscreen::types::FheType for #name #ty_generics #where_clause {}
+
+        impl #impl_generics sunscreen::types::BfvType for #name #ty_generics #where_clause {}
+
+        #[doc = #fields_doc]
+        #[derive(Clone, Copy)]
+        #vis struct #fields_name <#generics_params> #where_clause {
+            #(
+                #[allow(missing_docs)]
+                #field_vis #field_names: sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#field_types>>,
+            )*
+        }
+
+        impl #impl_generics sunscreen::types::intern::FheStruct for #name #ty_generics #where_clause {
+            type CipherFields = #fields_name #ty_generics;
+
+            #[allow(unused_assignments)]
+            fn cipher_fields(
+                node: sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<Self>>,
+            ) -> Self::CipherFields {
+                let mut offset = 0;
+
+                #(
+                    let #field_names = {
+                        let count = <#field_types as sunscreen::types::NumCiphertexts>::NUM_CIPHERTEXTS;
+                        let field = sunscreen::types::intern::FheProgramNode::new(&node.ids[offset..offset + count]);
+                        offset += count;
+
+                        field
+                    };
+                )*
+
+                #fields_name { #(#field_names),* }
+            }
+        }
+
+        impl #impl_generics sunscreen::types::intern::Coerce<sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#name #ty_generics>>> for #fields_name #ty_generics #where_clause {
+            fn coerce(self) -> sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#name #ty_generics>> {
+                let ids = [#(self.#field_names.ids),*].concat();
+
+                sunscreen::types::intern::FheProgramNode::new(&ids)
+            }
+        }
+
+        impl #impl_generics From<#fields_name #ty_generics> for sunscreen::types::intern::FheProgramNode<sunscreen::types::Cipher<#name #ty_generics>> #where_clause {
+            fn from(fields: #fields_name #ty_generics) -> Self {
+                sunscreen::types::intern::Coerce::coerce(fields)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn derives_named_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Order {
+                price: Fractional<64>,
+                qty: Signed,
+            }
+        };
+
+        let output = derive_fhe_type_inner(input).unwrap();
+
+        syn::parse2::<syn::File>(output.clone()).unwrap();
+        assert!(output.to_string().contains("struct OrderFields"));
+    }
+
+    #[test]
+    fn rejects_tuple_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Order(Signed, Signed);
+        };
+
+        assert!(derive_fhe_type_inner(input).is_err());
+    }
+
+    #[test]
+    fn rejects_enum() {
+        let input: DeriveInput = parse_quote! {
+            enum Order {
+                A,
+            }
+        };
+
+        assert!(derive_fhe_type_inner(input).is_err());
+    }
+}
@@ -7,11 +7,14 @@
 extern crate proc_macro;
 
 mod error;
+mod fhe_function;
 mod fhe_program;
 mod fhe_program_transforms;
+mod fhe_type;
 mod internals;
 mod type_name;
 mod zkp_program;
+mod zkp_type;
 
 #[proc_macro_derive(TypeName)]
 /**
@@ -21,6 +24,85 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
     type_name::derive_typename(input)
 }
 
+#[proc_macro_derive(FheType)]
+/**
+ * Allows you to `#[derive(FheType)]` on a struct whose fields are all
+ * FHE types (e.g. `Signed`, `Fractional<64>`, or other derived structs).
+ * The struct can then be used as an argument or return value of an
+ * [`fhe_program`](macro@fhe_program) and encrypted or decrypted as a
+ * single unit, with each field occupying its own ciphertexts.
+ *
+ * The macro also generates a `<Name>Fields` struct. Calling `fields()`
+ * on a `Cipher<Name>` inside an FHE program splits it into its encrypted
+ * fields, and a `<Name>Fields` value can be returned directly from a
+ * program (or converted with `into()`) to reassemble it.
+ *
+ * The struct should also derive `Clone` and `Copy`.
+ *
+ * # Examples
+ * ```rust,ignore
+ * # use sunscreen::{fhe_program, types::{bfv::{Fractional, Signed}, Cipher}, FheType};
+ * #[derive(Debug, Clone, Copy, FheType)]
+ * struct Order {
+ *     price: Fractional<64>,
+ *     qty: Signed,
+ * }
+ *
+ * #[fhe_program(scheme = "bfv")]
+ * fn restock(order: Cipher<Order>, amount: Signed) -> Cipher<Order> {
+ *     let order = order.fields();
+ *
+ *     OrderFields {
+ *         price: order.price,
+ *         qty: order.qty + amount,
+ *     }
+ * }
+ * ```
+ */
+pub fn derive_fhe_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    fhe_type::derive_fhe_type(input)
+}
+
+#[proc_macro_derive(ZkpType)]
+/**
+ * Allows you to `#[derive(ZkpType)]` on a struct whose fields are all
+ * ZKP types (e.g. `Field<F>`, `ZkpBool<F>`, arrays of them or other
+ * derived structs). The struct can then be a public, private or
+ * constant argument of a [`zkp_program`](macro@zkp_program) and passed
+ * to the runtime's prove and verify methods as a single input, with
+ * its fields laid out in declaration order.
+ *
+ * The macro also generates a `<Name>Fields` struct. Calling `fields()`
+ * on a `ProgramNode<Name>` inside a ZKP program splits it into its
+ * fields, and a `<Name>Fields` value can be converted back with
+ * `into()`.
+ *
+ * The struct should also derive `Clone`. The macro implements `Copy`,
+ * as `#[derive(Copy)]` would require the field type parameter to be
+ * `Copy`.
+ *
+ * # Examples
+ * ```rust,ignore
+ * # use sunscreen::{types::zkp::{ConstrainCmp, Field}, zkp_program, FieldSpec, ZkpType};
+ * #[derive(Clone, ZkpType)]
+ * struct Transaction<F: FieldSpec> {
+ *     amount: Field<F>,
+ *     nonce: Field<F>,
+ *     sender: Field<F>,
+ * }
+ *
+ * #[zkp_program]
+ * fn spend<F: FieldSpec>(tx: Transaction<F>, #[public] balance: Field<F>) {
+ *     let tx = tx.fields();
+ *
+ *     tx.amount.constrain_le_bounded(balance, 64);
+ * }
+ * ```
+ */
+pub fn derive_zkp_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    zkp_type::derive_zkp_type(input)
+}
+
 #[proc_macro_attribute]
 /**
  * Specifies a function to be an [`fhe_program`](macro@fhe_program). An [`fhe_program`](macro@fhe_program) has any number of inputs that impl the
@@ -59,6 +141,41 @@ pub fn derive_typename(input: proc_macro::TokenStream) -> proc_macro::TokenStrea
  *   (a + b, b + c)
  * }
  * ```
+ *
+ * # Generics
+ * [`fhe_program`](macro@fhe_program)s may be generic over types and
+ * constants. A non-generic program is a constant you pass directly
+ * (`Compiler::new().fhe_program(multiply)`), but Rust constants can't be
+ * generic, so the macro instead emits a function of the same name that
+ * returns the given instantiation. Note the trailing call:
+ * `Compiler::new().fhe_program(dot::<16, Signed>())`, not
+ * `dot::<16, Signed>`. Use the same call to look the program up with
+ * `Application::get_fhe_program`.
+ *
+ * Each instantiation is compiled separately and is named after its
+ * generic arguments, e.g. `dot::<16, sunscreen::types::bfv::signed::Signed>`.
+ * Type arguments are named by their `TypeName`, so they must implement
+ * it, as all FHE types do.
+ *
+ * Type parameters must be bounded by the operations the program
+ * performs (see `sunscreen::types::ops`).
+ *
+ * ```rust,ignore
+ * # use sunscreen::{fhe_program, types::{ops::{GraphCipherAdd, GraphCipherMul}, Cipher, FheType}};
+ * #[fhe_program(scheme = "bfv")]
+ * fn dot<const N: usize, T>(a: [Cipher<T>; N], b: [Cipher<T>; N]) -> Cipher<T>
+ * where
+ *     T: FheType + Copy + GraphCipherMul<Left = T, Right = T> + GraphCipherAdd<Left = T, Right = T>,
+ * {
+ *     let mut sum = a[0] * b[0];
+ *
+ *     for i in 1..N {
+ *         sum = sum + a[i] * b[i];
+ *     }
+ *
+ *     sum
+ * }
+ * ```
  */
 pub fn fhe_program(
     metadata: proc_macro::TokenStream,
@@ -67,6 +184,42 @@ pub fn fhe_program(
     fhe_program::fhe_program_impl(metadata, input)
 }
 
+#[proc_macro_attribute]
+/**
+ * Specifies a reusable function whose graph is inlined into any
+ * [`fhe_program`](macro@fhe_program) (or other `fhe_function`) that
+ * calls it. Arguments and return values follow the same rules as an
+ * [`fhe_program`](macro@fhe_program)'s, and the function may be
+ * generic.
+ *
+ * `fhe_function`s can only be called while an FHE program is being
+ * built. Nodes they create record the function's name as their
+ * provenance, which validation errors report alongside the node.
+ *
+ * An [`fhe_program`](macro@fhe_program) can likewise be inlined into
+ * another by calling its `inline` method, e.g. `my_program.inline(a, b)`.
+ *
+ * # Examples
+ * ```rust,ignore
+ * # use sunscreen::{fhe_function, fhe_program, types::{bfv::Signed, Cipher}};
+ * #[fhe_function]
+ * fn square(x: Cipher<Signed>) -> Cipher<Signed> {
+ *   x * x
+ * }
+ *
+ * #[fhe_program(scheme = "bfv")]
+ * fn sum_of_squares(a: Cipher<Signed>, b: Cipher<Signed>) -> Cipher<Signed> {
+ *   square(a) + square(b)
+ * }
+ * ```
+ */
+pub fn fhe_function(
+    metadata: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    fhe_function::fhe_function_impl(metadata, input)
+}
+
 #[proc_macro_attribute]
 /**
  * Specifies a function to be a ZKP program. TODO: docs.
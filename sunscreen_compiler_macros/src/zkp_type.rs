@@ -0,0 +1,245 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Fields, Result,
+    Type,
+};
+
+use crate::type_name::derive_typename_inner;
+
+pub fn derive_zkp_type(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match derive_zkp_type_inner(input) {
+        Ok(s) => s.into(),
+        Err(e) => proc_macro::TokenStream::from(Error::into_compile_error(e)),
+    }
+}
+
+/**
+ * Maps a field's type `T` to `ProgramNode<T>`, or an array of them when
+ * `T` is an array, matching how `#[zkp_program]` lifts its arguments.
+ */
+fn lift_field_type(ty: &Type) -> Result<Type> {
+    match ty {
+        Type::Path(p) => Ok(parse_quote! { sunscreen::types::zkp::ProgramNode<#p> }),
+        Type::Array(a) => {
+            let inner = lift_field_type(&a.elem)?;
+            let len = &a.len;
+
+            Ok(parse_quote! { [#inner; #len] })
+        }
+        _ => Err(Error::new(
+            ty.span(),
+            "ZkpType fields must be ZKP types or arrays of them",
+        )),
+    }
+}
+
+fn derive_zkp_type_inner(input: DeriveInput) -> Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(f) => &f.named,
+            _ => {
+                return Err(Error::new(
+                    s.fields.span(),
+                    "ZkpType can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.ident.span(),
+                "ZkpType can only be derived for structs",
+            ))
+        }
+    };
+
+    if fields.is_empty() {
+        return Err(Error::new(
+            input.ident.span(),
+            "ZkpType structs must contain at least one field",
+        ));
+    }
+
+    let name = &input.ident;
+    let vis = &input.vis;
+    let fields_name = format_ident!("{}Fields", name);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let generics_params = &input.generics.params;
+
+    let field_names = fields
+        .iter()
+        .map(|f| f.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let field_vis = fields.iter().map(|f| &f.vis).collect::<Vec<_>>();
+    let field_types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+    let lifted_types = field_types
+        .iter()
+        .copied()
+        .map(lift_field_type)
+        .collect::<Result<Vec<_>>>()?;
+
+    // The fields are ZKP types, which are Copy regardless of F. Deriving
+    // Copy would needlessly require F: Copy, so bound on the fields.
+    let mut copy_generics = input.generics.clone();
+    let copy_where = copy_generics.make_where_clause();
+
+    for t in &field_types {
+        copy_where.predicates.push(parse_quote! { #t: Copy });
+    }
+
+    let (_, _, copy_where_clause) = copy_generics.split_for_impl();
+
+    let mut fields_copy_generics = input.generics.clone();
+    let fields_copy_where = fields_copy_generics.make_where_clause();
+
+    for t in &lifted_types {
+        fields_copy_where.predicates.push(parse_quote! { #t: Copy });
+    }
+
+    let (_, _, fields_copy_where_clause) = fields_copy_generics.split_for_impl();
+
+    let type_name = derive_typename_inner(input.clone());
+
+    let fields_doc = format!("The fields of a [`{name}`] during ZKP program construction.");
+
+    Ok(quote! {
+        #type_name
+
+        impl #impl_generics Copy for #name #ty_generics #copy_where_clause {}
+
+        impl #impl_generics sunscreen::types::zkp::NumFieldElements for #name #ty_generics #where_clause {
+            const NUM_NATIVE_FIELD_ELEMENTS: usize = 0 #(+ <#field_types as sunscreen::types::zkp::NumFieldElements>::NUM_NATIVE_FIELD_ELEMENTS)*;
+
+            #[allow(unused_assignments)]
+            fn constrain_input(ids: &[sunscreen::types::zkp::NodeIndex]) {
+                let mut offset = 0;
+
+                #(
+                    let count = <#field_types as sunscreen::types::zkp::NumFieldElements>::NUM_NATIVE_FIELD_ELEMENTS;
+                    <#field_types as sunscreen::types::zkp::NumFieldElements>::constrain_input(&ids[offset..offset + count]);
+                    offset += count;
+                )*
+            }
+        }
+
+        impl #impl_generics sunscreen::types::zkp::ToNativeFields for #name #ty_generics #where_clause {
+            fn to_native_fields(&self) -> Vec<sunscreen::types::zkp::BigInt> {
+                let mut fields = vec![];
+
+                #(
+                    fields.extend(sunscreen::types::zkp::ToNativeFields::to_native_fields(&self.#field_names));
+                )*
+
+                fields
+            }
+        }
+
+        impl #impl_generics sunscreen::types::zkp::IntoProgramNode for #name #ty_generics #where_clause {
+            type Output = Self;
+
+            fn into_program_node(self) -> sunscreen::types::zkp::ProgramNode<Self> {
+                use sunscreen::ZkpContextOps;
+
+                let vals = sunscreen::types::zkp::ToNativeFields::to_native_fields(&self);
+
+                sunscreen::with_zkp_ctx(|ctx| {
+                    let ids = vals.iter().map(|x| ctx.add_constant(x)).collect::<Vec<_>>();
+
+                    sunscreen::types::zkp::ProgramNode::new(&ids)
+                })
+            }
+        }
+
+        #[doc = #fields_doc]
+        #vis struct #fields_name <#generics_params> #where_clause {
+            #(
+                #[allow(missing_docs)]
+                #field_vis #field_names: #lifted_types,
+            )*
+        }
+
+        impl #impl_generics Clone for #fields_name #ty_generics #fields_copy_where_clause {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl #impl_generics Copy for #fields_name #ty_generics #fields_copy_where_clause {}
+
+        impl #impl_generics sunscreen::types::zkp::ZkpStruct for #name #ty_generics #where_clause {
+            type ProgramFields = #fields_name #ty_generics;
+
+            #[allow(unused_assignments)]
+            fn program_fields(
+                node: sunscreen::types::zkp::ProgramNode<Self>,
+            ) -> Self::ProgramFields {
+                let mut offset = 0;
+
+                #(
+                    let #field_names = {
+                        let count = <#field_types as sunscreen::types::zkp::NumFieldElements>::NUM_NATIVE_FIELD_ELEMENTS;
+                        let field = <#lifted_types as sunscreen::types::zkp::ProgramNodeIds>::from_ids(&node.ids[offset..offset + count]);
+                        offset += count;
+
+                        field
+                    };
+                )*
+
+                #fields_name { #(#field_names),* }
+            }
+        }
+
+        impl #impl_generics From<#fields_name #ty_generics> for sunscreen::types::zkp::ProgramNode<#name #ty_generics> #where_clause {
+            fn from(fields: #fields_name #ty_generics) -> Self {
+                let ids = [
+                    #(sunscreen::types::zkp::ProgramNodeIds::to_ids(&fields.#field_names)),*
+                ].concat();
+
+                <#name #ty_generics as sunscreen::types::zkp::Coerce>::coerce(&ids)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derives_named_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Transaction<F: FieldSpec> {
+                amount: Field<F>,
+                nonce: Field<F>,
+                sender: [Field<F>; 2],
+            }
+        };
+
+        let output = derive_zkp_type_inner(input).unwrap();
+
+        syn::parse2::<syn::File>(output.clone()).unwrap();
+        assert!(output.to_string().contains("struct TransactionFields"));
+    }
+
+    #[test]
+    fn rejects_tuple_struct() {
+        let input: DeriveInput = parse_quote! {
+            struct Transaction<F: FieldSpec>(Field<F>, Field<F>);
+        };
+
+        assert!(derive_zkp_type_inner(input).is_err());
+    }
+
+    #[test]
+    fn rejects_unsupported_field_type() {
+        let input: DeriveInput = parse_quote! {
+            struct Transaction<F: FieldSpec> {
+                amount: (Field<F>, Field<F>),
+            }
+        };
+
+        assert!(derive_zkp_type_inner(input).is_err());
+    }
+}
@@ -49,9 +49,11 @@ pub enum IRError {
     IRHasCycles,
 
     /**
-     * A node in the IR has an error.
+     * A node in the IR has an error. The last value is the node's
+     * [`provenance`](sunscreen_compiler_common::NodeInfo::provenance), if
+     * it was created by an inlined function.
      */
-    NodeError(Box<(NodeIndex, OpName, NodeError)>),
+    NodeError(Box<(NodeIndex, OpName, NodeError, Option<String>)>),
 }
 
 impl std::fmt::Display for IRError {
@@ -61,13 +63,13 @@ impl std::fmt::Display for IRError {
                 write!(f, "This FHE program has one or more cycles")
             }
             Self::NodeError(x) => {
-                write!(
-                    f,
-                    "Node {}:{} encountered an error: {}",
-                    x.1,
-                    x.0.index(),
-                    x.2
-                )
+                write!(f, "Node {}:{}", x.1, x.0.index())?;
+
+                if let Some(provenance) = &x.3 {
+                    write!(f, " (inlined from {provenance})")?;
+                }
+
+                write!(f, " encountered an error: {}", x.2)
             }
         }
     }
@@ -78,7 +80,21 @@ impl IRError {
      * Creates an [`IRError::NodeError`].
      */
     pub fn node_error(node_id: NodeIndex, op: OpName, inner: NodeError) -> Self {
-        Self::NodeError(Box::new((node_id, op, inner)))
+        Self::NodeError(Box::new((node_id, op, inner, None)))
+    }
+
+    /**
+     * Records the path of inlined functions that created the node this
+     * error refers to. Does nothing for other errors.
+     */
+    pub fn with_provenance(self, provenance: Option<String>) -> Self {
+        match self {
+            Self::NodeError(mut x) => {
+                x.3 = provenance;
+                Self::NodeError(x)
+            }
+            x => x,
+        }
     }
 }
 
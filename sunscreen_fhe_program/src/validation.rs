@@ -79,7 +79,10 @@ pub(crate) fn validate_nodes(ir: &FheProgram) -> Vec<IRError> {
             errors.append(
                 &mut node_errors
                     .into_iter()
-                    .map(|e| IRError::node_error(i, node_info.operation.to_string(), e))
+                    .map(|e| {
+                        IRError::node_error(i, node_info.operation.to_string(), e)
+                            .with_provenance(node_info.provenance.clone())
+                    })
                     .collect(),
             )
         }
@@ -411,4 +414,43 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn errors_report_node_provenance() {
+        let ir_str = serde_json::json!({
+          "data": "Bfv",
+          "graph": {
+            "nodes": [
+              {
+                "operation": {
+                  "InputCiphertext": 0
+                }
+              },
+              {
+                "operation": "Add",
+                "provenance": "norm::square"
+              }
+            ],
+            "node_holes": [],
+            "edge_property": "directed",
+            "edges": [
+              [
+                0,
+                1,
+                "Left"
+              ],
+            ]
+          }
+        });
+
+        let ir: FheProgram = serde_json::from_value(ir_str).unwrap();
+
+        let errors = validate_ir(&ir);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "Node Add:1 (inlined from norm::square) encountered an error: Incorrect operand count. Expected 2. Found 1."
+        );
+    }
 }
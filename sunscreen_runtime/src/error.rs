@@ -72,6 +72,14 @@ pub enum Error {
     #[error("Too much noise")]
     TooMuchNoise,
 
+    /**
+     * Decoding a plaintext found digits that likely overflowed the plain
+     * modulus or the type's representation, so the decoded value would be
+     * wrong.
+     */
+    #[error("Decoded value likely overflowed: {0}")]
+    PlaintextOverflow(Box<String>),
+
     /**
      * Executing an Fhe Program failed.
      */
@@ -153,6 +161,13 @@ impl Error {
         Self::FheTypeError(Box::new(msg.to_owned()))
     }
 
+    /**
+     * Create an [`Error::PlaintextOverflow`].
+     */
+    pub fn plaintext_overflow(msg: &str) -> Self {
+        Self::PlaintextOverflow(Box::new(msg.to_owned()))
+    }
+
     /**
      * Create an [`Error::ZkpBuilderError`].
      */
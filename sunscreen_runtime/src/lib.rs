@@ -11,6 +11,7 @@ mod run;
 mod runtime;
 mod serialization;
 
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 pub use crate::error::*;
@@ -21,7 +22,7 @@ pub use runtime::*;
 pub use serialization::WithContext;
 
 use seal_fhe::{Ciphertext as SealCiphertext, Plaintext as SealPlaintext};
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as DeError, Deserialize, Deserializer, Serialize, Serializer};
 use sunscreen_zkp_backend::BigInt;
 
 #[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, Eq)]
@@ -177,6 +178,124 @@ pub struct Ciphertext {
     pub inner: InnerCiphertext,
 }
 
+impl AsRef<Ciphertext> for Ciphertext {
+    fn as_ref(&self) -> &Ciphertext {
+        self
+    }
+}
+
+/**
+ * A [`Ciphertext`] that statically knows it encrypts a value of type `T`.
+ *
+ * # Remarks
+ * [`Ciphertext`] only carries its type at runtime in its `data_type`
+ * field, so passing the wrong value to [`GenericRuntime::run`] or
+ * [`GenericRuntime::decrypt`] only fails when executed. A
+ * `TypedCiphertext<T>` moves this check to compile time and is the
+ * preferred way to handle ciphertexts. You can get one from
+ * [`GenericRuntime::encrypt_typed`] and decrypt it with
+ * [`GenericRuntime::decrypt_typed`] (or [`GenericRuntime::decrypt`]). Additionally, the `#[fhe_program]`
+ * macro generates a `run` method that accepts and returns
+ * `TypedCiphertext`s matching the program's signature.
+ *
+ * Converting an untyped [`Ciphertext`] into a `TypedCiphertext` via
+ * [`TryFrom`] validates its `data_type` and returns
+ * [`Error::TypeMismatch`] on failure.
+ *
+ * This type serializes identically to [`Ciphertext`].
+ */
+pub struct TypedCiphertext<T> {
+    inner: Ciphertext,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> TypedCiphertext<T> {
+    /**
+     * Wraps the given ciphertext without checking its type.
+     */
+    pub(crate) fn new_unchecked(inner: Ciphertext) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+
+    /**
+     * Discards the static type information and returns the underlying
+     * [`Ciphertext`].
+     */
+    pub fn into_inner(self) -> Ciphertext {
+        self.inner
+    }
+}
+
+impl<T> Clone for TypedCiphertext<T> {
+    fn clone(&self) -> Self {
+        Self::new_unchecked(self.inner.clone())
+    }
+}
+
+impl<T> AsRef<Ciphertext> for TypedCiphertext<T> {
+    fn as_ref(&self) -> &Ciphertext {
+        &self.inner
+    }
+}
+
+impl<T> TryFrom<Ciphertext> for TypedCiphertext<T>
+where
+    T: TypeName,
+{
+    type Error = Error;
+
+    fn try_from(ciphertext: Ciphertext) -> Result<Self> {
+        let expected_type = Type {
+            is_encrypted: true,
+            ..T::type_name()
+        };
+
+        if expected_type != ciphertext.data_type {
+            return Err(Error::type_mismatch(&expected_type, &ciphertext.data_type));
+        }
+
+        Ok(Self::new_unchecked(ciphertext))
+    }
+}
+
+impl<T> From<TypedCiphertext<T>> for Ciphertext {
+    fn from(val: TypedCiphertext<T>) -> Self {
+        val.inner
+    }
+}
+
+impl<T> From<TypedCiphertext<T>> for FheProgramInput {
+    fn from(val: TypedCiphertext<T>) -> Self {
+        Self::Ciphertext(val.inner)
+    }
+}
+
+impl<T> Serialize for TypedCiphertext<T> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.inner.serialize(serializer)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for TypedCiphertext<T>
+where
+    T: TypeName,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ciphertext = Ciphertext::deserialize(deserializer)?;
+
+        Self::try_from(ciphertext).map_err(D::Error::custom)
+    }
+}
+
 /**
  * A trait that denotes this type can be used as an
  * argument to an FHE program.
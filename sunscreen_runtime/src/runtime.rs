@@ -7,7 +7,7 @@ use crate::ZkpProgramInput;
 use crate::{
     run_program_unchecked, serialization::WithContext, Ciphertext, FheProgramInput,
     InnerCiphertext, InnerPlaintext, Plaintext, PrivateKey, PublicKey, SealCiphertext, SealData,
-    SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance,
+    SealPlaintext, TryFromPlaintext, TryIntoPlaintext, TypeNameInstance, TypedCiphertext,
 };
 
 use log::trace;
@@ -114,11 +114,23 @@ where
 {
     /**
      * Decrypts the given ciphertext into the type P.
+     *
+     * # Remarks
+     * This accepts both [`Ciphertext`]s and [`TypedCiphertext`]s, but
+     * checks the ciphertext's type against `P` at runtime. Prefer
+     * [`decrypt_typed`](Self::decrypt_typed) for a [`TypedCiphertext`],
+     * which infers `P` from the ciphertext.
      */
-    pub fn decrypt<P>(&self, ciphertext: &Ciphertext, private_key: &PrivateKey) -> Result<P>
+    pub fn decrypt<P>(
+        &self,
+        ciphertext: &impl AsRef<Ciphertext>,
+        private_key: &PrivateKey,
+    ) -> Result<P>
     where
         P: TryFromPlaintext + TypeName,
     {
+        let ciphertext = ciphertext.as_ref();
+
         let expected_type = Type {
             is_encrypted: true,
             ..P::type_name()
@@ -168,6 +180,25 @@ where
         Ok(val)
     }
 
+    /**
+     * Decrypts the given [`TypedCiphertext`] into its underlying type.
+     *
+     * # Remarks
+     * Unlike [`decrypt`](Self::decrypt), the type of the result is
+     * determined by the ciphertext, so decrypting as the wrong type
+     * fails to compile.
+     */
+    pub fn decrypt_typed<P>(
+        &self,
+        ciphertext: &TypedCiphertext<P>,
+        private_key: &PrivateKey,
+    ) -> Result<P>
+    where
+        P: TryFromPlaintext + TypeName,
+    {
+        self.decrypt(ciphertext, private_key)
+    }
+
     /**
      * Returns the amount of noise budget (in bits) remaining in the
      * given ciphertext.
@@ -390,6 +421,12 @@ where
      *
      * Returns [`Error::ParameterMismatch`] if the plaintext is incompatible with this runtime's
      * scheme.
+     *
+     * # Remarks
+     * The returned [`Ciphertext`] only records its type at runtime. Prefer
+     * [`encrypt_typed`](Self::encrypt_typed), whose [`TypedCiphertext`]
+     * lets the compiler check it's passed to programs and
+     * [`decrypt_typed`](Self::decrypt_typed) as the right type.
      */
     pub fn encrypt<P>(&self, val: P, public_key: &PublicKey) -> Result<Ciphertext>
     where
@@ -426,6 +463,22 @@ where
 
         Ok(ciphertext)
     }
+
+    /**
+     * Encrypts the given [`FheType`](crate::FheType) using the given public key
+     * and returns a [`TypedCiphertext`] that remembers `P` at compile time.
+     *
+     * Returns [`Error::ParameterMismatch`] if the plaintext is incompatible with this runtime's
+     * scheme.
+     */
+    pub fn encrypt_typed<P>(&self, val: P, public_key: &PublicKey) -> Result<TypedCiphertext<P>>
+    where
+        P: TryIntoPlaintext + TypeName,
+    {
+        Ok(TypedCiphertext::new_unchecked(
+            self.encrypt(val, public_key)?,
+        ))
+    }
 }
 
 impl<T, B> GenericRuntime<T, B>
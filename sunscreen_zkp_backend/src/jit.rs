@@ -564,9 +564,9 @@ where
         if let Operation::PublicInput(x) = query.get_node(id).unwrap().operation {
             let as_bigint: BigInt = public_inputs[x].clone().zkp_into();
 
-            let constraint = transforms.push(Transform::AddNode(NodeInfo {
-                operation: Operation::Constraint(as_bigint),
-            }));
+            let constraint = transforms.push(Transform::AddNode(NodeInfo::new(
+                Operation::Constraint(as_bigint),
+            )));
             transforms.push(Transform::AddEdge(
                 id.into(),
                 constraint.into(),
@@ -420,7 +420,7 @@ pub trait ZkpBackend {
  * ZKP backend. E.g. Bulletproofs uses Ristretto `Scalar`
  * values.
  */
-pub trait FieldSpec: Clone {
+pub trait FieldSpec: Clone + 'static {
     /// The underlying field type used in a backend.
     type BackendField: Add<Self::BackendField, Output = Self::BackendField>
         + Sub<Self::BackendField, Output = Self::BackendField>